@@ -0,0 +1,119 @@
+//! Timing leakage test of the constant-time hashers, following dudect
+//! (Reparaz, Balasch and Verbauwhede, "Dude, is my code constant time?").
+//!
+//! Messages are drawn either from a fixed class (always the same message) or
+//! from a random class, and the hashing times of both classes are compared
+//! with Welch's t-test. A |t| above 4.5 indicates that the running time
+//! depends on the message.
+//!
+//! The suite measured is BLS12381G2_XMD:SHA-256_SSWU_RO_ unless another one
+//! is given with --suite; the suites of prime-order groups have no
+//! constant-time hasher.
+//!
+//! Usage: cargo bench --bench dudect -- [--variable-time] [--suite NAME] [MEASUREMENTS]
+
+use std::env;
+use std::time::Instant;
+
+use h2c_rust_ref::{AnySuite, GetHashToCurve, Suite, SUITES};
+
+/// Threshold on |t| above which the timings of both classes are considered
+/// different.
+const THRESHOLD: f64 = 4.5;
+
+/// Percentiles at which the measurements are cropped, since large outliers
+/// (e.g., interrupts) hide small differences between the classes.
+const CROP: [f64; 5] = [1.0, 0.99, 0.95, 0.9, 0.75];
+
+fn main() {
+    let mut ct = true;
+    let mut count = 10_000;
+    let mut name = String::from("BLS12381G2_XMD:SHA-256_SSWU_RO_");
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--variable-time" => ct = false,
+            "--suite" => name = args.next().expect("--suite needs a NAME"),
+            "--bench" => {}
+            n => count = n.parse().expect("MEASUREMENTS must be a number"),
+        }
+    }
+
+    match SUITES.get(&name) {
+        Some(AnySuite::Weierstrass(s)) => measure(s.constant_time(ct), count),
+        Some(AnySuite::Montgomery(s)) => measure(s.constant_time(ct), count),
+        Some(AnySuite::Edwards(s)) => measure(s.constant_time(ct), count),
+        Some(AnySuite::BLS12381G2(s)) => measure(s.constant_time(ct), count),
+        Some(AnySuite::Ristretto255(_) | AnySuite::Decaf448(_)) => {
+            panic!("{name} has no constant-time hasher")
+        }
+        None => panic!("unknown suite: {name}"),
+    }
+}
+
+/// Measures the hashing times of `count` messages of both classes and
+/// prints Welch's t statistic at each cropping percentile.
+fn measure<T: Copy>(suite: Suite<T>, count: usize)
+where
+    Suite<T>: GetHashToCurve,
+{
+    let dst = format!("QUUX-V01-CS02-with-{}", suite.name());
+    let h = suite.get(dst.as_bytes());
+    let mut rng = XorShift(0x2545f4914f6cdd1d);
+    let fixed = [0u8; 32];
+
+    let mut samples = [Vec::with_capacity(count), Vec::with_capacity(count)];
+    for _ in 0..count {
+        let class = (rng.next() & 1) as usize;
+        let mut msg = fixed;
+        if class == 1 {
+            msg.iter_mut().for_each(|b| *b = rng.next() as u8);
+        }
+        let start = Instant::now();
+        std::hint::black_box(h.hash(std::hint::black_box(&msg)));
+        samples[class].push(start.elapsed().as_nanos() as f64);
+    }
+
+    let mut all: Vec<f64> = samples.concat();
+    all.sort_by(|a, b| a.total_cmp(b));
+    let mut max_t: f64 = 0.0;
+    println!("{} ({} measurements)", suite.name(), count);
+    for crop in CROP {
+        let limit = all[((all.len() - 1) as f64 * crop) as usize];
+        let t = welch_t(&samples[0], &samples[1], limit);
+        println!("  percentile {:>4}: t = {:>8.3}", crop * 100.0, t);
+        max_t = max_t.max(t.abs());
+    }
+    if max_t > THRESHOLD {
+        println!("leakage detected: max |t| = {:.3}", max_t);
+    } else {
+        println!("no leakage detected: max |t| = {:.3}", max_t);
+    }
+}
+
+/// Returns Welch's t statistic of the samples below `limit`.
+fn welch_t(a: &[f64], b: &[f64], limit: f64) -> f64 {
+    let stats = |v: &[f64]| {
+        let v: Vec<_> = v.iter().filter(|&&x| x <= limit).collect();
+        let n = v.len() as f64;
+        let mean = v.iter().copied().sum::<f64>() / n;
+        let var = v.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+        (n, mean, var)
+    };
+    let (n0, m0, v0) = stats(a);
+    let (n1, m1, v1) = stats(b);
+    (m0 - m1) / (v0 / n0 + v1 / n1).sqrt()
+}
+
+/// XorShift is a small pseudo-random generator, good enough to pick classes
+/// and random messages.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -1,8 +1,9 @@
-use criterion::{criterion_group, criterion_main, Criterion};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
 
 use h2c_rust_ref::{
-    GetHashToCurve, P256_XMDSHA256_SSWU_NU_, P256_XMDSHA256_SSWU_RO_, P384_XMDSHA384_SSWU_NU_,
-    P384_XMDSHA384_SSWU_RO_, P521_XMDSHA512_SSWU_NU_, P521_XMDSHA512_SSWU_RO_,
+    GetHashToCurve, BLS12381G2_XMDSHA256_SSWU_RO_, P256_XMDSHA256_SSWU_NU_,
+    P256_XMDSHA256_SSWU_RO_, P384_XMDSHA384_SSWU_NU_, P384_XMDSHA384_SSWU_RO_,
+    P521_XMDSHA512_SSWU_NU_, P521_XMDSHA512_SSWU_RO_,
 };
 
 fn h2c(c: &mut Criterion) {
@@ -13,12 +14,12 @@ fn h2c(c: &mut Criterion) {
     group.sample_size(10);
 
     for suite in [
-        P256_XMDSHA256_SSWU_RO_,
-        P384_XMDSHA384_SSWU_RO_,
-        P521_XMDSHA512_SSWU_RO_,
-        P256_XMDSHA256_SSWU_NU_,
-        P384_XMDSHA384_SSWU_NU_,
-        P521_XMDSHA512_SSWU_NU_,
+        &P256_XMDSHA256_SSWU_RO_,
+        &P384_XMDSHA384_SSWU_RO_,
+        &P521_XMDSHA512_SSWU_RO_,
+        &P256_XMDSHA256_SSWU_NU_,
+        &P384_XMDSHA384_SSWU_NU_,
+        &P521_XMDSHA512_SSWU_NU_,
     ]
     .iter()
     {
@@ -30,5 +31,40 @@ fn h2c(c: &mut Criterion) {
     group.finish()
 }
 
-criterion_group!(h2c_bench, h2c);
+fn batch(c: &mut Criterion) {
+    let msgs: Vec<Vec<u8>> = (0..64u32).map(|i| i.to_be_bytes().to_vec()).collect();
+    let msgs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+    let dst = b"domain separation tag";
+
+    let mut group = c.benchmark_group("Batch");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(msgs.len() as u64));
+
+    let h = P256_XMDSHA256_SSWU_RO_.get(dst);
+    group.bench_function("P256_XMD:SHA-256_SSWU_RO_/hash", |b| {
+        b.iter(|| msgs.iter().map(|m| h.hash(m)).collect::<Vec<_>>())
+    });
+    group.bench_function("P256_XMD:SHA-256_SSWU_RO_/hash_batch", |b| {
+        b.iter(|| h.hash_batch(&msgs))
+    });
+
+    let h = BLS12381G2_XMDSHA256_SSWU_RO_.get(dst);
+    group.bench_function("BLS12381G2_XMD:SHA-256_SSWU_RO_/hash", |b| {
+        b.iter(|| {
+            msgs.iter()
+                .map(|m| {
+                    let mut p = h.hash(m);
+                    p.normalize();
+                    p
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("BLS12381G2_XMD:SHA-256_SSWU_RO_/hash_batch", |b| {
+        b.iter(|| h.hash_batch(&msgs))
+    });
+    group.finish()
+}
+
+criterion_group!(h2c_bench, h2c, batch);
 criterion_main!(h2c_bench);
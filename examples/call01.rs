@@ -8,12 +8,12 @@ fn main() {
     let msg = b"This is a message string";
     let dst = b"QUUX-V01-CS02";
     for suite in [
-        EDWARDS25519_XMDSHA256_ELL2_NU_,
-        EDWARDS25519_XMDSHA256_ELL2_RO_,
-        EDWARDS25519_XMDSHA512_ELL2_NU_,
-        EDWARDS25519_XMDSHA512_ELL2_RO_,
-        EDWARDS448_XOFSHAKE256_ELL2_NU_,
-        EDWARDS448_XOFSHAKE256_ELL2_RO_,
+        &EDWARDS25519_XMDSHA256_ELL2_NU_,
+        &EDWARDS25519_XMDSHA256_ELL2_RO_,
+        &EDWARDS25519_XMDSHA512_ELL2_NU_,
+        &EDWARDS25519_XMDSHA512_ELL2_RO_,
+        &EDWARDS448_XOFSHAKE256_ELL2_NU_,
+        &EDWARDS448_XOFSHAKE256_ELL2_RO_,
     ]
     .iter()
     {
@@ -1,39 +1,317 @@
-use redox_ecc::ellipticcurve::{EllipticCurve, MapToCurve};
-use redox_ecc::field::Field;
-use redox_ecc::instances::GetCurve;
+use std::borrow::Cow;
+use std::sync::OnceLock;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+use rand_core::RngCore;
+
+use crate::curve::{EcPoint, EllipticCurve, MapToCurve};
+use crate::error::Error;
+use crate::expander::ExpanderStream;
+use crate::field::Field;
+use crate::squared;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HashID {
     SHA256,
     SHA384,
     SHA512,
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum XofID {
     SHAKE128,
     SHAKE256,
 }
 
 /// HashToField hashes a string msg of any length into an element of a field F.
-pub trait HashToField {
+pub trait HashToField: Send + Sync {
     type F: Field;
-    fn hash(&self, msg: &[u8], count: usize) -> Vec<<Self::F as Field>::Elt>;
+    /// Returns a stream that absorbs the message to be hashed.
+    fn stream(&self) -> Box<dyn ExpanderStream + '_>;
+    /// Outputs `count` field elements from the message absorbed by `s`.
+    fn try_hash_stream(
+        &self,
+        s: Box<dyn ExpanderStream + '_>,
+        count: usize,
+    ) -> Result<Vec<<Self::F as Field>::Elt>, Error>;
+    fn try_hash(&self, msg: &[u8], count: usize) -> Result<Vec<<Self::F as Field>::Elt>, Error> {
+        let mut s = self.stream();
+        s.update(msg);
+        self.try_hash_stream(s, count)
+    }
+    fn hash(&self, msg: &[u8], count: usize) -> Vec<<Self::F as Field>::Elt> {
+        self.try_hash(msg, count).unwrap_or_else(|e| panic!("{e}"))
+    }
 }
 
 pub trait GetHashToCurve {
     type E: EllipticCurve;
-    fn get(&self, dst: &[u8]) -> Box<dyn HashToCurve<E = Self::E> + 'static>;
+    fn try_get(&self, dst: &[u8]) -> Result<Box<dyn HashToCurve<E = Self::E> + 'static>, Error>;
+    fn get(&self, dst: &[u8]) -> Box<dyn HashToCurve<E = Self::E> + 'static> {
+        self.try_get(dst).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Hashes the messages of `msgs` in parallel, outputting the points in
+    /// the same order as `msgs`. The worker threads share one hasher and hash
+    /// a chunk of messages each with `try_hash_batch`.
+    #[cfg(feature = "rayon")]
+    fn try_par_hash(
+        &self,
+        dst: &[u8],
+        msgs: &[&[u8]],
+    ) -> Result<Vec<<Self::E as EllipticCurve>::Point>, Error> {
+        use rayon::prelude::*;
+        let h = self.try_get(dst)?;
+        let h: &dyn HashToCurve<E = Self::E> = &*h;
+        let chunk = msgs.len().div_ceil(rayon::current_num_threads()).max(1);
+        let batches = msgs
+            .par_chunks(chunk)
+            .map(|c| h.try_hash_batch(c))
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(batches.into_iter().flatten().collect())
+    }
+    #[cfg(feature = "rayon")]
+    fn par_hash(&self, dst: &[u8], msgs: &[&[u8]]) -> Vec<<Self::E as EllipticCurve>::Point> {
+        self.try_par_hash(dst, msgs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+/// ClearCofactor is a method for clearing the cofactor of points of a curve,
+/// i.e., for mapping them to the prime-order subgroup. Besides multiplying by
+/// the (effective) cofactor, curves may implement faster methods based on
+/// endomorphisms, as allowed by Section 7 of the draft.
+pub trait ClearCofactor: Send + Sync {
+    type Point;
+    fn clear_cofactor(&self, p: Self::Point) -> Self::Point;
+}
+
+/// InverseMap inverts a map to curve: given a point P and a branch, it outputs
+/// a field element u that the map sends to P, if the branch has one. Each
+/// point has at most one preimage per branch.
+pub trait InverseMap: Send + Sync {
+    type E: EllipticCurve;
+    /// Returns the number of branches.
+    fn branches(&self) -> usize;
+    /// Outputs the preimage of `p` in the given branch. The output must still
+    /// be checked against the map, since the exceptional cases of the map
+    /// are not inverted.
+    fn preimage(
+        &self,
+        p: &<Self::E as EllipticCurve>::Point,
+        branch: usize,
+    ) -> Option<<<Self::E as EllipticCurve>::F as Field>::Elt>;
 }
+
 /// HashToCurve is a function that outputs a point on an elliptic curve from an
-/// arbitrary string.
-pub trait HashToCurve {
+/// arbitrary string. Hashers are Send + Sync, so threads can share one.
+pub trait HashToCurve: Send + Sync {
     type E: EllipticCurve;
     fn get_curve(&self) -> &Self::E;
     fn is_random_oracle(&self) -> bool;
-    fn hash(&self, msg: &[u8]) -> <Self::E as EllipticCurve>::Point;
+    /// Returns a stream that absorbs the message to be hashed.
+    fn stream(&self) -> Box<dyn ExpanderStream + '_>;
+    /// Outputs a point from the message absorbed by `s`.
+    fn try_hash_stream(
+        &self,
+        s: Box<dyn ExpanderStream + '_>,
+    ) -> Result<<Self::E as EllipticCurve>::Point, Error>;
+    fn try_hash(&self, msg: &[u8]) -> Result<<Self::E as EllipticCurve>::Point, Error> {
+        let mut s = self.stream();
+        s.update(msg);
+        self.try_hash_stream(s)
+    }
+    fn hash(&self, msg: &[u8]) -> <Self::E as EllipticCurve>::Point {
+        self.try_hash(msg).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Hashes every message of `msgs` as `try_hash` does. The default hashes
+    /// the messages one by one; the hashers of the crate instead share the
+    /// field inversions of their maps and of the final normalization across
+    /// the batch, and output the points normalized.
+    fn try_hash_batch(
+        &self,
+        msgs: &[&[u8]],
+    ) -> Result<Vec<<Self::E as EllipticCurve>::Point>, Error> {
+        msgs.iter().map(|msg| self.try_hash(msg)).collect()
+    }
+    fn hash_batch(&self, msgs: &[&[u8]]) -> Vec<<Self::E as EllipticCurve>::Point> {
+        self.try_hash_batch(msgs).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Same as `try_hash`, but also returns the intermediate values of the
+    /// computation. Hashers that do not expose them fail with
+    /// [`Error::UnsupportedTrace`], which is the default.
+    fn try_trace(&self, _msg: &[u8]) -> Result<CurveTrace<Self::E>, Error> {
+        Err(Error::UnsupportedTrace)
+    }
+    fn trace(&self, msg: &[u8]) -> CurveTrace<Self::E> {
+        self.try_trace(msg).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Implements hash_to_curve of Section 3 of the draft, regardless of
+    /// whether the suite is a random oracle.
+    fn try_hash_to_curve(&self, msg: &[u8]) -> Result<<Self::E as EllipticCurve>::Point, Error>;
+    fn hash_to_curve(&self, msg: &[u8]) -> <Self::E as EllipticCurve>::Point {
+        self.try_hash_to_curve(msg)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Implements encode_to_curve of Section 3 of the draft, regardless of
+    /// whether the suite is a random oracle.
+    fn try_encode_to_curve(&self, msg: &[u8]) -> Result<<Self::E as EllipticCurve>::Point, Error>;
+    fn encode_to_curve(&self, msg: &[u8]) -> <Self::E as EllipticCurve>::Point {
+        self.try_encode_to_curve(msg)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Maps the field element `u` to a point on the curve, without clearing
+    /// the cofactor.
+    fn map_to_curve(
+        &self,
+        u: &<<Self::E as EllipticCurve>::F as Field>::Elt,
+    ) -> <Self::E as EllipticCurve>::Point;
+    /// Implements clear_cofactor of Section 7 of the draft, which maps a point
+    /// of the curve to its prime-order subgroup.
+    fn clear_cofactor(
+        &self,
+        p: <Self::E as EllipticCurve>::Point,
+    ) -> <Self::E as EllipticCurve>::Point;
+    /// Returns the number of branches of `inverse_map`, which is zero if the
+    /// map of the suite has no inverse.
+    fn inverse_branches(&self) -> usize;
+    /// Inverts map_to_curve: outputs a field element u such that
+    /// map_to_curve(u) = p, taken from the branch selected by `branch`, or
+    /// None if that branch has no preimage of p.
+    fn inverse_map(
+        &self,
+        p: &<Self::E as EllipticCurve>::Point,
+        branch: usize,
+    ) -> Option<<<Self::E as EllipticCurve>::F as Field>::Elt>;
+    /// Encodes p as a string of 2*m*L bytes that is indistinguishable from a
+    /// random one, using Elligator Squared with the inverse map of the suite;
+    /// m is the extension degree of the field. The encoding is randomized, so
+    /// encoding a point twice gives different strings. Fails if the map of
+    /// the suite has no inverse.
+    fn try_encode_uniform(
+        &self,
+        p: &<Self::E as EllipticCurve>::Point,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u8>, Error>;
+    fn encode_uniform(
+        &self,
+        p: &<Self::E as EllipticCurve>::Point,
+        rng: &mut dyn RngCore,
+    ) -> Vec<u8> {
+        self.try_encode_uniform(p, rng)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Decodes a string output by `encode_uniform`. Any string of 2*m*L bytes
+    /// is decoded into a point; other lengths give `Error::InvalidLength`.
+    fn try_decode_uniform(&self, b: &[u8]) -> Result<<Self::E as EllipticCurve>::Point, Error>;
+    fn decode_uniform(&self, b: &[u8]) -> <Self::E as EllipticCurve>::Point {
+        self.try_decode_uniform(b).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// Hasher hashes to a curve a message given in chunks, so that long
+/// messages need not be held in memory.
+///
+/// ```
+///  use h2c_rust_ref::{GetHashToCurve, Hasher, P256_XMDSHA256_SSWU_RO_};
+///  let h2c = P256_XMDSHA256_SSWU_RO_.get(b"Domain separation tag");
+///  let mut hasher = Hasher::new(&*h2c);
+///  hasher.update(b"Message ");
+///  hasher.update(b"string");
+///  assert!(hasher.finalize() == h2c.hash(b"Message string"));
+/// ```
+pub struct Hasher<'a, E: EllipticCurve> {
+    h2c: &'a dyn HashToCurve<E = E>,
+    stream: Box<dyn ExpanderStream + 'a>,
 }
 
+impl<'a, E: EllipticCurve> Hasher<'a, E> {
+    pub fn new(h2c: &'a dyn HashToCurve<E = E>) -> Self {
+        Hasher {
+            h2c,
+            stream: h2c.stream(),
+        }
+    }
+    /// Appends `chunk` to the message.
+    pub fn update(&mut self, chunk: &[u8]) {
+        self.stream.update(chunk)
+    }
+    /// Outputs the point corresponding to the concatenation of all the chunks.
+    pub fn try_finalize(self) -> Result<E::Point, Error> {
+        self.h2c.try_hash_stream(self.stream)
+    }
+    pub fn finalize(self) -> E::Point {
+        self.try_finalize().unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<E: EllipticCurve> std::io::Write for Hasher<'_, E> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Trace holds the values computed by a call to hash_to_curve (or
+/// encode_to_curve), following the notation of Section 3 of the draft.
+#[derive(Clone, Debug)]
+pub struct Trace<Elt, Point> {
+    /// Field elements output by hash_to_field: two for random-oracle
+    /// encodings (u0, u1), and one otherwise.
+    pub u: Vec<Elt>,
+    /// Points output by map_to_curve for each element of `u`, i.e., Q0 and Q1
+    /// (or Q for nonuniform encodings).
+    pub q: Vec<Point>,
+    /// Sum of the points in `q`, before clearing the cofactor.
+    pub r: Point,
+    /// Output point, i.e., the result of clear_cofactor(R).
+    pub p: Point,
+}
+
+pub type CurveTrace<E> =
+    Trace<<<E as EllipticCurve>::F as Field>::Elt, <E as EllipticCurve>::Point>;
+
+/// CtEncoder hashes messages to a curve in constant time, on behalf of an
+/// [`Encoding`] whose suite asked for it.
+pub(crate) trait CtEncoder: Send + Sync {
+    type Point;
+    /// Outputs hash_to_curve (if `ro` is set) or encode_to_curve of the
+    /// message absorbed by `s`.
+    fn encode(&self, s: Box<dyn ExpanderStream + '_>, ro: bool) -> Result<Self::Point, Error>;
+}
+
+/// InverseMapBuilder builds the inverse map of a suite, which may fail if the
+/// parameters of the suite admit no inverse.
+pub(crate) type InverseMapBuilder<EE> =
+    Box<dyn Fn() -> Result<Box<dyn InverseMap<E = EE>>, Error> + Send + Sync>;
+
+/// LazyInverseMap builds an inverse map on its first use, since building it
+/// takes square roots or parses an isogeny, and most hashers never invert.
+pub(crate) struct LazyInverseMap<EE: EllipticCurve> {
+    build: Option<InverseMapBuilder<EE>>,
+    map: OnceLock<Result<Box<dyn InverseMap<E = EE>>, Error>>,
+}
+
+impl<EE: EllipticCurve> LazyInverseMap<EE> {
+    /// Returns the inverse map built by `build`, or no inverse map if None.
+    pub(crate) fn new(build: Option<InverseMapBuilder<EE>>) -> Self {
+        LazyInverseMap {
+            build,
+            map: OnceLock::new(),
+        }
+    }
+    fn get(&self) -> Result<&dyn InverseMap<E = EE>, Error> {
+        let build = self.build.as_ref().ok_or(Error::UnsupportedInverseMap)?;
+        match self.map.get_or_init(build) {
+            Ok(m) => Ok(&**m),
+            Err(e) => Err(e.clone()),
+        }
+    }
+}
+
+/// Encoding is the hasher of the suites of the crate. For constant-time
+/// suites, hashing goes through `ct`, whereas `trace` and `map_to_curve` do
+/// not.
 pub(crate) struct Encoding<EE>
 where
     EE: EllipticCurve,
@@ -41,14 +319,15 @@ where
     pub(crate) curve: EE,
     pub(crate) map_to_curve: Box<dyn MapToCurve<E = EE> + 'static>,
     pub(crate) hash_to_field: Box<dyn HashToField<F = <EE as EllipticCurve>::F> + 'static>,
-    pub(crate) cofactor: <EE as EllipticCurve>::Scalar,
+    pub(crate) clear_cofactor:
+        Box<dyn ClearCofactor<Point = <EE as EllipticCurve>::Point> + 'static>,
+    pub(crate) inverse_map: LazyInverseMap<EE>,
+    pub(crate) l: usize,
     pub(crate) ro: bool,
+    pub(crate) ct: Option<Box<dyn CtEncoder<Point = <EE as EllipticCurve>::Point> + 'static>>,
 }
 
-impl<EE> HashToCurve for Encoding<EE>
-where
-    EE: EllipticCurve + Clone,
-{
+impl<EE: EllipticCurve> HashToCurve for Encoding<EE> {
     type E = EE;
     #[inline]
     fn get_curve(&self) -> &Self::E {
@@ -58,53 +337,325 @@ where
     fn is_random_oracle(&self) -> bool {
         self.ro
     }
-    fn hash(&self, msg: &[u8]) -> <Self::E as EllipticCurve>::Point {
-        let p = if self.ro {
-            let u = self.hash_to_field.hash(msg, 2);
+    fn stream(&self) -> Box<dyn ExpanderStream + '_> {
+        self.hash_to_field.stream()
+    }
+    fn try_hash_stream(
+        &self,
+        s: Box<dyn ExpanderStream + '_>,
+    ) -> Result<<Self::E as EllipticCurve>::Point, Error> {
+        self.encode(s, self.ro)
+    }
+    fn try_hash_batch(
+        &self,
+        msgs: &[&[u8]],
+    ) -> Result<Vec<<Self::E as EllipticCurve>::Point>, Error> {
+        if self.ct.is_some() {
+            return msgs.iter().map(|msg| self.try_hash(msg)).collect();
+        }
+        let count = if self.ro { 2 } else { 1 };
+        let mut u = Vec::with_capacity(count * msgs.len());
+        for msg in msgs {
+            u.extend(self.hash_to_field.try_hash(msg, count)?);
+        }
+        let q = self.map_to_curve.map_batch(&u);
+        let mut p: Vec<_> = q
+            .chunks(count)
+            .map(|q| {
+                let r = q[1..].iter().fold(q[0].clone(), |r, qi| r + qi.clone());
+                self.clear_cofactor.clear_cofactor(r)
+            })
+            .collect();
+        EE::Point::normalize_batch(&mut p);
+        Ok(p)
+    }
+    fn try_trace(&self, msg: &[u8]) -> Result<CurveTrace<Self::E>, Error> {
+        let count = if self.ro { 2 } else { 1 };
+        let u = self.hash_to_field.try_hash(msg, count)?;
+        let q = u
+            .iter()
+            .map(|ui| self.map_to_curve.map(ui))
+            .collect::<Vec<_>>();
+        let r = q[1..].iter().fold(q[0].clone(), |r, qi| r + qi.clone());
+        let p = self.clear_cofactor.clear_cofactor(r.clone());
+        Ok(Trace { u, q, r, p })
+    }
+    fn try_hash_to_curve(&self, msg: &[u8]) -> Result<<Self::E as EllipticCurve>::Point, Error> {
+        let mut s = self.stream();
+        s.update(msg);
+        self.encode(s, true)
+    }
+    fn try_encode_to_curve(&self, msg: &[u8]) -> Result<<Self::E as EllipticCurve>::Point, Error> {
+        let mut s = self.stream();
+        s.update(msg);
+        self.encode(s, false)
+    }
+    fn map_to_curve(
+        &self,
+        u: &<<Self::E as EllipticCurve>::F as Field>::Elt,
+    ) -> <Self::E as EllipticCurve>::Point {
+        self.map_to_curve.map(u)
+    }
+    fn clear_cofactor(
+        &self,
+        p: <Self::E as EllipticCurve>::Point,
+    ) -> <Self::E as EllipticCurve>::Point {
+        self.clear_cofactor.clear_cofactor(p)
+    }
+    fn inverse_branches(&self) -> usize {
+        self.inverse_map.get().map_or(0, |m| m.branches())
+    }
+    fn inverse_map(
+        &self,
+        p: &<Self::E as EllipticCurve>::Point,
+        branch: usize,
+    ) -> Option<<<Self::E as EllipticCurve>::F as Field>::Elt> {
+        let u = self.inverse_map.get().ok()?.preimage(p, branch)?;
+        (self.map_to_curve.map(&u) == *p).then_some(u)
+    }
+    fn try_encode_uniform(
+        &self,
+        p: &<Self::E as EllipticCurve>::Point,
+        rng: &mut dyn RngCore,
+    ) -> Result<Vec<u8>, Error> {
+        // Fails with the reason the inverse map could not be built, if any.
+        self.inverse_map.get()?;
+        squared::encode(self, self.l, p, rng)
+    }
+    fn try_decode_uniform(&self, b: &[u8]) -> Result<<Self::E as EllipticCurve>::Point, Error> {
+        squared::decode(self, self.l, b)
+    }
+}
+
+impl<EE: EllipticCurve> Encoding<EE> {
+    /// Outputs hash_to_curve (if `ro` is set) or encode_to_curve of the
+    /// message absorbed by `s`.
+    fn encode(
+        &self,
+        s: Box<dyn ExpanderStream + '_>,
+        ro: bool,
+    ) -> Result<<EE as EllipticCurve>::Point, Error> {
+        if let Some(ct) = &self.ct {
+            return ct.encode(s, ro);
+        }
+        let p = if ro {
+            let u = self.hash_to_field.try_hash_stream(s, 2)?;
             let p0 = self.map_to_curve.map(&u[0]);
             let p1 = self.map_to_curve.map(&u[1]);
             p0 + p1
         } else {
-            let u = self.hash_to_field.hash(msg, 1);
+            let u = self.hash_to_field.try_hash_stream(s, 1)?;
             self.map_to_curve.map(&u[0])
         };
-        p * &self.cofactor
+        Ok(self.clear_cofactor.clear_cofactor(p))
     }
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub enum MapID {
     SSWU(i32),
     SSWUAB0(i32),
     SVDW(i32),
     ELL2(i32),
+    /// The Simplified SWU map for AB == 0 over GF(p^2), whose Z = c0 + c1*I
+    /// is given by its two components (c0, c1).
+    SSWUAB0FP2(i32, i32),
+    /// The one-way map of ristretto255 (Section 4.3.4 of RFC 9496).
+    R255MAP,
+    /// The one-way map of decaf448 (Section 5.3.4 of RFC 9496).
+    D448MAP,
 }
 
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, Debug)]
 pub enum ExpID {
     XMD(HashID),
     XOF(XofID),
 }
 
-#[derive(Copy, Clone)]
-pub struct Suite<T>
-where
-    T: GetCurve,
-{
+impl ExpID {
+    /// Returns the tag that names the expander in suite IDs, e.g.,
+    /// "XMD:SHA-256" (Section 8.10 of the draft).
+    pub fn suite_tag(&self) -> &'static str {
+        match self {
+            ExpID::XMD(HashID::SHA256) => "XMD:SHA-256",
+            ExpID::XMD(HashID::SHA384) => "XMD:SHA-384",
+            ExpID::XMD(HashID::SHA512) => "XMD:SHA-512",
+            ExpID::XOF(XofID::SHAKE128) => "XOF:SHAKE128",
+            ExpID::XOF(XofID::SHAKE256) => "XOF:SHAKE256",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Suite<T> {
     pub(super) curve: T,
-    pub(super) name: &'static str,
+    pub(super) name: Cow<'static, str>,
     pub(super) map: MapID,
     pub(super) exp: ExpID,
     pub(super) k: usize,
     pub(super) l: usize,
     pub(super) ro: bool,
+    pub(super) ct: bool,
 }
 
-impl<T> std::fmt::Display for Suite<T>
-where
-    T: GetCurve,
-{
+impl<T> Suite<T> {
+    /// Returns a builder of a suite with the given name, curve, map and
+    /// expander. By default, the suite targets k = 128 bits of security, is
+    /// not a random oracle, and derives L from k and the field of the curve.
+    pub fn builder(
+        name: impl Into<Cow<'static, str>>,
+        curve: T,
+        map: MapID,
+        exp: ExpID,
+    ) -> SuiteBuilder<T> {
+        SuiteBuilder {
+            suite: Suite {
+                curve,
+                name: name.into(),
+                map,
+                exp,
+                k: 128,
+                l: 0,
+                ro: false,
+                ct: false,
+            },
+            l: None,
+        }
+    }
+    /// Returns the suite ID, e.g., "P256_XMD:SHA-256_SSWU_RO_".
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    #[inline]
+    pub fn curve(&self) -> &T {
+        &self.curve
+    }
+    #[inline]
+    pub fn map(&self) -> MapID {
+        self.map
+    }
+    #[inline]
+    pub fn exp(&self) -> ExpID {
+        self.exp
+    }
+    /// Returns the target security level in bits.
+    #[inline]
+    pub fn k(&self) -> usize {
+        self.k
+    }
+    /// Returns the number of bytes used to derive each field element.
+    #[inline]
+    pub fn l(&self) -> usize {
+        self.l
+    }
+    #[inline]
+    pub fn is_random_oracle(&self) -> bool {
+        self.ro
+    }
+    /// Returns the same suite, whose hashers run in constant time if `ct` is
+    /// set. The suites of prime-order groups have no constant-time hasher:
+    /// getting one fails with [`Error::UnsupportedConstantTime`].
+    pub fn constant_time(&self, ct: bool) -> Self
+    where
+        T: Clone,
+    {
+        Suite { ct, ..self.clone() }
+    }
+    #[inline]
+    pub fn is_constant_time(&self) -> bool {
+        self.ct
+    }
+}
+
+impl<T> std::fmt::Display for Suite<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(f, "{}", self.name)
     }
 }
+
+/// ModulusBits is implemented by curve identifiers to report the size of the
+/// characteristic p of the field over which the curve is defined.
+pub trait ModulusBits {
+    /// Returns ceil(log2(p)).
+    fn modulus_bits(&self) -> usize;
+}
+
+/// CheckMap is implemented by curve identifiers to check that a map to curve
+/// applies to the curve.
+pub trait CheckMap {
+    /// Fails if the map is not defined for the family of the curve, or if its
+    /// Z does not meet the criteria of Appendix H of the draft.
+    fn check_map(&self, map: MapID) -> Result<(), Error>;
+}
+
+/// SuiteBuilder defines a suite from user-supplied parameters. It is obtained
+/// from [`Suite::builder`].
+#[derive(Clone)]
+pub struct SuiteBuilder<T> {
+    suite: Suite<T>,
+    l: Option<usize>,
+}
+
+impl<T: ModulusBits + CheckMap> SuiteBuilder<T> {
+    /// Sets the target security level in bits.
+    pub fn k(mut self, k: usize) -> Self {
+        self.suite.k = k;
+        self
+    }
+    /// Sets L, which must equal ceil((ceil(log2(p)) + k) / 8).
+    pub fn l(mut self, l: usize) -> Self {
+        self.l = Some(l);
+        self
+    }
+    pub fn random_oracle(mut self, ro: bool) -> Self {
+        self.suite.ro = ro;
+        self
+    }
+    /// Checks the consistency of the parameters, including the expander and
+    /// the encoding type named by the suite ID, and the map and its Z, and
+    /// returns the suite.
+    pub fn build(self) -> Result<Suite<T>, Error> {
+        let mut s = self.suite;
+        let tag = s.exp.suite_tag();
+        if !s.name.contains(&format!("_{}_", tag)) {
+            return Err(Error::InvalidSuite(format!(
+                "{}: the suite ID does not name the expander {}",
+                s.name, tag
+            )));
+        }
+        let suffix = if s.ro { "_RO_" } else { "_NU_" };
+        if !s.name.ends_with(suffix) {
+            return Err(Error::InvalidSuite(format!(
+                "{}: the suite ID of this encoding must end with {}",
+                s.name, suffix
+            )));
+        }
+        s.curve.check_map(s.map).map_err(|e| match e {
+            Error::InvalidSuite(e) => Error::InvalidSuite(format!("{}: {}", s.name, e)),
+            e => e,
+        })?;
+        let max_k = match s.exp {
+            ExpID::XMD(HashID::SHA256) | ExpID::XOF(XofID::SHAKE128) => 128,
+            ExpID::XMD(HashID::SHA384) => 192,
+            ExpID::XMD(HashID::SHA512) | ExpID::XOF(XofID::SHAKE256) => 256,
+        };
+        if s.k == 0 || s.k > max_k {
+            return Err(Error::InvalidSuite(format!(
+                "{}: the expander does not provide k = {} bits of security",
+                s.name, s.k
+            )));
+        }
+        let l = (s.curve.modulus_bits() + s.k).div_ceil(8);
+        match self.l {
+            Some(got) if got != l => Err(Error::InvalidSuite(format!(
+                "{}: L must be {}, but got {}",
+                s.name, l, got
+            ))),
+            _ => {
+                s.l = l;
+                Ok(s)
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::exit;
+
+use h2c_rust_ref::{
+    default_expanders, default_messages, expander_file_name, expander_vectors, suite_file_name,
+    suite_vectors, SUITES,
+};
+
+const USAGE: &str = "\
+Writes test vectors in the JSON format of the draft.
+
+Usage: h2c-vectors [OPTIONS] [SUITE]...
+
+Writes one file per suite, or for every suite if none is given. The DST of
+each suite is QUUX-V01-CS02-with-<SUITE>, as in the draft. With --expanders,
+writes the test vectors of the expanders of the draft instead.
+
+Options:
+  -o, --out <DIR>    Output directory [default: .]
+  -m, --msg <MSG>    Message to hash, can be repeated [default: the messages of the draft]
+  -e, --expanders    Write the test vectors of expand_message
+  -h, --help         Print this help and exit";
+
+fn main() {
+    if let Err(e) = run(env::args().skip(1)) {
+        eprintln!("h2c-vectors: {}", e);
+        exit(1)
+    }
+}
+
+fn run(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let (mut out, mut msgs, mut names) = (PathBuf::from("."), Vec::new(), Vec::new());
+    let mut expanders = false;
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", name))
+        };
+        match arg.as_str() {
+            "-o" | "--out" => out = PathBuf::from(value(&arg)?),
+            "-m" | "--msg" => msgs.push(value(&arg)?),
+            "-e" | "--expanders" => expanders = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            _ if arg.starts_with('-') => return Err(format!("unknown option: {}", arg)),
+            _ => names.push(arg),
+        }
+    }
+    if msgs.is_empty() {
+        msgs = default_messages();
+    }
+    let msgs: Vec<&str> = msgs.iter().map(|m| m.as_str()).collect();
+    let write = |name: String, json: String| {
+        let path = out.join(name);
+        fs::write(&path, json).map_err(|e| format!("{}: {}", path.display(), e))?;
+        println!("{}", path.display());
+        Ok::<(), String>(())
+    };
+    if expanders {
+        for (exp, dst, k) in default_expanders() {
+            let json =
+                expander_vectors(exp, &dst, k, &msgs, &[0x20, 0x80]).map_err(|e| e.to_string())?;
+            write(expander_file_name(exp, &dst), json)?;
+        }
+        return Ok(());
+    }
+    if names.is_empty() {
+        // The groups have no field elements, hence no vectors to write.
+        names = SUITES
+            .iter()
+            .filter(|(_, s)| !s.is_group())
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+    }
+    for name in names.iter() {
+        let suite = SUITES
+            .get(name)
+            .ok_or_else(|| format!("unknown suite: {}", name))?;
+        let dst = format!("QUUX-V01-CS02-with-{}", name);
+        let json = suite_vectors(suite, &dst, &msgs).map_err(|e| e.to_string())?;
+        write(suite_file_name(suite), json)?;
+    }
+    Ok(())
+}
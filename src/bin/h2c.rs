@@ -0,0 +1,240 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::process::exit;
+
+use h2c_rust_ref::{
+    serialize_rfc7748, serialize_rfc8032, serialize_sec1, serialize_zcash_g1, serialize_zcash_g2,
+    AnyHashToCurve, AnyPoint, AnySuite, AnyTrace, Error, SUITES,
+};
+
+const USAGE: &str = "\
+Hashes a message to a point of an elliptic curve, or to an element of a
+prime-order group, using any suite of the draft.
+
+Usage: h2c [OPTIONS] <SUITE> [MSG]
+
+The message is read from MSG, from the file given by --file, or from the
+standard input if neither is given or if MSG is \"-\". Arguments after \"--\"
+are not read as options, e.g., to hash a message that starts with \"-\".
+
+Options:
+  -d, --dst <DST>        Domain separation tag [default: QUUX-V01-CS02-with-<SUITE>]
+  -f, --file <PATH>      Read the message from a file (\"-\" for the standard input)
+  -x, --hex              The message is hex-encoded
+  -X, --dst-hex          The DST is hex-encoded
+  -o, --output <FORMAT>  Output format: json or hex [default: json]
+  -t, --trace            Also output u and the points output by map_to_curve
+  -l, --list             List the suite IDs and exit
+  -h, --help             Print this help and exit";
+
+enum Output {
+    Json,
+    Hex,
+}
+
+struct Options {
+    suite: AnySuite,
+    dst: Vec<u8>,
+    msg: Vec<u8>,
+    output: Output,
+    trace: bool,
+}
+
+fn main() {
+    match parse(env::args().skip(1)) {
+        Ok(Some(opts)) => run(&opts),
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("h2c: {}\nTry 'h2c --help' for more information.", e);
+            exit(2)
+        }
+    }
+}
+
+fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut positional = Vec::new();
+    let (mut dst, mut file) = (None, None);
+    let (mut is_hex, mut is_dst_hex) = (false, false);
+    let (mut trace, mut output) = (false, Output::Json);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or_else(|| format!("missing value for {}", name))
+        };
+        match arg.as_str() {
+            "-d" | "--dst" => dst = Some(value(&arg)?),
+            "-f" | "--file" => file = Some(value(&arg)?),
+            "-x" | "--hex" => is_hex = true,
+            "-X" | "--dst-hex" => is_dst_hex = true,
+            "-t" | "--trace" => trace = true,
+            "-o" | "--output" => {
+                output = match value(&arg)?.as_str() {
+                    "json" => Output::Json,
+                    "hex" => Output::Hex,
+                    o => return Err(format!("unknown output format: {}", o)),
+                }
+            }
+            "-l" | "--list" => {
+                let mut names: Vec<_> = SUITES.keys().collect();
+                names.sort();
+                names.iter().for_each(|name| println!("{}", name));
+                return Ok(None);
+            }
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(None);
+            }
+            "--" => {
+                positional.extend(args);
+                break;
+            }
+            _ if arg.starts_with('-') && arg != "-" => {
+                return Err(format!("unknown option: {}", arg))
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let mut positional = positional.into_iter();
+    let name = positional.next().ok_or("missing suite ID")?;
+    let suite = SUITES
+        .get(&name)
+        .ok_or_else(|| format!("unknown suite: {} (see --list)", name))?
+        .clone();
+    let decode = |s: String, is_hex: bool| {
+        if is_hex {
+            hex::decode(s.trim()).map_err(|e| format!("invalid hex string: {}", e))
+        } else {
+            Ok(s.into_bytes())
+        }
+    };
+    let dst = match dst {
+        Some(dst) => decode(dst, is_dst_hex)?,
+        None => format!("QUUX-V01-CS02-with-{}", name).into_bytes(),
+    };
+    let msg = match (positional.next(), file) {
+        (Some(_), Some(_)) => return Err("MSG and --file are mutually exclusive".into()),
+        (Some(msg), None) if msg != "-" => decode(msg, is_hex)?,
+        (msg, path) => {
+            let bytes = match path.or(msg).as_deref() {
+                Some(path) if path != "-" => {
+                    fs::read(path).map_err(|e| format!("{}: {}", path, e))?
+                }
+                _ => {
+                    let mut bytes = Vec::new();
+                    io::stdin()
+                        .read_to_end(&mut bytes)
+                        .map_err(|e| e.to_string())?;
+                    bytes
+                }
+            };
+            if is_hex {
+                decode(String::from_utf8(bytes).map_err(|e| e.to_string())?, true)?
+            } else {
+                bytes
+            }
+        }
+    };
+    if let Some(arg) = positional.next() {
+        return Err(format!("unexpected argument: {}", arg));
+    }
+    Ok(Some(Options {
+        suite,
+        dst,
+        msg,
+        output,
+        trace,
+    }))
+}
+
+fn run(opts: &Options) {
+    let h = match opts.suite.try_get(&opts.dst) {
+        Ok(h) => h,
+        Err(e) => {
+            eprintln!("h2c: {}", e);
+            exit(1)
+        }
+    };
+    let t = if opts.trace {
+        h.try_trace(&opts.msg)
+    } else {
+        // Only P is output, so suites that cannot be traced are fine.
+        h.try_hash(&opts.msg).map(|p| AnyTrace {
+            u: vec![],
+            q: vec![],
+            r: p.clone(),
+            p,
+        })
+    };
+    let t = match t {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("h2c: {}", e);
+            exit(1)
+        }
+    };
+    // Points output by map_to_curve are named as in the test vectors.
+    let names: &[&str] = if t.q.len() == 1 {
+        &["Q"]
+    } else {
+        &["Q0", "Q1"]
+    };
+    match opts.output {
+        Output::Json => {
+            let point = |p: &AnyPoint| match h.coordinates(p) {
+                Ok(Some((x, y))) => format!("{{ \"x\": \"{}\", \"y\": \"{}\" }}", x, y),
+                Ok(None) => "null".to_string(),
+                // Elements of the groups have no coordinates, only an encoding.
+                Err(Error::NoCoordinates) => format!("\"{}\"", p),
+                // The points are output by h, so they never belong to another curve.
+                Err(e) => unreachable!("{}", e),
+            };
+            let mut fields = vec![format!("\"P\": {}", point(&t.p))];
+            if opts.trace {
+                for (name, q) in names.iter().zip(&t.q) {
+                    fields.push(format!("\"{}\": {}", name, point(q)));
+                }
+                let u: Vec<_> = t.u.iter().map(|u| format!("\"{}\"", u)).collect();
+                fields.push(format!("\"u\": [{}]", u.join(", ")));
+            }
+            println!("{{\n  {}\n}}", fields.join(",\n  "));
+        }
+        Output::Hex => {
+            if opts.trace {
+                for (i, u) in t.u.iter().enumerate() {
+                    println!("u{}: {}", i, u);
+                }
+                for (name, q) in names.iter().zip(&t.q) {
+                    println!("{}: {}", name, hex::encode(serialize(&opts.suite, &h, q)));
+                }
+                println!("P: {}", hex::encode(serialize(&opts.suite, &h, &t.p)));
+            } else {
+                println!("{}", hex::encode(serialize(&opts.suite, &h, &t.p)));
+            }
+        }
+    }
+}
+
+/// Serializes a point in the compressed format customary for its curve: ZCash
+/// for BLS12-381, RFC 8032 for Edwards curves, RFC 7748 for Montgomery curves,
+/// RFC 9496 for prime-order groups, and SEC 1 otherwise.
+fn serialize(suite: &AnySuite, h: &AnyHashToCurve, p: &AnyPoint) -> Vec<u8> {
+    match (h, p) {
+        (AnyHashToCurve::Weierstrass(h), AnyPoint::Weierstrass(p)) => {
+            if suite.curve_name() == "BLS12381G1" {
+                serialize_zcash_g1(h.get_curve(), p, true)
+            } else {
+                serialize_sec1(h.get_curve(), p, true)
+            }
+        }
+        (AnyHashToCurve::Montgomery(h), AnyPoint::Montgomery(p)) => {
+            serialize_rfc7748(h.get_curve(), p)
+        }
+        (AnyHashToCurve::Edwards(h), AnyPoint::Edwards(p)) => serialize_rfc8032(h.get_curve(), p),
+        (AnyHashToCurve::BLS12381G2(_), AnyPoint::BLS12381G2(p)) => serialize_zcash_g2(p, true),
+        (AnyHashToCurve::Ristretto255(_), AnyPoint::Ristretto255(p)) => p.encode(),
+        (AnyHashToCurve::Decaf448(_), AnyPoint::Decaf448(p)) => p.encode(),
+        _ => unreachable!(),
+    }
+}
@@ -0,0 +1,145 @@
+use std::marker::PhantomData;
+
+use num_bigint::BigUint;
+
+use crate::api::ClearCofactor;
+use crate::curve::{weierstrass, EllipticCurve, GetCurve};
+use crate::field::Field;
+use crate::fp::FpElt;
+use crate::weier::WeCurveID;
+
+/// MulByCofactor clears the cofactor by multiplying by the (effective)
+/// cofactor h_eff of the curve. It works for any curve and is the fallback of
+/// the other methods.
+pub struct MulByCofactor<E: EllipticCurve> {
+    h_eff: BigUint,
+    curve: PhantomData<fn(E) -> E>,
+}
+
+impl<E: EllipticCurve> MulByCofactor<E> {
+    pub fn new(h_eff: BigUint) -> Self {
+        MulByCofactor {
+            h_eff,
+            curve: PhantomData,
+        }
+    }
+}
+
+impl<E: EllipticCurve> ClearCofactor for MulByCofactor<E> {
+    type Point = E::Point;
+    fn clear_cofactor(&self, p: E::Point) -> E::Point {
+        p * &self.h_eff
+    }
+}
+
+/// NoCofactor outputs points unchanged. It is the method of curves of prime
+/// order, such as P-256 and secp256k1, which have no cofactor to clear.
+pub struct NoCofactor<E: EllipticCurve> {
+    curve: PhantomData<fn(E) -> E>,
+}
+
+impl<E: EllipticCurve> Default for NoCofactor<E> {
+    fn default() -> Self {
+        NoCofactor { curve: PhantomData }
+    }
+}
+
+impl<E: EllipticCurve> ClearCofactor for NoCofactor<E> {
+    type Point = E::Point;
+    fn clear_cofactor(&self, p: E::Point) -> E::Point {
+        p
+    }
+}
+
+/// DoubleCofactor clears a cofactor h = 2^k by doubling k times, e.g., three
+/// times for edwards25519.
+pub struct DoubleCofactor<E: EllipticCurve> {
+    k: u32,
+    curve: PhantomData<fn(E) -> E>,
+}
+
+impl<E: EllipticCurve> DoubleCofactor<E> {
+    pub fn new(k: u32) -> Self {
+        DoubleCofactor {
+            k,
+            curve: PhantomData,
+        }
+    }
+}
+
+impl<E: EllipticCurve> ClearCofactor for DoubleCofactor<E> {
+    type Point = E::Point;
+    fn clear_cofactor(&self, p: E::Point) -> E::Point {
+        (0..self.k).fold(p, |p, _| p.clone() + p)
+    }
+}
+
+/// Absolute value of the parameter z = -0xd201000000010000 of BLS12-381.
+pub(crate) const BLS12381_Z_ABS: u64 = 0xd201000000010000;
+
+/// Bls12381G1Cofactor clears the cofactor of BLS12-381 G1 by multiplying by
+/// the effective cofactor h_eff = 1 - z of Section 8.8.1 of the draft, that
+/// is, by computing P + |z|*P with a double-and-add over the bits of |z|. No
+/// endomorphism is involved: the gain over [`MulByCofactor`] comes from
+/// h_eff being 64 bits long and sparse, whereas the cofactor has 126 bits.
+pub struct Bls12381G1Cofactor;
+
+impl ClearCofactor for Bls12381G1Cofactor {
+    type Point = weierstrass::Point;
+    fn clear_cofactor(&self, p: Self::Point) -> Self::Point {
+        let mut q = p.clone();
+        for i in (0..63).rev() {
+            q = q.clone() + q;
+            if (BLS12381_Z_ABS >> i) & 1 == 1 {
+                q = q + p.clone();
+            }
+        }
+        q + p
+    }
+}
+
+/// SigmaCofactor clears the cofactor of a curve y^2 = x^3 + B with the
+/// endomorphism sigma(x, y) = (beta * x, y), where beta is a cube root of
+/// unity, by computing m * (P - sigma(P)). This clears the cofactor when
+/// m * (1 - sigma) annihilates the points of order dividing it, e.g., for
+/// BLS12-381 G1 with m = (1 - z)/3.
+///
+/// On the points of prime order r, sigma multiplies by some lambda, so the
+/// output is (1 - lambda)/3 times that of [`Bls12381G1Cofactor`] for
+/// BLS12-381 G1. Both outputs are in G1, but only the latter is the one of
+/// the draft, so the suites do not use this method. Nor is it faster: the
+/// cofactor part of E(Fp) is isomorphic to Z\[sigma\]/(m * (1 - sigma)), so
+/// no multiple with scalars shorter than m clears it, and m is dense
+/// whereas h_eff is sparse.
+pub struct SigmaCofactor {
+    beta: FpElt,
+    m: BigUint,
+}
+
+impl SigmaCofactor {
+    /// Returns the method that computes m * (P - sigma(P)) with
+    /// sigma(x, y) = (beta * x, y).
+    pub fn new(beta: FpElt, m: BigUint) -> Self {
+        SigmaCofactor { beta, m }
+    }
+    /// Returns the method of BLS12-381 G1, with the cube root of unity beta
+    /// for which sigma multiplies the points of G1 by z^2 - 1.
+    pub fn bls12381g1() -> Self {
+        let f = WeCurveID::Bls12381G1.get().get_field();
+        let beta = f.from("0x1a0111ea397fe699ec02408663d4de85aa0d857d89759ad4897d29650fb85f9b409427eb4f49fffd8bfd00000000aaac");
+        SigmaCofactor::new(beta, BigUint::from((BLS12381_Z_ABS + 1) / 3))
+    }
+}
+
+impl ClearCofactor for SigmaCofactor {
+    type Point = weierstrass::Point;
+    fn clear_cofactor(&self, p: Self::Point) -> Self::Point {
+        let q = p * &self.m;
+        // In Jacobian coordinates, x = X/Z^2, so sigma multiplies X by beta.
+        let sigma = weierstrass::Point {
+            x: q.x.clone() * &self.beta,
+            ..q.clone()
+        };
+        q + -sigma
+    }
+}
@@ -0,0 +1,250 @@
+use crate::ct::{Choice, CtGf, CtGfElt, CtGroup};
+use crate::curve::{edwards, montgomery, weierstrass, EllipticCurve};
+use crate::field::FieldElement;
+
+/// CtPoint is a point of a Weierstrass curve in homogeneous projective
+/// coordinates (X:Y:Z), which represents the affine point (X/Z, Y/Z).
+#[derive(Clone, Copy)]
+pub(crate) struct CtPoint {
+    x: CtGfElt,
+    y: CtGfElt,
+    z: CtGfElt,
+}
+
+/// CtWeierstrass implements the group law of a Weierstrass curve
+/// y^2 = x^3 + a * x + b with the complete formulas of Renes, Costello and
+/// Batina, which have no exceptional cases and thus no branches.
+pub(crate) struct CtWeierstrass {
+    f: CtGf,
+    a: CtGfElt,
+    b3: CtGfElt,
+    e: weierstrass::Curve,
+}
+
+impl CtWeierstrass {
+    pub(crate) fn new(f: &CtGf, e: &weierstrass::Curve) -> CtWeierstrass {
+        let b = f.elt(&e.b);
+        CtWeierstrass {
+            a: f.elt(&e.a),
+            b3: f.add(&f.add(&b, &b), &b),
+            f: f.clone(),
+            e: e.clone(),
+        }
+    }
+    /// Returns the point (X:Y:Z), or the identity if Z = 0.
+    pub(crate) fn point(&self, x: CtGfElt, y: CtGfElt, z: CtGfElt) -> CtPoint {
+        // The only points with Z = 0 are multiples of (0:1:0).
+        let is_identity = self.f.is_zero(&z);
+        self.select(&CtPoint { x, y, z }, &self.identity(), is_identity)
+    }
+}
+
+impl CtGroup for CtWeierstrass {
+    type Point = CtPoint;
+    type Output = weierstrass::Point;
+    fn identity(&self) -> CtPoint {
+        CtPoint {
+            x: self.f.zero(),
+            y: self.f.one(),
+            z: self.f.zero(),
+        }
+    }
+    /// Adds two points (Algorithm 1 of Renes et al.), which also doubles.
+    fn add(&self, p: &CtPoint, q: &CtPoint) -> CtPoint {
+        let (f, a, b3) = (&self.f, &self.a, &self.b3);
+        let (x1, y1, z1) = (&p.x, &p.y, &p.z);
+        let (x2, y2, z2) = (&q.x, &q.y, &q.z);
+        let mut t0 = f.mul(x1, x2);
+        let mut t1 = f.mul(y1, y2);
+        let mut t2 = f.mul(z1, z2);
+        let mut t3 = f.mul(&f.add(x1, y1), &f.add(x2, y2));
+        let mut t4 = f.add(&t0, &t1);
+        t3 = f.sub(&t3, &t4);
+        t4 = f.mul(&f.add(x1, z1), &f.add(x2, z2));
+        let mut t5 = f.add(&t0, &t2);
+        t4 = f.sub(&t4, &t5);
+        t5 = f.mul(&f.add(y1, z1), &f.add(y2, z2));
+        let mut x3 = f.add(&t1, &t2);
+        t5 = f.sub(&t5, &x3);
+        let mut z3 = f.mul(a, &t4);
+        x3 = f.mul(b3, &t2);
+        z3 = f.add(&x3, &z3);
+        x3 = f.sub(&t1, &z3);
+        z3 = f.add(&t1, &z3);
+        let mut y3 = f.mul(&x3, &z3);
+        t1 = f.add(&t0, &t0);
+        t1 = f.add(&t1, &t0);
+        t2 = f.mul(a, &t2);
+        t4 = f.mul(b3, &t4);
+        t1 = f.add(&t1, &t2);
+        t2 = f.sub(&t0, &t2);
+        t2 = f.mul(a, &t2);
+        t4 = f.add(&t4, &t2);
+        t0 = f.mul(&t1, &t4);
+        y3 = f.add(&y3, &t0);
+        t0 = f.mul(&t5, &t4);
+        x3 = f.mul(&t3, &x3);
+        x3 = f.sub(&x3, &t0);
+        t0 = f.mul(&t3, &t1);
+        z3 = f.mul(&t5, &z3);
+        z3 = f.add(&z3, &t0);
+        CtPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+    fn select(&self, a: &CtPoint, b: &CtPoint, c: Choice) -> CtPoint {
+        CtPoint {
+            x: self.f.select(&a.x, &b.x, c),
+            y: self.f.select(&a.y, &b.y, c),
+            z: self.f.select(&a.z, &b.z, c),
+        }
+    }
+    fn to_point(&self, p: &CtPoint) -> weierstrass::Point {
+        if self.f.is_zero(&p.z).unwrap() {
+            return self.e.identity();
+        }
+        let inv_z = self.f.inv0(&p.z);
+        let x = self.f.mul(&p.x, &inv_z);
+        let y = self.f.mul(&p.y, &inv_z);
+        self.e.new_point(self.f.to_elt(&x), self.f.to_elt(&y))
+    }
+}
+
+/// CtMontgomery implements the group law of a Montgomery curve on the
+/// isomorphic Weierstrass curve, as [`montgomery::Curve`] does.
+pub(crate) struct CtMontgomery {
+    w: CtWeierstrass,
+    /// A/3 and 1/B, which move points to the Weierstrass curve.
+    a3: CtGfElt,
+    inv_b: CtGfElt,
+    e: montgomery::Curve,
+}
+
+impl CtMontgomery {
+    pub(crate) fn new(f: &CtGf, e: &montgomery::Curve) -> CtMontgomery {
+        let three = e.get_field().from_i32(3);
+        CtMontgomery {
+            w: CtWeierstrass::new(f, e.weierstrass()),
+            a3: f.elt(&(e.a().clone() * &three.inv())),
+            inv_b: f.elt(&e.b().inv()),
+            e: e.clone(),
+        }
+    }
+    /// Returns the point with affine coordinates (s, t) on the Montgomery
+    /// curve.
+    pub(crate) fn point(&self, s: &CtGfElt, t: &CtGfElt) -> CtPoint {
+        let f = &self.w.f;
+        let x = f.mul(&f.add(s, &self.a3), &self.inv_b);
+        let y = f.mul(t, &self.inv_b);
+        CtPoint { x, y, z: f.one() }
+    }
+}
+
+impl CtGroup for CtMontgomery {
+    type Point = CtPoint;
+    type Output = montgomery::Point;
+    fn identity(&self) -> CtPoint {
+        self.w.identity()
+    }
+    fn add(&self, p: &CtPoint, q: &CtPoint) -> CtPoint {
+        self.w.add(p, q)
+    }
+    fn select(&self, a: &CtPoint, b: &CtPoint, c: Choice) -> CtPoint {
+        self.w.select(a, b, c)
+    }
+    fn to_point(&self, p: &CtPoint) -> montgomery::Point {
+        self.e.point(self.w.to_point(p))
+    }
+}
+
+/// CtEdPoint is a point (X:Y:Z:T) of a twisted Edwards curve in extended
+/// coordinates, with x = X/Z, y = Y/Z and x * y = T/Z.
+#[derive(Clone, Copy)]
+pub(crate) struct CtEdPoint {
+    x: CtGfElt,
+    y: CtGfElt,
+    z: CtGfElt,
+    t: CtGfElt,
+}
+
+/// CtEdwards implements the group law of a twisted Edwards curve
+/// a * x^2 + y^2 = 1 + d * x^2 * y^2 whose addition law is complete, as that
+/// of [`edwards::Curve`].
+pub(crate) struct CtEdwards {
+    f: CtGf,
+    a: CtGfElt,
+    d: CtGfElt,
+    e: edwards::Curve,
+}
+
+impl CtEdwards {
+    pub(crate) fn new(f: &CtGf, e: &edwards::Curve) -> CtEdwards {
+        CtEdwards {
+            a: f.elt(e.a()),
+            d: f.elt(e.d()),
+            f: f.clone(),
+            e: e.clone(),
+        }
+    }
+    /// Returns the point with affine coordinates (X/Z, Y/Z), or the identity
+    /// if Z = 0.
+    pub(crate) fn point(&self, x: &CtGfElt, y: &CtGfElt, z: &CtGfElt) -> CtEdPoint {
+        let f = &self.f;
+        let p = CtEdPoint {
+            x: f.mul(x, z),
+            y: f.mul(y, z),
+            z: f.square(z),
+            t: f.mul(x, y),
+        };
+        self.select(&p, &self.identity(), f.is_zero(z))
+    }
+}
+
+impl CtGroup for CtEdwards {
+    type Point = CtEdPoint;
+    type Output = edwards::Point;
+    fn identity(&self) -> CtEdPoint {
+        CtEdPoint {
+            x: self.f.zero(),
+            y: self.f.one(),
+            z: self.f.one(),
+            t: self.f.zero(),
+        }
+    }
+    /// Adds two points with add-2008-hwcd, which also doubles.
+    fn add(&self, p: &CtEdPoint, q: &CtEdPoint) -> CtEdPoint {
+        let f = &self.f;
+        let a = f.mul(&p.x, &q.x);
+        let b = f.mul(&p.y, &q.y);
+        let c = f.mul(&f.mul(&p.t, &self.d), &q.t);
+        let d = f.mul(&p.z, &q.z);
+        let e = f.mul(&f.add(&p.x, &p.y), &f.add(&q.x, &q.y));
+        let e = f.sub(&f.sub(&e, &a), &b);
+        let ff = f.sub(&d, &c);
+        let g = f.add(&d, &c);
+        let h = f.sub(&b, &f.mul(&self.a, &a));
+        CtEdPoint {
+            x: f.mul(&e, &ff),
+            y: f.mul(&g, &h),
+            z: f.mul(&ff, &g),
+            t: f.mul(&e, &h),
+        }
+    }
+    fn select(&self, a: &CtEdPoint, b: &CtEdPoint, c: Choice) -> CtEdPoint {
+        let f = &self.f;
+        CtEdPoint {
+            x: f.select(&a.x, &b.x, c),
+            y: f.select(&a.y, &b.y, c),
+            z: f.select(&a.z, &b.z, c),
+            t: f.select(&a.t, &b.t, c),
+        }
+    }
+    fn to_point(&self, p: &CtEdPoint) -> edwards::Point {
+        let inv_z = self.f.inv0(&p.z);
+        let x = self.f.mul(&p.x, &inv_z);
+        let y = self.f.mul(&p.y, &inv_z);
+        self.e.new_point(self.f.to_elt(&x), self.f.to_elt(&y))
+    }
+}
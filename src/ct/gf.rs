@@ -0,0 +1,312 @@
+// Multi-precision arithmetic reads best with the limbs indexed explicitly.
+#![allow(clippy::needless_range_loop)]
+
+use num_bigint::BigUint;
+
+use crate::ct::{adc, mac, sbb, Choice};
+use crate::error::Error;
+use crate::field::{Field, FieldElement};
+use crate::fp::{Fp, FpElt, FromUniformBytes};
+
+/// Number of 64-bit limbs of an element, enough for the field of P-521.
+const LIMBS: usize = 9;
+
+/// CtGf is the field [`Fp`] with constant-time arithmetic. Elements are held
+/// in Montgomery form, in the first `n` limbs of a [`CtGfElt`], where n is
+/// the least number of limbs that holds p. Only n and p, which are public,
+/// affect the running time.
+#[derive(Clone)]
+pub(crate) struct CtGf {
+    fp: Fp,
+    n: usize,
+    p: [u64; LIMBS],
+    /// -1/p mod 2^64.
+    inv: u64,
+    /// R = 2^(64*n) mod p, i.e., one in Montgomery form.
+    r: [u64; LIMBS],
+    /// R^2 mod p, which converts integers below 2^(64*n) into Montgomery
+    /// form.
+    r2: [u64; LIMBS],
+    /// p - 2, the exponent of inv0.
+    p_minus_2: Vec<u64>,
+    /// (p - 1)/2, the exponent of Euler's criterion.
+    p_minus_1_half: Vec<u64>,
+}
+
+/// CtGfElt is an element of a [`CtGf`].
+#[derive(Clone, Copy)]
+pub(crate) struct CtGfElt([u64; LIMBS]);
+
+/// Returns the little-endian limbs of n, which must be below 2^(64*LIMBS).
+fn limbs(n: &BigUint) -> [u64; LIMBS] {
+    let mut out = [0; LIMBS];
+    for (o, d) in out.iter_mut().zip(n.to_u64_digits()) {
+        *o = d;
+    }
+    out
+}
+
+impl CtGf {
+    pub(crate) fn new(fp: Fp) -> CtGf {
+        let p = fp.get_modulus();
+        let n = (p.bits() as usize).div_ceil(64);
+        assert!(n <= LIMBS, "the modulus has more than {} limbs", LIMBS);
+        // Newton's iteration doubles the number of correct low bits of 1/p.
+        let p0 = p.to_u64_digits()[0];
+        let inv = (0..6).fold(1u64, |x, _| {
+            x.wrapping_mul(2u64.wrapping_sub(p0.wrapping_mul(x)))
+        });
+        let r = (BigUint::from(1u32) << (64 * n)) % &p;
+        CtGf {
+            fp,
+            n,
+            p: limbs(&p),
+            inv: inv.wrapping_neg(),
+            r2: limbs(&(&r * &r % &p)),
+            r: limbs(&r),
+            p_minus_2: (&p - 2u32).to_u64_digits(),
+            p_minus_1_half: ((&p - 1u32) >> 1usize).to_u64_digits(),
+        }
+    }
+    pub(crate) fn zero(&self) -> CtGfElt {
+        CtGfElt([0; LIMBS])
+    }
+    pub(crate) fn one(&self) -> CtGfElt {
+        CtGfElt(self.r)
+    }
+    /// Interprets `b` as a big-endian integer of any length and reduces it
+    /// modulo p. Only the length of `b` affects the running time.
+    pub(crate) fn elt_from_bytes(&self, b: &[u8]) -> CtGfElt {
+        let r2 = CtGfElt(self.r2);
+        let mut shift = [0; LIMBS];
+        shift[1] = 1;
+        // 2^64 in Montgomery form.
+        let shift = self.mul(&CtGfElt(shift), &r2);
+        let head = b.len() % 8;
+        let chunks = std::iter::once(&b[..head]).chain(b[head..].chunks(8));
+        chunks
+            .filter(|c| !c.is_empty())
+            .fold(self.zero(), |acc, chunk| {
+                let mut word = [0u8; 8];
+                word[8 - chunk.len()..].copy_from_slice(chunk);
+                let mut w = [0; LIMBS];
+                w[0] = u64::from_be_bytes(word);
+                self.add(&self.mul(&acc, &shift), &self.mul(&CtGfElt(w), &r2))
+            })
+    }
+    /// Converts an element of [`Fp`], which is public.
+    pub(crate) fn elt(&self, e: &FpElt) -> CtGfElt {
+        self.elt_from_bytes(&e.value().to_bytes_be())
+    }
+    /// Converts the element into an element of [`Fp`], whose arithmetic is
+    /// not constant time.
+    pub(crate) fn to_elt(&self, a: &CtGfElt) -> FpElt {
+        let c = self.canonical(a);
+        let digits: Vec<u32> = c[..self.n]
+            .iter()
+            .flat_map(|l| [*l as u32, (l >> 32) as u32])
+            .collect();
+        self.fp.elt(&BigUint::from_slice(&digits))
+    }
+    /// Returns the element out of Montgomery form.
+    fn canonical(&self, a: &CtGfElt) -> [u64; LIMBS] {
+        let mut one = [0; LIMBS];
+        one[0] = 1;
+        self.mul(a, &CtGfElt(one)).0
+    }
+    pub(crate) fn is_zero(&self, a: &CtGfElt) -> Choice {
+        let acc = a.0.iter().fold(0, |acc, l| acc | l);
+        // The top bit of acc | -acc is set if and only if acc is not zero.
+        Choice::from_bit(!((acc | acc.wrapping_neg()) >> 63))
+    }
+    pub(crate) fn ct_eq(&self, a: &CtGfElt, b: &CtGfElt) -> Choice {
+        self.is_zero(&self.sub(a, b))
+    }
+    /// Returns `b` if `c` is true, and `a` otherwise. This is CMOV(a, b, c)
+    /// of the draft.
+    pub(crate) fn select(&self, a: &CtGfElt, b: &CtGfElt, c: Choice) -> CtGfElt {
+        let mut out = [0; LIMBS];
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = a.0[i] ^ (c.mask() & (a.0[i] ^ b.0[i]));
+        }
+        CtGfElt(out)
+    }
+    /// Returns the sign of the element as defined in Section 4.1 of the draft.
+    pub(crate) fn sgn0(&self, a: &CtGfElt) -> Choice {
+        Choice::from_bit(self.canonical(a)[0])
+    }
+    /// Returns true if the element is a square, including zero.
+    pub(crate) fn is_square(&self, a: &CtGfElt) -> Choice {
+        let t = self.pow(a, &self.p_minus_1_half);
+        self.is_zero(&t).or(self.ct_eq(&t, &self.one()))
+    }
+    pub(crate) fn add(&self, a: &CtGfElt, b: &CtGfElt) -> CtGfElt {
+        let (mut s, mut carry) = ([0; LIMBS], 0);
+        for i in 0..self.n {
+            (s[i], carry) = adc(a.0[i], b.0[i], carry);
+        }
+        self.reduce(s, carry)
+    }
+    pub(crate) fn sub(&self, a: &CtGfElt, b: &CtGfElt) -> CtGfElt {
+        let (mut d, mut borrow) = ([0; LIMBS], 0);
+        for i in 0..self.n {
+            (d[i], borrow) = sbb(a.0[i], b.0[i], borrow);
+        }
+        // Adds p back if the subtraction wrapped around.
+        let mask = 0u64.wrapping_sub(borrow);
+        let mut carry = 0;
+        for i in 0..self.n {
+            (d[i], carry) = adc(d[i], self.p[i] & mask, carry);
+        }
+        CtGfElt(d)
+    }
+    pub(crate) fn neg(&self, a: &CtGfElt) -> CtGfElt {
+        self.sub(&self.zero(), a)
+    }
+    /// Montgomery multiplication with the CIOS method.
+    pub(crate) fn mul(&self, a: &CtGfElt, b: &CtGfElt) -> CtGfElt {
+        let n = self.n;
+        let (mut t, mut t_n) = ([0; LIMBS], 0);
+        for i in 0..n {
+            let mut c = 0;
+            for j in 0..n {
+                (t[j], c) = mac(t[j], a.0[j], b.0[i], c);
+            }
+            let (s, t_n1) = adc(t_n, c, 0);
+            t_n = s;
+            let m = t[0].wrapping_mul(self.inv);
+            let (_, mut c) = mac(t[0], m, self.p[0], 0);
+            for j in 1..n {
+                (t[j - 1], c) = mac(t[j], m, self.p[j], c);
+            }
+            (t[n - 1], c) = adc(t_n, c, 0);
+            t_n = t_n1 + c;
+        }
+        self.reduce(t, t_n)
+    }
+    pub(crate) fn square(&self, a: &CtGfElt) -> CtGfElt {
+        self.mul(a, a)
+    }
+    /// Computes a^e for a public exponent `e` given as little-endian limbs.
+    pub(crate) fn pow(&self, a: &CtGfElt, e: &[u64]) -> CtGfElt {
+        let mut acc = self.one();
+        for limb in e.iter().rev() {
+            for i in (0..64).rev() {
+                acc = self.square(&acc);
+                if (limb >> i) & 1 == 1 {
+                    acc = self.mul(&acc, a);
+                }
+            }
+        }
+        acc
+    }
+    /// Returns 1/a, or zero if a is zero, using Fermat's little theorem.
+    pub(crate) fn inv0(&self, a: &CtGfElt) -> CtGfElt {
+        self.pow(a, &self.p_minus_2)
+    }
+    /// Subtracts p if the value `a` (plus `carry` times 2^(64*n)) is not
+    /// smaller than p.
+    fn reduce(&self, a: [u64; LIMBS], carry: u64) -> CtGfElt {
+        let (mut d, mut borrow) = ([0; LIMBS], 0);
+        for i in 0..self.n {
+            (d[i], borrow) = sbb(a[i], self.p[i], borrow);
+        }
+        let (_, borrow) = sbb(carry, 0, borrow);
+        self.select(&CtGfElt(d), &CtGfElt(a), Choice::from_bit(borrow))
+    }
+}
+
+impl FromUniformBytes for CtGf {
+    type Elt = CtGfElt;
+    fn degree(&self) -> usize {
+        1
+    }
+    fn elt_from_uniform_bytes(&self, e: &[&[u8]]) -> Result<Self::Elt, Error> {
+        Ok(self.elt_from_bytes(e[0]))
+    }
+}
+
+/// SqrtRatio implements sqrt_ratio of Appendix F.2.1.1 of the draft, which
+/// works for any prime field, for a non-square Z.
+pub(crate) struct SqrtRatio {
+    f: CtGf,
+    c1: usize,
+    c3: Vec<u64>,
+    c4: Vec<u64>,
+    c5: Vec<u64>,
+    c6: CtGfElt,
+    c7: CtGfElt,
+}
+
+impl SqrtRatio {
+    pub(crate) fn new(f: &CtGf, z: &FpElt) -> SqrtRatio {
+        let one = BigUint::from(1u32);
+        let q1 = f.fp.get_modulus() - 1u32;
+        let c1 = q1.trailing_zeros().unwrap() as usize;
+        let c2: BigUint = &q1 >> c1;
+        let c3: BigUint = (&c2 - 1u32) >> 1u32;
+        let c4: BigUint = (&one << c1) - 1u32;
+        let c5: BigUint = &one << (c1 - 1);
+        let c7: BigUint = (&c2 + 1u32) >> 1u32;
+        let z = f.elt(z);
+        SqrtRatio {
+            c1,
+            c3: c3.to_u64_digits(),
+            c4: c4.to_u64_digits(),
+            c5: c5.to_u64_digits(),
+            c6: f.pow(&z, &c2.to_u64_digits()),
+            c7: f.pow(&z, &c7.to_u64_digits()),
+            f: f.clone(),
+        }
+    }
+
+    /// Returns a non-square of `f`, for the square roots of fields whose map
+    /// has no non-square Z.
+    pub(crate) fn non_square(f: &CtGf) -> FpElt {
+        let fp = f.fp;
+        (2..)
+            .map(|i| fp.from_i32(i))
+            .find(|c| !c.is_square())
+            .expect("half of the elements are not squares")
+    }
+
+    /// Returns (true, sqrt(u/v)) if u/v is square, and (false, sqrt(Z*u/v))
+    /// otherwise.
+    pub(crate) fn sqrt_ratio(&self, u: &CtGfElt, v: &CtGfElt) -> (Choice, CtGfElt) {
+        let f = &self.f;
+        let mut tv1 = self.c6;
+        let mut tv2 = f.pow(v, &self.c4);
+        let mut tv3 = f.square(&tv2);
+        tv3 = f.mul(&tv3, v);
+        let mut tv5 = f.mul(u, &tv3);
+        tv5 = f.pow(&tv5, &self.c3);
+        tv5 = f.mul(&tv5, &tv2);
+        tv2 = f.mul(&tv5, v);
+        tv3 = f.mul(&tv5, u);
+        let mut tv4 = f.mul(&tv3, &tv2);
+        tv5 = f.pow(&tv4, &self.c5);
+        let is_qr = f.ct_eq(&tv5, &f.one());
+        tv2 = f.mul(&tv3, &self.c7);
+        tv5 = f.mul(&tv4, &tv1);
+        tv3 = f.select(&tv2, &tv3, is_qr);
+        tv4 = f.select(&tv5, &tv4, is_qr);
+        for i in (2..=self.c1).rev() {
+            tv5 = tv4;
+            for _ in 0..i - 2 {
+                tv5 = f.square(&tv5);
+            }
+            let e1 = f.ct_eq(&tv5, &f.one());
+            tv2 = f.mul(&tv3, &tv1);
+            tv1 = f.square(&tv1);
+            tv5 = f.mul(&tv4, &tv1);
+            tv3 = f.select(&tv2, &tv3, e1);
+            tv4 = f.select(&tv5, &tv4, e1);
+        }
+        (is_qr, tv3)
+    }
+
+    /// Returns a square root of u, which must be a square.
+    pub(crate) fn sqrt(&self, u: &CtGfElt) -> CtGfElt {
+        self.sqrt_ratio(u, &self.f.one()).1
+    }
+}
@@ -0,0 +1,315 @@
+use crate::ct::curve::{CtEdPoint, CtPoint};
+use crate::ct::gf::SqrtRatio;
+use crate::ct::{CtEdwards, CtGf, CtGfElt, CtMap, CtMontgomery, CtWeierstrass};
+use crate::curve::weierstrass;
+use crate::field::Field;
+use crate::fp::{Fp, FpElt};
+use crate::isogeny::IsogenyMap;
+use crate::map::{Ell2, Ell2Edwards, Isogeny, RationalMap, SVDW};
+
+/// CtIsogeny is an [`Isogeny`] with its coefficients in a [`CtGf`].
+struct CtIsogeny {
+    x_num: Vec<CtGfElt>,
+    x_den: Vec<CtGfElt>,
+    y_num: Vec<CtGfElt>,
+    y_den: Vec<CtGfElt>,
+}
+
+/// CtSSWU is the Simplified SWU map of Section 6.6.2 of the draft, computed
+/// with the straight-line procedure of Appendix F.2. For curves with AB == 0,
+/// it maps to the isogenous curve E' and evaluates the isogeny, as the
+/// method of Section 6.6.3.
+pub(crate) struct CtSSWU {
+    f: CtGf,
+    e: CtWeierstrass,
+    /// The coefficients A and B of E, or of E' if there is an isogeny.
+    a: CtGfElt,
+    b: CtGfElt,
+    z: CtGfElt,
+    sqrt: SqrtRatio,
+    iso: Option<CtIsogeny>,
+}
+
+impl CtSSWU {
+    /// Returns the map to the curve `e`, whose A and B are not zero.
+    pub(crate) fn new(f: &CtGf, e: &weierstrass::Curve, z: &FpElt) -> CtSSWU {
+        CtSSWU {
+            f: f.clone(),
+            e: CtWeierstrass::new(f, e),
+            a: f.elt(&e.a),
+            b: f.elt(&e.b),
+            z: f.elt(z),
+            sqrt: SqrtRatio::new(f, z),
+            iso: None,
+        }
+    }
+    /// Returns the map to the curve `e` through the curve E' of `iso`.
+    pub(crate) fn with_isogeny(
+        f: &CtGf,
+        e: &weierstrass::Curve,
+        iso: &IsogenyMap,
+        z: &FpElt,
+    ) -> CtSSWU {
+        let fp: Fp = e.get_field();
+        let elts = |v: &[FpElt]| v.iter().map(|c| f.elt(c)).collect();
+        let coeffs = Isogeny::new(&fp, iso);
+        CtSSWU {
+            a: f.elt(&fp.from(iso.a)),
+            b: f.elt(&fp.from(iso.b)),
+            iso: Some(CtIsogeny {
+                x_num: elts(&coeffs.x_num),
+                x_den: elts(&coeffs.x_den),
+                y_num: elts(&coeffs.y_num),
+                y_den: elts(&coeffs.y_den),
+            }),
+            ..CtSSWU::new(f, e, z)
+        }
+    }
+}
+
+impl CtMap for CtSSWU {
+    type Elt = CtGfElt;
+    type Group = CtWeierstrass;
+    fn group(&self) -> &CtWeierstrass {
+        &self.e
+    }
+    fn map(&self, u: &CtGfElt) -> CtPoint {
+        let (f, a, b, z) = (&self.f, &self.a, &self.b, &self.z);
+        let mut tv1 = f.square(u);
+        tv1 = f.mul(z, &tv1);
+        let mut tv2 = f.square(&tv1);
+        tv2 = f.add(&tv2, &tv1);
+        let mut tv3 = f.add(&tv2, &f.one());
+        tv3 = f.mul(b, &tv3);
+        let mut tv4 = f.select(z, &f.neg(&tv2), f.is_zero(&tv2).not());
+        tv4 = f.mul(a, &tv4);
+        tv2 = f.square(&tv3);
+        let mut tv6 = f.square(&tv4);
+        let mut tv5 = f.mul(a, &tv6);
+        tv2 = f.add(&tv2, &tv5);
+        tv2 = f.mul(&tv2, &tv3);
+        tv6 = f.mul(&tv6, &tv4);
+        tv5 = f.mul(b, &tv6);
+        tv2 = f.add(&tv2, &tv5);
+        let mut x = f.mul(&tv1, &tv3);
+        let (is_gx1_square, y1) = self.sqrt.sqrt_ratio(&tv2, &tv6);
+        let mut y = f.mul(&tv1, u);
+        y = f.mul(&y, &y1);
+        x = f.select(&x, &tv3, is_gx1_square);
+        y = f.select(&y, &y1, is_gx1_square);
+        let e1 = f.sgn0(u).eq(f.sgn0(&y));
+        y = f.select(&f.neg(&y), &y, e1);
+        let Some(iso) = &self.iso else {
+            // (x/tv4, y) in projective coordinates.
+            return self.e.point(x, f.mul(&y, &tv4), tv4);
+        };
+        let x = f.mul(&x, &f.inv0(&tv4));
+        let horner = |coeffs: &[CtGfElt]| {
+            coeffs
+                .iter()
+                .rev()
+                .fold(f.zero(), |acc, k| f.add(&f.mul(&acc, &x), k))
+        };
+        let (xn, xd) = (horner(&iso.x_num), horner(&iso.x_den));
+        let (yn, yd) = (horner(&iso.y_num), horner(&iso.y_den));
+        self.e.point(
+            f.mul(&xn, &yd),
+            f.mul(&f.mul(&y, &yn), &xd),
+            f.mul(&xd, &yd),
+        )
+    }
+}
+
+/// CtSVDW is the Shallue-van de Woestijne map of Section 6.6.1 of the draft,
+/// computed with the straight-line procedure of Appendix F.1.
+pub(crate) struct CtSVDW {
+    f: CtGf,
+    e: CtWeierstrass,
+    a: CtGfElt,
+    b: CtGfElt,
+    z: CtGfElt,
+    c1: CtGfElt,
+    c2: CtGfElt,
+    c3: CtGfElt,
+    c4: CtGfElt,
+    sqrt: SqrtRatio,
+}
+
+impl CtSVDW {
+    /// Returns the map with the constants of `m`.
+    pub(crate) fn new(f: &CtGf, m: &SVDW<Fp>) -> CtSVDW {
+        CtSVDW {
+            f: f.clone(),
+            e: CtWeierstrass::new(f, &m.e),
+            a: f.elt(&m.e.a),
+            b: f.elt(&m.e.b),
+            z: f.elt(&m.z),
+            c1: f.elt(&m.c1),
+            c2: f.elt(&m.c2),
+            c3: f.elt(&m.c3),
+            c4: f.elt(&m.c4),
+            // Z need not be a non-square for this map.
+            sqrt: SqrtRatio::new(f, &SqrtRatio::non_square(f)),
+        }
+    }
+    /// Returns g(x) = x^3 + A * x + B.
+    fn g(&self, x: &CtGfElt) -> CtGfElt {
+        let f = &self.f;
+        let gx = f.add(&f.square(x), &self.a);
+        f.add(&f.mul(&gx, x), &self.b)
+    }
+}
+
+impl CtMap for CtSVDW {
+    type Elt = CtGfElt;
+    type Group = CtWeierstrass;
+    fn group(&self) -> &CtWeierstrass {
+        &self.e
+    }
+    fn map(&self, u: &CtGfElt) -> CtPoint {
+        let f = &self.f;
+        let mut tv1 = f.square(u);
+        tv1 = f.mul(&tv1, &self.c1);
+        let tv2 = f.add(&f.one(), &tv1);
+        tv1 = f.sub(&f.one(), &tv1);
+        let mut tv3 = f.mul(&tv1, &tv2);
+        tv3 = f.inv0(&tv3);
+        let mut tv4 = f.mul(u, &tv1);
+        tv4 = f.mul(&tv4, &tv3);
+        tv4 = f.mul(&tv4, &self.c3);
+        let x1 = f.sub(&self.c2, &tv4);
+        let e1 = f.is_square(&self.g(&x1));
+        let x2 = f.add(&self.c2, &tv4);
+        let e2 = f.is_square(&self.g(&x2)).and(e1.not());
+        let mut x3 = f.square(&tv2);
+        x3 = f.mul(&x3, &tv3);
+        x3 = f.square(&x3);
+        x3 = f.mul(&x3, &self.c4);
+        x3 = f.add(&x3, &self.z);
+        let mut x = f.select(&x3, &x1, e1);
+        x = f.select(&x, &x2, e2);
+        let mut y = self.sqrt.sqrt(&self.g(&x));
+        let e3 = f.sgn0(u).eq(f.sgn0(&y));
+        y = f.select(&f.neg(&y), &y, e3);
+        self.e.point(x, y, f.one())
+    }
+}
+
+/// CtEll2 is the Elligator 2 map of Section 6.7.1 of the draft, computed with
+/// the straight-line procedure of Appendix F.3.
+pub(crate) struct CtEll2 {
+    f: CtGf,
+    e: CtMontgomery,
+    z: CtGfElt,
+    c1: CtGfElt,
+    c2: CtGfElt,
+    k: CtGfElt,
+    sqrt: SqrtRatio,
+}
+
+impl CtEll2 {
+    /// Returns the map with the constants of `m`.
+    pub(crate) fn new(f: &CtGf, m: &Ell2) -> CtEll2 {
+        CtEll2 {
+            f: f.clone(),
+            e: CtMontgomery::new(f, &m.e),
+            z: f.elt(&m.z),
+            c1: f.elt(&m.c1),
+            c2: f.elt(&m.c2),
+            k: f.elt(&m.k),
+            sqrt: SqrtRatio::new(f, &m.z),
+        }
+    }
+    /// Outputs the affine coordinates (s, t) of the image of u.
+    fn map_affine(&self, u: &CtGfElt) -> (CtGfElt, CtGfElt) {
+        let f = &self.f;
+        let mut tv1 = f.square(u);
+        tv1 = f.mul(&self.z, &tv1);
+        let e = f.ct_eq(&tv1, &f.neg(&f.one()));
+        tv1 = f.select(&tv1, &f.zero(), e);
+        let mut x1 = f.add(&tv1, &f.one());
+        x1 = f.inv0(&x1);
+        x1 = f.mul(&f.neg(&self.c1), &x1);
+        let mut gx1 = f.add(&x1, &self.c1);
+        gx1 = f.mul(&gx1, &x1);
+        gx1 = f.add(&gx1, &self.c2);
+        gx1 = f.mul(&gx1, &x1);
+        let x2 = f.sub(&f.neg(&x1), &self.c1);
+        let gx2 = f.mul(&tv1, &gx1);
+        let e2 = f.is_square(&gx1);
+        let x = f.select(&x2, &x1, e2);
+        let y2 = f.select(&gx2, &gx1, e2);
+        let mut y = self.sqrt.sqrt(&y2);
+        let e3 = f.sgn0(&y);
+        y = f.select(&y, &f.neg(&y), e2.eq(e3).not());
+        (f.mul(&x, &self.k), f.mul(&y, &self.k))
+    }
+}
+
+impl CtMap for CtEll2 {
+    type Elt = CtGfElt;
+    type Group = CtMontgomery;
+    fn group(&self) -> &CtMontgomery {
+        &self.e
+    }
+    fn map(&self, u: &CtGfElt) -> CtPoint {
+        let (s, t) = self.map_affine(u);
+        self.e.point(&s, &t)
+    }
+}
+
+/// CtEll2Edwards composes [`CtEll2`] with the rational map of an
+/// [`Ell2Edwards`], whose exceptional cases are sent to the identity.
+pub(crate) struct CtEll2Edwards {
+    f: CtGf,
+    e: CtEdwards,
+    ell2: CtEll2,
+    /// c1 of the map to edwards25519, or None for the map to edwards448.
+    c1: Option<CtGfElt>,
+}
+
+impl CtEll2Edwards {
+    /// Returns the map with the constants of `m`.
+    pub(crate) fn new(f: &CtGf, m: &Ell2Edwards) -> CtEll2Edwards {
+        CtEll2Edwards {
+            f: f.clone(),
+            e: CtEdwards::new(f, &m.e),
+            ell2: CtEll2::new(f, &m.ell2),
+            c1: match &m.ratmap {
+                RationalMap::Edwards25519 { c1 } => Some(f.elt(c1)),
+                RationalMap::Edwards448 => None,
+            },
+        }
+    }
+}
+
+impl CtMap for CtEll2Edwards {
+    type Elt = CtGfElt;
+    type Group = CtEdwards;
+    fn group(&self) -> &CtEdwards {
+        &self.e
+    }
+    fn map(&self, u: &CtGfElt) -> CtEdPoint {
+        let f = &self.f;
+        let (s, t) = self.ell2.map_affine(u);
+        let one = f.one();
+        let [xn, xd, yn, yd] = match &self.c1 {
+            Some(c1) => [f.mul(c1, &s), t, f.sub(&s, &one), f.add(&s, &one)],
+            None => {
+                let (s2, t2) = (f.square(&s), f.square(&t));
+                let s3 = f.mul(&s2, &s);
+                let s5 = f.mul(&s3, &s2);
+                let two = |x: &CtGfElt| f.add(x, x);
+                let four = |x: &CtGfElt| two(&two(x));
+                let xn = four(&f.mul(&t, &f.sub(&s2, &one)));
+                let xd = f.add(&f.sub(&f.square(&s2), &two(&s2)), &f.add(&four(&t2), &one));
+                let yn = f.sub(&f.sub(&s5, &two(&s3)), &f.sub(&four(&f.mul(&s, &t2)), &s));
+                let yd = f.sub(&f.sub(&s5, &two(&f.mul(&s2, &t2))), &two(&s3));
+                let yd = f.add(&f.sub(&yd, &two(&t2)), &s);
+                [xn, xd, f.neg(&yn), yd]
+            }
+        };
+        let z = f.mul(&xd, &yd);
+        self.e.point(&f.mul(&xn, &yd), &f.mul(&yn, &xd), &z)
+    }
+}
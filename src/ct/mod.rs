@@ -0,0 +1,182 @@
+//! Constant-time hashing. Field elements are held in fixed-size limbs, the
+//! maps to curve follow the straight-line procedures of Appendix F of the
+//! draft, points are added with complete formulas, and cofactors are cleared
+//! with a fixed-window multiplication, so that the running time does not
+//! depend on the message being hashed.
+//!
+//! This module holds what the prime-field suites and the suites of
+//! BLS12-381 G2 share: branchless selection, multi-precision arithmetic, and
+//! the [`CtEncoding`] that chains hash_to_field, map_to_curve and
+//! clear_cofactor.
+
+use std::hint::black_box;
+
+use num_bigint::BigUint;
+
+use crate::api::CtEncoder;
+use crate::curve::EcPoint;
+use crate::error::Error;
+use crate::expander::ExpanderStream;
+use crate::fp::{FpHasher, FromUniformBytes};
+
+mod curve;
+mod gf;
+mod map;
+
+pub(crate) use crate::ct::curve::{CtEdwards, CtMontgomery, CtWeierstrass};
+pub(crate) use crate::ct::gf::{CtGf, CtGfElt};
+pub(crate) use crate::ct::map::{CtEll2, CtEll2Edwards, CtSSWU, CtSVDW};
+
+/// Choice is the outcome of a comparison, held as a mask with all its bits
+/// set (true) or cleared (false), so that it selects values without branches.
+#[derive(Clone, Copy)]
+pub(crate) struct Choice(u64);
+
+impl Choice {
+    /// Returns true if `bit` is one; only the least significant bit is read.
+    pub(crate) fn from_bit(bit: u64) -> Choice {
+        Choice(black_box(0u64.wrapping_sub(bit & 1)))
+    }
+    pub(crate) fn and(self, other: Choice) -> Choice {
+        Choice(self.0 & other.0)
+    }
+    pub(crate) fn or(self, other: Choice) -> Choice {
+        Choice(self.0 | other.0)
+    }
+    pub(crate) fn not(self) -> Choice {
+        Choice(!self.0)
+    }
+    pub(crate) fn eq(self, other: Choice) -> Choice {
+        Choice(!(self.0 ^ other.0))
+    }
+    /// Returns the mask, e.g., to select limbs.
+    pub(crate) fn mask(self) -> u64 {
+        self.0
+    }
+    /// Reveals the choice. Only use it on values that are not secret.
+    pub(crate) fn unwrap(self) -> bool {
+        self.0 != 0
+    }
+}
+
+/// Returns whether a equals b without branching.
+pub(crate) fn eq(a: u64, b: u64) -> Choice {
+    let d = a ^ b;
+    Choice::from_bit(!((d | d.wrapping_neg()) >> 63))
+}
+
+/// Returns a + b + carry, and the carry out.
+#[inline(always)]
+pub(crate) fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Returns a - b - borrow, and the borrow out (0 or 1).
+#[inline(always)]
+pub(crate) fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let t = (a as u128).wrapping_sub((b as u128) + (borrow as u128));
+    (t as u64, ((t >> 64) as u64) & 1)
+}
+
+/// Returns a + b*c + carry, and the carry out.
+#[inline(always)]
+pub(crate) fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = (a as u128) + (b as u128) * (c as u128) + (carry as u128);
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Width in bits of the windows of [`CtGroup::mul`].
+const WINDOW: usize = 4;
+
+/// CtGroup is the group law of a curve, computed with complete formulas so
+/// that points are added without branches.
+pub(crate) trait CtGroup: Send + Sync {
+    type Point: Copy;
+    /// The point type of the curve, whose arithmetic is not constant time.
+    type Output: EcPoint;
+    fn identity(&self) -> Self::Point;
+    fn add(&self, p: &Self::Point, q: &Self::Point) -> Self::Point;
+    fn double(&self, p: &Self::Point) -> Self::Point {
+        self.add(p, p)
+    }
+    /// Returns `b` if `c` is true, and `a` otherwise.
+    fn select(&self, a: &Self::Point, b: &Self::Point, c: Choice) -> Self::Point;
+    /// Converts the point into an [`Output`](CtGroup::Output); only this
+    /// conversion is not constant time.
+    fn to_point(&self, p: &Self::Point) -> Self::Output;
+    /// Computes k*P with a fixed-window method. The running time depends on
+    /// the bit length of `k`, but neither on its bits nor on the point, since
+    /// each window reads the whole table of multiples.
+    fn mul(&self, p: &Self::Point, k: &BigUint) -> Self::Point {
+        let mut table = [self.identity(); 1 << WINDOW];
+        for i in 1..table.len() {
+            table[i] = self.add(&table[i - 1], p);
+        }
+        let windows = (k.bits() as usize).div_ceil(WINDOW);
+        let mut q = self.identity();
+        for w in (0..windows).rev() {
+            for _ in 0..WINDOW {
+                q = self.double(&q);
+            }
+            let digit = (0..WINDOW).fold(0u64, |d, i| {
+                d | (k.bit((w * WINDOW + i) as u64) as u64) << i
+            });
+            let mut t = self.identity();
+            for (i, entry) in table.iter().enumerate() {
+                t = self.select(&t, entry, eq(i as u64, digit));
+            }
+            q = self.add(&q, &t);
+        }
+        q
+    }
+}
+
+/// CtMap is a map to curve whose running time does not depend on its input.
+pub(crate) trait CtMap: Send + Sync {
+    type Elt;
+    type Group: CtGroup;
+    fn group(&self) -> &Self::Group;
+    fn map(&self, u: &Self::Elt) -> <Self::Group as CtGroup>::Point;
+}
+
+/// CtEncoding hashes messages to a curve in constant time. It is selected
+/// with [`Suite::constant_time`](crate::Suite::constant_time).
+pub(crate) struct CtEncoding<F: FromUniformBytes, G: CtGroup> {
+    hash_to_field: FpHasher<F>,
+    map_to_curve: Box<dyn CtMap<Elt = F::Elt, Group = G>>,
+    h_eff: BigUint,
+}
+
+impl<F: FromUniformBytes, G: CtGroup> CtEncoding<F, G> {
+    /// Returns the encoding that clears the cofactor by multiplying by
+    /// `h_eff`.
+    pub(crate) fn new(
+        hash_to_field: FpHasher<F>,
+        map_to_curve: Box<dyn CtMap<Elt = F::Elt, Group = G>>,
+        h_eff: BigUint,
+    ) -> CtEncoding<F, G> {
+        CtEncoding {
+            hash_to_field,
+            map_to_curve,
+            h_eff,
+        }
+    }
+}
+
+impl<F, G> CtEncoder for CtEncoding<F, G>
+where
+    F: FromUniformBytes + Send + Sync,
+    G: CtGroup,
+{
+    type Point = G::Output;
+    fn encode(&self, s: Box<dyn ExpanderStream + '_>, ro: bool) -> Result<G::Output, Error> {
+        let count = if ro { 2 } else { 1 };
+        let u = self.hash_to_field.hash_to_field_stream(s, count)?;
+        let g = self.map_to_curve.group();
+        let r = u
+            .iter()
+            .fold(g.identity(), |r, u| g.add(&r, &self.map_to_curve.map(u)));
+        Ok(g.to_point(&g.mul(&r, &self.h_eff)))
+    }
+}
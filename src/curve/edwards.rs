@@ -0,0 +1,220 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg};
+
+use num_bigint::BigUint;
+
+use crate::curve::{EcPoint, EllipticCurve};
+use crate::field::{Field, FieldElement};
+use crate::fp::{Fp, FpElt};
+
+/// Curve is the twisted Edwards curve a * x^2 + y^2 = 1 + d * x^2 * y^2
+/// defined over [`Fp`]. Both edwards25519 (a = -1) and edwards448 (a = 1)
+/// have a square and d non-square, so their addition law is complete.
+#[derive(Clone)]
+pub struct Curve {
+    pub(crate) f: Fp,
+    pub(crate) a: FpElt,
+    pub(crate) d: FpElt,
+    pub(crate) r: BigUint,
+    pub(crate) h: BigUint,
+}
+
+/// Point is a point (X:Y:Z:T) of a twisted Edwards [`Curve`] in extended
+/// coordinates, with x = X/Z, y = Y/Z and x * y = T/Z. It keeps the field
+/// and the coefficients of its curve, which the addition law needs.
+#[derive(Clone)]
+pub struct Point {
+    f: Fp,
+    a: FpElt,
+    d: FpElt,
+    x: FpElt,
+    y: FpElt,
+    z: FpElt,
+    t: FpElt,
+}
+
+impl Curve {
+    /// Returns the coefficient a.
+    pub fn a(&self) -> &FpElt {
+        &self.a
+    }
+    /// Returns the coefficient d.
+    pub fn d(&self) -> &FpElt {
+        &self.d
+    }
+    /// Returns the point with projective coordinates (X:Y:Z), i.e., with
+    /// affine coordinates (X/Z, Y/Z); Z must be non-zero.
+    pub(crate) fn projective(&self, x: FpElt, y: FpElt, z: FpElt) -> Point {
+        Point {
+            f: self.f,
+            a: self.a.clone(),
+            d: self.d.clone(),
+            t: x.clone() * &y,
+            x: x * &z,
+            y: y * &z,
+            z: z.square(),
+        }
+    }
+}
+
+impl EllipticCurve for Curve {
+    type F = Fp;
+    type Point = Point;
+    fn get_field(&self) -> Fp {
+        self.f
+    }
+    fn get_order(&self) -> BigUint {
+        self.r.clone()
+    }
+    fn get_cofactor(&self) -> BigUint {
+        self.h.clone()
+    }
+    fn identity(&self) -> Point {
+        self.new_point(self.f.zero(), self.f.one())
+    }
+    fn new_point(&self, x: FpElt, y: FpElt) -> Point {
+        self.projective(x, y, self.f.one())
+    }
+    fn is_on_curve(&self, p: &Point) -> bool {
+        let (x, y) = p.affine();
+        let (x2, y2) = (x.square(), y.square());
+        let lhs = self.a.clone() * &x2 + &y2;
+        let rhs = self.f.one() + &(self.d.clone() * &x2 * &y2);
+        lhs == rhs
+    }
+}
+
+impl Point {
+    /// Returns the identity (0:1:1:0) of the curve of the point.
+    fn identity(&self) -> Point {
+        Point {
+            x: self.f.zero(),
+            y: self.f.one(),
+            z: self.f.one(),
+            t: self.f.zero(),
+            ..self.clone()
+        }
+    }
+    pub fn is_identity(&self) -> bool {
+        self.x.is_zero() && self.y == self.z
+    }
+    /// Scales the coordinates so that Z = 1.
+    pub fn normalize(&mut self) {
+        Point::normalize_batch(std::slice::from_mut(self))
+    }
+    /// Normalizes all the points with a single field inversion.
+    pub fn normalize_batch(points: &mut [Point]) {
+        let Some(f) = points.first().map(|p| p.f) else {
+            return;
+        };
+        let z: Vec<_> = points.iter().map(|p| p.z.clone()).collect();
+        for (p, inv_z) in points.iter_mut().zip(f.batch_inv(&z)) {
+            p.x = p.x.clone() * &inv_z;
+            p.y = p.y.clone() * &inv_z;
+            p.z = f.one();
+            p.t = p.x.clone() * &p.y;
+        }
+    }
+    /// Returns the affine coordinates (x, y). Every point of a twisted
+    /// Edwards curve is affine, including the identity (0, 1).
+    pub fn to_affine(&self) -> Option<(FpElt, FpElt)> {
+        Some(self.affine())
+    }
+    fn affine(&self) -> (FpElt, FpElt) {
+        let inv_z = self.z.inv();
+        (self.x.clone() * &inv_z, self.y.clone() * &inv_z)
+    }
+}
+
+impl Add<&Point> for &Point {
+    type Output = Point;
+    fn add(self, q: &Point) -> Point {
+        // add-2008-hwcd, which is complete on these curves.
+        let a = self.x.clone() * &q.x;
+        let b = self.y.clone() * &q.y;
+        let c = self.t.clone() * &self.d * &q.t;
+        let d = self.z.clone() * &q.z;
+        let e = (self.x.clone() + &self.y) * &(q.x.clone() + &q.y) - &a - &b;
+        let f = d.clone() - &c;
+        let g = d + &c;
+        let h = b - &(self.a.clone() * &a);
+        Point {
+            f: self.f,
+            a: self.a.clone(),
+            d: self.d.clone(),
+            x: e.clone() * &f,
+            y: g.clone() * &h,
+            z: f * &g,
+            t: e * &h,
+        }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        &self + &other
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+    fn neg(self) -> Point {
+        Point {
+            x: -self.x,
+            t: -self.t,
+            ..self
+        }
+    }
+}
+
+impl<'b> Mul<&'b BigUint> for Point {
+    type Output = Point;
+    /// Computes k*P using the left-to-right double-and-add method.
+    fn mul(self, k: &'b BigUint) -> Point {
+        let mut q = self.identity();
+        for i in (0..k.bits()).rev() {
+            q = &q + &q;
+            if k.bit(i) {
+                q = &q + &self;
+            }
+        }
+        q
+    }
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Point) -> bool {
+        self.x.clone() * &other.z == other.x.clone() * &self.z
+            && self.y.clone() * &other.z == other.y.clone() * &self.z
+    }
+}
+
+impl EcPoint for Point {
+    type Elt = FpElt;
+    fn is_identity(&self) -> bool {
+        Point::is_identity(self)
+    }
+    fn normalize(&mut self) {
+        Point::normalize(self)
+    }
+    fn normalize_batch(points: &mut [Self]) {
+        Point::normalize_batch(points)
+    }
+    fn to_affine(&self) -> Option<(FpElt, FpElt)> {
+        Point::to_affine(self)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (x, y) = self.affine();
+        write!(f, "(\n  x: {}\n  y: {}\n)", x, y)
+    }
+}
+
+impl fmt::Debug for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
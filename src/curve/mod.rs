@@ -0,0 +1,85 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Mul, Neg};
+
+use num_bigint::BigUint;
+
+use crate::field::Field;
+
+pub mod edwards;
+pub mod montgomery;
+pub mod weierstrass;
+
+/// EllipticCurve is an elliptic curve defined over the field `F`, whose
+/// points are of type `Point`. It is implemented by the Weierstrass,
+/// Montgomery and twisted Edwards curves of the crate, including the G2
+/// curve of BLS12-381.
+pub trait EllipticCurve: Clone + Send + Sync + 'static {
+    type F: Field;
+    type Point: EcPoint<Elt = <Self::F as Field>::Elt>;
+    fn get_field(&self) -> Self::F;
+    /// Returns the order of the prime-order subgroup.
+    fn get_order(&self) -> BigUint;
+    /// Returns the cofactor of the curve, i.e., the number of points of the
+    /// curve divided by the order of the prime-order subgroup.
+    fn get_cofactor(&self) -> BigUint;
+    fn identity(&self) -> Self::Point;
+    /// Returns the point with affine coordinates (x, y), which must lie on
+    /// the curve.
+    fn new_point(&self, x: <Self::F as Field>::Elt, y: <Self::F as Field>::Elt) -> Self::Point;
+    fn is_on_curve(&self, p: &Self::Point) -> bool;
+}
+
+/// EcPoint is a point of an [`EllipticCurve`]. Points are compared as
+/// points of the curve, whatever their internal coordinates.
+pub trait EcPoint:
+    Clone
+    + PartialEq
+    + Display
+    + Debug
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + Neg<Output = Self>
+    + for<'a> Mul<&'a BigUint, Output = Self>
+{
+    type Elt;
+    fn is_identity(&self) -> bool;
+    /// Scales the internal coordinates of the point, so that the affine
+    /// coordinates can be read without an inversion.
+    fn normalize(&mut self);
+    /// Normalizes all the points; curves that can share the inversions
+    /// across the points override the default, which normalizes them one by
+    /// one.
+    fn normalize_batch(points: &mut [Self]) {
+        points.iter_mut().for_each(|p| p.normalize())
+    }
+    /// Returns the affine coordinates (x, y), or None if the point is the
+    /// point at infinity.
+    fn to_affine(&self) -> Option<(Self::Elt, Self::Elt)>;
+}
+
+/// MapToCurve is a deterministic function from field elements to points of a
+/// curve, e.g., the maps of Section 6 of the draft.
+pub trait MapToCurve: Send + Sync {
+    type E: EllipticCurve;
+    fn map(
+        &self,
+        u: &<<Self::E as EllipticCurve>::F as Field>::Elt,
+    ) -> <Self::E as EllipticCurve>::Point;
+    /// Maps every element of `u` to a point. Maps that can share their field
+    /// inversions across the batch override the default, which maps the
+    /// elements one by one.
+    fn map_batch(
+        &self,
+        u: &[<<Self::E as EllipticCurve>::F as Field>::Elt],
+    ) -> Vec<<Self::E as EllipticCurve>::Point> {
+        u.iter().map(|u| self.map(u)).collect()
+    }
+}
+
+/// GetCurve is implemented by curve identifiers, such as
+/// [`WeCurveID`](crate::WeCurveID), to build the curve they name.
+pub trait GetCurve {
+    type E: EllipticCurve;
+    fn get(&self) -> Self::E;
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg};
+
+use num_bigint::BigUint;
+
+use crate::curve::{weierstrass, EcPoint, EllipticCurve};
+use crate::field::FieldElement;
+use crate::fp::{Fp, FpElt};
+
+/// Curve is the Montgomery curve B * y^2 = x^3 + A * x^2 + x defined over
+/// [`Fp`]. Its points are handled on the isomorphic Weierstrass curve
+/// Y^2 = X^3 + a * X + b, where x = B * X - A/3 and y = B * Y, with
+/// a = (3 - A^2) / (3 * B^2) and b = (2 * A^3 - 9 * A) / (27 * B^3).
+#[derive(Clone)]
+pub struct Curve {
+    a: FpElt,
+    b: FpElt,
+    /// A/3 and 1/B, which move points between both models.
+    a3: FpElt,
+    inv_b: FpElt,
+    w: weierstrass::Curve,
+}
+
+/// Point is a point on a Montgomery [`Curve`], held as a point of the
+/// isomorphic Weierstrass curve.
+#[derive(Clone)]
+pub struct Point {
+    w: weierstrass::Point,
+    b: FpElt,
+    a3: FpElt,
+}
+
+impl Curve {
+    /// Returns the curve with coefficients A and B, whose prime-order
+    /// subgroup has order r and cofactor h.
+    pub(crate) fn new(f: Fp, a: FpElt, b: FpElt, r: BigUint, h: BigUint) -> Curve {
+        let three = f.from_i32(3);
+        let inv_b = b.inv();
+        let a3 = a.clone() * &three.inv();
+        let wa = (three.clone() - &a.square()) * &(three * &b.square()).inv();
+        let wb = (a.square() * &a * &f.from_i32(2) - &(a.clone() * &f.from_i32(9)))
+            * &(b.square() * &b * &f.from_i32(27)).inv();
+        Curve {
+            w: weierstrass::Curve {
+                f,
+                a: wa,
+                b: wb,
+                r,
+                h,
+            },
+            a,
+            b,
+            a3,
+            inv_b,
+        }
+    }
+    /// Returns the coefficient A.
+    pub fn a(&self) -> &FpElt {
+        &self.a
+    }
+    /// Returns the coefficient B.
+    pub fn b(&self) -> &FpElt {
+        &self.b
+    }
+    /// Returns the isomorphic Weierstrass curve.
+    pub(crate) fn weierstrass(&self) -> &weierstrass::Curve {
+        &self.w
+    }
+    /// Returns the point of the curve that is `w` on the Weierstrass curve.
+    pub(crate) fn point(&self, w: weierstrass::Point) -> Point {
+        Point {
+            w,
+            b: self.b.clone(),
+            a3: self.a3.clone(),
+        }
+    }
+}
+
+impl EllipticCurve for Curve {
+    type F = Fp;
+    type Point = Point;
+    fn get_field(&self) -> Fp {
+        self.w.f
+    }
+    fn get_order(&self) -> BigUint {
+        self.w.r.clone()
+    }
+    fn get_cofactor(&self) -> BigUint {
+        self.w.h.clone()
+    }
+    fn identity(&self) -> Point {
+        self.point(self.w.identity())
+    }
+    fn new_point(&self, x: FpElt, y: FpElt) -> Point {
+        let wx = (x + &self.a3) * &self.inv_b;
+        let wy = y * &self.inv_b;
+        self.point(self.w.new_point(wx, wy))
+    }
+    fn is_on_curve(&self, p: &Point) -> bool {
+        self.w.is_on_curve(&p.w)
+    }
+}
+
+impl Point {
+    pub fn is_identity(&self) -> bool {
+        self.w.is_identity()
+    }
+    pub fn normalize(&mut self) {
+        self.w.normalize()
+    }
+    /// Returns the affine coordinates (x, y), or None for the identity.
+    pub fn to_affine(&self) -> Option<(FpElt, FpElt)> {
+        let (x, y) = self.w.to_affine()?;
+        Some((x * &self.b - &self.a3, y * &self.b))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        Point {
+            w: self.w + other.w,
+            ..self
+        }
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+    fn neg(self) -> Point {
+        Point { w: -self.w, ..self }
+    }
+}
+
+impl<'b> Mul<&'b BigUint> for Point {
+    type Output = Point;
+    fn mul(self, k: &'b BigUint) -> Point {
+        Point {
+            w: self.w.clone() * k,
+            ..self
+        }
+    }
+}
+
+impl PartialEq for Point {
+    fn eq(&self, other: &Point) -> bool {
+        self.w == other.w
+    }
+}
+
+impl EcPoint for Point {
+    type Elt = FpElt;
+    fn is_identity(&self) -> bool {
+        Point::is_identity(self)
+    }
+    fn normalize(&mut self) {
+        Point::normalize(self)
+    }
+    fn normalize_batch(points: &mut [Self]) {
+        let mut w: Vec<_> = points.iter().map(|p| p.w.clone()).collect();
+        weierstrass::Point::normalize_batch(&mut w);
+        for (p, w) in points.iter_mut().zip(w) {
+            p.w = w;
+        }
+    }
+    fn to_affine(&self) -> Option<(FpElt, FpElt)> {
+        Point::to_affine(self)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_affine() {
+            None => write!(f, "Identity"),
+            Some((x, y)) => write!(f, "(\n  x: {}\n  y: {}\n)", x, y),
+        }
+    }
+}
+
+impl fmt::Debug for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
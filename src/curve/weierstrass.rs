@@ -0,0 +1,290 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg};
+
+use num_bigint::BigUint;
+
+use crate::curve::{EcPoint, EllipticCurve};
+use crate::field::{Field, FieldElement};
+use crate::fp::Fp;
+
+/// Curve is the short Weierstrass curve y^2 = x^3 + A * x + B defined over
+/// the field `F`, e.g., P-256 over [`Fp`] or the G2 curve of BLS12-381 over
+/// [`Fp2`](crate::Fp2).
+#[derive(Clone)]
+pub struct Curve<F: Field = Fp> {
+    pub(crate) f: F,
+    pub(crate) a: F::Elt,
+    pub(crate) b: F::Elt,
+    pub(crate) r: BigUint,
+    pub(crate) h: BigUint,
+}
+
+/// Point is a point on [`Curve`] in Jacobian coordinates (X:Y:Z), which
+/// represents the affine point (X/Z^2, Y/Z^3). It keeps the field and the
+/// coefficient A of its curve, which doubling needs.
+#[derive(Clone)]
+pub struct Point<F: Field = Fp> {
+    pub(crate) f: F,
+    pub(crate) a: F::Elt,
+    pub(crate) x: F::Elt,
+    pub(crate) y: F::Elt,
+    pub(crate) z: F::Elt,
+}
+
+impl<F: Field> Curve<F> {
+    /// Returns the coefficient A.
+    pub fn a(&self) -> &F::Elt {
+        &self.a
+    }
+    /// Returns the coefficient B.
+    pub fn b(&self) -> &F::Elt {
+        &self.b
+    }
+    /// Returns g(x) = x^3 + A * x + B.
+    pub(crate) fn g(&self, x: &F::Elt) -> F::Elt {
+        (x.square() + &self.a) * x + &self.b
+    }
+    pub fn get_field(&self) -> F {
+        self.f.clone()
+    }
+    pub fn identity(&self) -> Point<F> {
+        self.point(self.f.one(), self.f.one(), self.f.zero())
+    }
+    /// Returns the point with affine coordinates (x, y).
+    pub fn new_point(&self, x: F::Elt, y: F::Elt) -> Point<F> {
+        self.point(x, y, self.f.one())
+    }
+    /// Returns the point with Jacobian coordinates (X:Y:Z).
+    pub(crate) fn point(&self, x: F::Elt, y: F::Elt, z: F::Elt) -> Point<F> {
+        Point {
+            f: self.f.clone(),
+            a: self.a.clone(),
+            x,
+            y,
+            z,
+        }
+    }
+}
+
+impl<F: Field> EllipticCurve for Curve<F> {
+    type F = F;
+    type Point = Point<F>;
+    fn get_field(&self) -> F {
+        self.f.clone()
+    }
+    fn get_order(&self) -> BigUint {
+        self.r.clone()
+    }
+    fn get_cofactor(&self) -> BigUint {
+        self.h.clone()
+    }
+    fn identity(&self) -> Point<F> {
+        Curve::identity(self)
+    }
+    fn new_point(&self, x: F::Elt, y: F::Elt) -> Point<F> {
+        Curve::new_point(self, x, y)
+    }
+    fn is_on_curve(&self, p: &Point<F>) -> bool {
+        if p.is_identity() {
+            return true;
+        }
+        let z2 = p.z.square();
+        let z4 = z2.square();
+        let z6 = z4.clone() * &z2;
+        let lhs = p.y.square();
+        let rhs = (p.x.square() + &(self.a.clone() * &z4)) * &p.x + &(self.b.clone() * &z6);
+        lhs == rhs
+    }
+}
+
+impl<F: Field> Point<F> {
+    fn identity(&self) -> Point<F> {
+        Point {
+            f: self.f.clone(),
+            a: self.a.clone(),
+            x: self.f.one(),
+            y: self.f.one(),
+            z: self.f.zero(),
+        }
+    }
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+    /// Scales the coordinates so that Z = 1, unless the point is the identity.
+    pub fn normalize(&mut self) {
+        Point::normalize_batch(std::slice::from_mut(self))
+    }
+    /// Normalizes all the points with a single field inversion.
+    pub fn normalize_batch(points: &mut [Point<F>]) {
+        let Some(f) = points.first().map(|p| p.f.clone()) else {
+            return;
+        };
+        let z: Vec<_> = points.iter().map(|p| p.z.clone()).collect();
+        for (p, inv_z) in points.iter_mut().zip(f.batch_inv(&z)) {
+            if p.is_identity() {
+                continue;
+            }
+            let inv_z2 = inv_z.square();
+            p.x = p.x.clone() * &inv_z2;
+            p.y = p.y.clone() * &inv_z2 * &inv_z;
+            p.z = f.one();
+        }
+    }
+    /// Returns the affine coordinates (x, y), or None for the identity.
+    pub fn to_affine(&self) -> Option<(F::Elt, F::Elt)> {
+        if self.is_identity() {
+            return None;
+        }
+        let mut p = self.clone();
+        p.normalize();
+        Some((p.x, p.y))
+    }
+    /// Computes k*P using the left-to-right double-and-add method.
+    fn scalar_mult(&self, k: &BigUint) -> Point<F> {
+        let mut q = self.identity();
+        for i in (0..k.bits()).rev() {
+            q = q.double();
+            if k.bit(i) {
+                q = &q + self;
+            }
+        }
+        q
+    }
+    fn double(&self) -> Point<F> {
+        if self.is_identity() || self.y.is_zero() {
+            return self.identity();
+        }
+        // dbl-2007-bl, for any a.
+        let xx = self.x.square();
+        let yy = self.y.square();
+        let yyyy = yy.square();
+        let zz = self.z.square();
+        let s = (self.x.clone() + &yy).square() - &xx - &yyyy;
+        let s = s.clone() + &s;
+        let m = xx.clone() + &xx + &xx + &(self.a.clone() * &zz.square());
+        let x3 = m.square() - &s - &s;
+        let yyyy8 = {
+            let c2 = yyyy.clone() + &yyyy;
+            let c4 = c2.clone() + &c2;
+            c4.clone() + &c4
+        };
+        let y3 = m * &(s - &x3) - &yyyy8;
+        let z3 = (self.y.clone() + &self.z).square() - &yy - &zz;
+        Point {
+            x: x3,
+            y: y3,
+            z: z3,
+            ..self.identity()
+        }
+    }
+}
+
+impl<F: Field> Add<&Point<F>> for &Point<F> {
+    type Output = Point<F>;
+    fn add(self, other: &Point<F>) -> Point<F> {
+        if self.is_identity() {
+            return other.clone();
+        }
+        if other.is_identity() {
+            return self.clone();
+        }
+        // add-2007-bl
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        let u1 = self.x.clone() * &z2z2;
+        let u2 = other.x.clone() * &z1z1;
+        let s1 = self.y.clone() * &other.z * &z2z2;
+        let s2 = other.y.clone() * &self.z * &z1z1;
+        if u1 == u2 {
+            return if s1 == s2 {
+                self.double()
+            } else {
+                self.identity()
+            };
+        }
+        let h = u2 - &u1;
+        let i = (h.clone() + &h).square();
+        let j = h.clone() * &i;
+        let r = s2 - &s1;
+        let r = r.clone() + &r;
+        let v = u1 * &i;
+        let x3 = r.square() - &j - &v - &v;
+        let s1j = s1 * &j;
+        let y3 = r * &(v - &x3) - &s1j - &s1j;
+        let z3 = ((self.z.clone() + &other.z).square() - &z1z1 - &z2z2) * &h;
+        Point {
+            x: x3,
+            y: y3,
+            z: z3,
+            ..self.identity()
+        }
+    }
+}
+
+impl<F: Field> Add for Point<F> {
+    type Output = Point<F>;
+    fn add(self, other: Point<F>) -> Point<F> {
+        &self + &other
+    }
+}
+
+impl<F: Field> Neg for Point<F> {
+    type Output = Point<F>;
+    fn neg(self) -> Point<F> {
+        Point { y: -self.y, ..self }
+    }
+}
+
+impl<'b, F: Field> Mul<&'b BigUint> for Point<F> {
+    type Output = Point<F>;
+    fn mul(self, k: &'b BigUint) -> Point<F> {
+        self.scalar_mult(k)
+    }
+}
+
+impl<F: Field> PartialEq for Point<F> {
+    fn eq(&self, other: &Point<F>) -> bool {
+        match (self.is_identity(), other.is_identity()) {
+            (true, true) => true,
+            (false, false) => {
+                let z1z1 = self.z.square();
+                let z2z2 = other.z.square();
+                let x_eq = self.x.clone() * &z2z2 == other.x.clone() * &z1z1;
+                let y_eq = self.y.clone() * &other.z * &z2z2 == other.y.clone() * &self.z * &z1z1;
+                x_eq && y_eq
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<F: Field> EcPoint for Point<F> {
+    type Elt = F::Elt;
+    fn is_identity(&self) -> bool {
+        Point::is_identity(self)
+    }
+    fn normalize(&mut self) {
+        Point::normalize(self)
+    }
+    fn normalize_batch(points: &mut [Self]) {
+        Point::normalize_batch(points)
+    }
+    fn to_affine(&self) -> Option<(F::Elt, F::Elt)> {
+        Point::to_affine(self)
+    }
+}
+
+impl<F: Field> fmt::Display for Point<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.to_affine() {
+            None => write!(f, "Identity"),
+            Some((x, y)) => write!(f, "(\n  x: {}\n  y: {}\n)", x, y),
+        }
+    }
+}
+
+impl<F: Field> fmt::Debug for Point<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -1,49 +1,140 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-use redox_ecc::edwards::{Curve as EdCurve, Ell2};
-use redox_ecc::ellipticcurve::{EllipticCurve, MapToCurve, RationalMap};
-use redox_ecc::instances::{
-    edwards25519_to_curve25519, edwards448_to_curve448, EdCurveID, GetCurve, EDWARDS25519,
-    EDWARDS448,
-};
-use redox_ecc::montgomery::Curve as MtCurve;
-use redox_ecc::ops::FromFactory;
-
 use crate::api::{
-    Encoding, ExpID, GetHashToCurve, HashID, HashToCurve, HashToField, MapID, Suite, XofID,
+    CheckMap, ClearCofactor, CtEncoder, Encoding, ExpID, GetHashToCurve, HashID, HashToCurve,
+    HashToField, InverseMapBuilder, LazyInverseMap, MapID, ModulusBits, Suite, XofID,
 };
+use crate::cofactor::DoubleCofactor;
+use crate::ct::{CtEll2Edwards, CtEncoding, CtGf};
+use crate::curve::edwards::{Curve as EdCurve, Point as EdPoint};
+use crate::curve::{EllipticCurve, GetCurve, MapToCurve};
+use crate::error::Error;
 use crate::expander::get_expander;
-use crate::fp::FpHasher;
+use crate::field::Field;
+use crate::findz::check_z_ell2;
+use crate::fp::{Fp, FpHasher};
+use crate::inverse::{Edwards25519Inverse, Edwards448Inverse};
+use crate::map::{Ell2, Ell2Edwards, RationalMap};
+use crate::mont::{MtCurveID, F25519, F448, R25519, R448};
 use crate::register_in_map;
 
+/// EdCurveID identifies the twisted Edwards curves of the suites.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EdCurveID {
+    Edwards25519,
+    Edwards448,
+}
+
+impl GetCurve for EdCurveID {
+    type E = EdCurve;
+    fn get(&self) -> EdCurve {
+        match self {
+            EdCurveID::Edwards25519 => {
+                let f = Fp::new(&F25519);
+                EdCurve {
+                    a: -f.one(),
+                    d: f.from("0x52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3"),
+                    r: R25519.clone(),
+                    h: 8u32.into(),
+                    f,
+                }
+            }
+            EdCurveID::Edwards448 => {
+                let f = Fp::new(&F448);
+                EdCurve {
+                    a: f.one(),
+                    d: f.from_i32(-39081),
+                    r: R448.clone(),
+                    h: 4u32.into(),
+                    f,
+                }
+            }
+        }
+    }
+}
+
 impl GetHashToCurve for Suite<EdCurveID> {
     type E = EdCurve;
-    fn get(&self, dst: &[u8]) -> Box<dyn HashToCurve<E = Self::E>> {
+    fn try_get(&self, dst: &[u8]) -> Result<Box<dyn HashToCurve<E = Self::E>>, Error> {
         let curve = self.curve.get();
         let f = curve.get_field();
-        let cofactor = curve.new_scalar(curve.get_cofactor());
-        let ratmap: Option<Box<dyn RationalMap<E0 = EdCurve, E1 = MtCurve>>> =
-            if self.curve == EDWARDS25519 {
-                Some(Box::new(edwards25519_to_curve25519()))
-            } else if self.curve == EDWARDS448 {
-                Some(Box::new(edwards448_to_curve448()))
-            } else {
-                None
+        // The Montgomery curve that the map goes through, and the rational
+        // map from it to the curve.
+        let (mt, ratmap) = match self.curve {
+            EdCurveID::Edwards25519 => {
+                let mt = MtCurveID::Curve25519.get();
+                let ratmap = RationalMap::edwards25519(&mt)?;
+                (mt, ratmap)
+            }
+            EdCurveID::Edwards448 => (MtCurveID::Curve448.get(), RationalMap::Edwards448),
+        };
+        let z = match self.map {
+            MapID::ELL2(z) => z,
+            _ => return Err(Error::UnsupportedMap(self.name.to_string())),
+        };
+        let ell2 = Ell2::new(mt.clone(), f.from_i32(z));
+        let ell2 = Ell2Edwards::new(curve.clone(), ell2, ratmap);
+        let ct: Option<Box<dyn CtEncoder<Point = EdPoint>>> = if self.ct {
+            let g = CtGf::new(f);
+            let map_to_curve = Box::new(CtEll2Edwards::new(&g, &ell2));
+            let exp = get_expander(self.exp, dst, self.k);
+            let hash_to_field = FpHasher {
+                f: g,
+                exp,
+                l: self.l,
             };
-        let map_to_curve: Box<dyn MapToCurve<E = EdCurve>> = match self.map {
-            MapID::ELL2(z) => Box::new(Ell2::new(curve.clone(), f.from(z), ratmap)),
-            _ => unimplemented!(),
+            let h_eff = curve.get_cofactor();
+            Some(Box::new(CtEncoding::new(
+                hash_to_field,
+                map_to_curve,
+                h_eff,
+            )))
+        } else {
+            None
         };
+        let map_to_curve: Box<dyn MapToCurve<E = EdCurve>> = Box::new(ell2);
         let exp = get_expander(self.exp, dst, self.k);
         let hash_to_field: Box<dyn HashToField<F = <EdCurve as EllipticCurve>::F>> =
             Box::new(FpHasher { f, exp, l: self.l });
-        Box::new(Encoding {
+        let clear_cofactor: Box<dyn ClearCofactor<Point = <EdCurve as EllipticCurve>::Point>> =
+            match self.curve {
+                EdCurveID::Edwards25519 => Box::new(DoubleCofactor::<EdCurve>::new(3)),
+                EdCurveID::Edwards448 => Box::new(DoubleCofactor::<EdCurve>::new(2)),
+            };
+        let (e, id) = (curve.clone(), self.curve);
+        let inverse_map: InverseMapBuilder<EdCurve> = Box::new(move || match id {
+            EdCurveID::Edwards25519 => Ok(Box::new(Edwards25519Inverse::new(e.clone(), &mt, z)?)),
+            EdCurveID::Edwards448 => Ok(Box::new(Edwards448Inverse::new(e.clone(), &mt, z)?)),
+        });
+        Ok(Box::new(Encoding {
             curve,
             hash_to_field,
             map_to_curve,
-            cofactor,
+            clear_cofactor,
+            inverse_map: LazyInverseMap::new(Some(inverse_map)),
+            l: self.l,
             ro: self.ro,
-        })
+            ct,
+        }))
+    }
+}
+
+impl ModulusBits for EdCurveID {
+    fn modulus_bits(&self) -> usize {
+        self.get().get_field().get_modulus().bits() as usize
+    }
+}
+
+impl CheckMap for EdCurveID {
+    fn check_map(&self, map: MapID) -> Result<(), Error> {
+        match map {
+            MapID::ELL2(z) => check_z_ell2(&self.get().get_field().from_i32(z)),
+            _ => Err(Error::InvalidSuite(format!(
+                "{:?} is not a map to {:?}",
+                map, self
+            ))),
+        }
     }
 }
 
@@ -59,46 +150,49 @@ lazy_static! {
 }
 
 pub static EDWARDS25519_XMDSHA256_ELL2_NU_: Suite<EdCurveID> = Suite {
-    name: "edwards25519_XMD:SHA-256_ELL2_NU_",
-    curve: EDWARDS25519,
+    name: Cow::Borrowed("edwards25519_XMD:SHA-256_ELL2_NU_"),
+    curve: EdCurveID::Edwards25519,
     k: 128,
     exp: ExpID::XMD(HashID::SHA256),
     map: MapID::ELL2(2),
     l: 48,
     ro: false,
+    ct: false,
 };
 pub static EDWARDS25519_XMDSHA256_ELL2_RO_: Suite<EdCurveID> = Suite {
-    name: "edwards25519_XMD:SHA-256_ELL2_RO_",
+    name: Cow::Borrowed("edwards25519_XMD:SHA-256_ELL2_RO_"),
     ro: true,
     ..EDWARDS25519_XMDSHA256_ELL2_NU_
 };
 
 pub static EDWARDS25519_XMDSHA512_ELL2_NU_: Suite<EdCurveID> = Suite {
-    name: "edwards25519_XMD:SHA-512_ELL2_NU_",
-    curve: EDWARDS25519,
+    name: Cow::Borrowed("edwards25519_XMD:SHA-512_ELL2_NU_"),
+    curve: EdCurveID::Edwards25519,
     map: MapID::ELL2(2),
     k: 128,
     exp: ExpID::XMD(HashID::SHA512),
     l: 48,
     ro: false,
+    ct: false,
 };
 pub static EDWARDS25519_XMDSHA512_ELL2_RO_: Suite<EdCurveID> = Suite {
-    name: "edwards25519_XMD:SHA-512_ELL2_RO_",
+    name: Cow::Borrowed("edwards25519_XMD:SHA-512_ELL2_RO_"),
     ro: true,
     ..EDWARDS25519_XMDSHA512_ELL2_NU_
 };
 
 pub static EDWARDS448_XOFSHAKE256_ELL2_NU_: Suite<EdCurveID> = Suite {
-    name: "edwards448_XOF:SHAKE256_ELL2_NU_",
-    curve: EDWARDS448,
+    name: Cow::Borrowed("edwards448_XOF:SHAKE256_ELL2_NU_"),
+    curve: EdCurveID::Edwards448,
     map: MapID::ELL2(-1),
     k: 224,
     exp: ExpID::XOF(XofID::SHAKE256),
     l: 84,
     ro: false,
+    ct: false,
 };
 pub static EDWARDS448_XOFSHAKE256_ELL2_RO_: Suite<EdCurveID> = Suite {
-    name: "edwards448_XOF:SHAKE256_ELL2_RO_",
+    name: Cow::Borrowed("edwards448_XOF:SHAKE256_ELL2_RO_"),
     ro: true,
     ..EDWARDS448_XOFSHAKE256_ELL2_NU_
 };
@@ -0,0 +1,270 @@
+use num_bigint::{BigInt, BigUint};
+use rand_core::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::curve::GetCurve;
+use crate::error::Error;
+use crate::gf::Gf;
+use crate::weier::WeCurveID;
+
+lazy_static! {
+    static ref P: BigUint = BigUint::parse_bytes(
+        b"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+        16
+    )
+    .unwrap();
+    static ref N: BigUint = BigUint::parse_bytes(
+        b"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+        16
+    )
+    .unwrap();
+    static ref F: Gf = Gf::new(&BigInt::from(P.clone()));
+    static ref G: Point = Some((
+        BigUint::parse_bytes(
+            b"79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            16
+        )
+        .unwrap(),
+        BigUint::parse_bytes(
+            b"483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+            16
+        )
+        .unwrap(),
+    ));
+    static ref B: BigUint = BigUint::from(7u32);
+    // This is the square root of -3 fixed by BIP324.
+    static ref C0: BigUint = BigUint::parse_bytes(
+        b"0a2d2ba93507f1df233770c2a797962cc61f6d15da14ecd47d8d27ae1cd5f852",
+        16
+    )
+    .unwrap();
+    // c3 = (1 - c0) / 2 and c4 = (1 + c0) / 2.
+    static ref C3: BigUint = F.mul(&F.sub(&BigUint::from(1u32), &C0), &F.inv(&BigUint::from(2u32)));
+    static ref C4: BigUint = F.mul(&F.add(&BigUint::from(1u32), &C0), &F.inv(&BigUint::from(2u32)));
+}
+
+/// Point is an affine point of secp256k1, or None for the point at infinity.
+type Point = Option<(BigUint, BigUint)>;
+
+/// Computes k*P on the secp256k1 curve with a Montgomery ladder: each of
+/// the 256 bits of k costs one addition and one doubling, whatever its
+/// value. The field arithmetic of [`Fp`](crate::Fp) is not constant time,
+/// so this is no defense against a fine-grained timing attacker.
+fn mul(p: &Point, k: &BigUint) -> Point {
+    let curve = WeCurveID::Secp256k1.get();
+    let f = curve.get_field();
+    let (x, y) = p.as_ref()?;
+    let mut r = [curve.identity(), curve.new_point(f.elt(x), f.elt(y))];
+    for i in (0..256).rev() {
+        let b = usize::from(k.bit(i));
+        r[1 - b] = r[0].clone() + r[1].clone();
+        r[b] = r[b].clone() + r[b].clone();
+    }
+    let [q, _] = r;
+    let (x, y) = q.to_affine()?;
+    Some((x.value().clone(), y.value().clone()))
+}
+
+/// Returns x^3 + 7.
+fn g(x: &BigUint) -> BigUint {
+    F.add(&F.mul(&F.mul(x, x), x), &B)
+}
+
+fn is_valid_x(x: &BigUint) -> bool {
+    F.is_square(&g(x))
+}
+
+/// Returns the point with x-coordinate x whose y-coordinate is odd if `odd`
+/// is set.
+fn lift_x(x: &BigUint, odd: bool) -> Point {
+    let y = F.sqrt(&g(x))?;
+    let y = if F.sgn0(&y) == odd { y } else { F.neg(&y) };
+    Some((x.clone(), y))
+}
+
+fn to_bytes(x: &BigUint) -> [u8; 32] {
+    let b = x.to_bytes_be();
+    let mut out = [0u8; 32];
+    out[32 - b.len()..].copy_from_slice(&b);
+    out
+}
+
+/// Parses a secret key, which must be an integer in [1, n).
+fn secret_key(seckey: &[u8; 32]) -> Result<BigUint, Error> {
+    let k = BigUint::from_bytes_be(seckey);
+    if k == BigUint::default() || k >= *N {
+        return Err(Error::InvalidKey(hex::encode(seckey)));
+    }
+    Ok(k)
+}
+
+/// Implements XSwiftEC of BIP324, which maps any pair of field elements
+/// (u, t) to the x-coordinate of a point of secp256k1.
+pub fn xswiftec(u: &BigUint, t: &BigUint) -> BigUint {
+    let f = &*F;
+    let one = BigUint::from(1u32);
+    let u = if f.is_zero(u) { one.clone() } else { u % &*P };
+    let t = if f.is_zero(t) { one } else { t % &*P };
+    let gu = g(&u);
+    let t = if f.is_zero(&f.add(&gu, &f.mul(&t, &t))) {
+        f.add(&t, &t)
+    } else {
+        t
+    };
+    let x = f.mul(&f.sub(&gu, &f.mul(&t, &t)), &f.inv(&f.add(&t, &t)));
+    let y = f.mul(&f.add(&x, &t), &f.inv(&f.mul(&C0, &u)));
+    let half = f.inv(&BigUint::from(2u32));
+    let x_over_y = f.mul(&x, &f.inv(&y));
+    let x3 = f.add(&u, &f.mul(&BigUint::from(4u32), &f.mul(&y, &y)));
+    let x2 = f.mul(&f.sub(&f.neg(&x_over_y), &u), &half);
+    let x1 = f.mul(&f.sub(&x_over_y, &u), &half);
+    [x3, x2].into_iter().find(is_valid_x).unwrap_or(x1)
+}
+
+/// Implements XSwiftEC_inv of BIP324: outputs a t such that
+/// xswiftec(u, t) = x, or None. Each of the eight cases gives at most one t,
+/// and no two cases give the same one.
+pub fn xswiftec_inv(x: &BigUint, u: &BigUint, case: usize) -> Option<BigUint> {
+    let f = &*F;
+    if f.is_zero(u) {
+        return None;
+    }
+    let (x, u) = (x % &*P, u % &*P);
+    let (s, v) = if case & 2 == 0 {
+        // x is x1 or x2, so -x - u must not be a valid x3.
+        if is_valid_x(&f.neg(&f.add(&x, &u))) {
+            return None;
+        }
+        // s = -(u^3 + 7) / (u^2 + u*x + x^2).
+        let d = f.add(&f.mul(&u, &f.add(&u, &x)), &f.mul(&x, &x));
+        let s = f.neg(&f.mul(&g(&u), &f.inv(&d)));
+        (s, x)
+    } else {
+        let s = f.sub(&x, &u);
+        // r = sqrt(-s * (4 * (u^3 + 7) + 3 * u^2 * s)).
+        let q = f.add(
+            &f.mul(&BigUint::from(4u32), &g(&u)),
+            &f.mul(&BigUint::from(3u32), &f.mul(&f.mul(&u, &u), &s)),
+        );
+        let r = f.sqrt(&f.neg(&f.mul(&s, &q)))?;
+        if (case & 1 == 1 && f.is_zero(&r)) || f.is_zero(&s) {
+            return None;
+        }
+        // v = (r / s - u) / 2.
+        let v = f.mul(
+            &f.sub(&f.mul(&r, &f.inv(&s)), &u),
+            &f.inv(&BigUint::from(2u32)),
+        );
+        (s, v)
+    };
+    let w = f.sqrt(&s)?;
+    let (w, c) = match case & 5 {
+        0 => (f.neg(&w), &*C3),
+        1 => (w, &*C4),
+        4 => (w, &*C3),
+        _ => (f.neg(&w), &*C4),
+    };
+    Some(f.mul(&w, &f.add(&f.mul(c, &u), &v)))
+}
+
+/// Decodes a 64-byte ElligatorSwift encoding u || t into the public key it
+/// represents, written in SEC1 compressed form. The y-coordinate has the
+/// parity of t. Every 64-byte string is a valid encoding.
+pub fn ellswift_decode(enc: &[u8; 64]) -> [u8; 33] {
+    let u = BigUint::from_bytes_be(&enc[..32]) % &*P;
+    let t = BigUint::from_bytes_be(&enc[32..]) % &*P;
+    let mut out = [0u8; 33];
+    out[0] = if F.sgn0(&t) { 0x03 } else { 0x02 };
+    out[1..].copy_from_slice(&to_bytes(&xswiftec(&u, &t)));
+    out
+}
+
+/// Encodes a public key, given in SEC1 compressed form, as 64 bytes that are
+/// indistinguishable from random. It implements XElligatorSwift of BIP324:
+/// u is taken at random until one of the eight cases of `xswiftec_inv`,
+/// also taken at random, gives a t. Then, t is negated if needed to match
+/// the parity of y.
+pub fn ellswift_encode(pubkey: &[u8; 33], rng: &mut dyn RngCore) -> Result<[u8; 64], Error> {
+    let x = BigUint::from_bytes_be(&pubkey[1..]);
+    let odd = match pubkey[0] {
+        0x02 => false,
+        0x03 => true,
+        _ => return Err(Error::InvalidKey(hex::encode(pubkey))),
+    };
+    if x >= *P || !is_valid_x(&x) {
+        return Err(Error::InvalidKey(hex::encode(pubkey)));
+    }
+    let mut b = [0u8; 32];
+    loop {
+        rng.fill_bytes(&mut b);
+        let u = BigUint::from_bytes_be(&b) % &*P;
+        let case = (rng.next_u32() & 7) as usize;
+        if let Some(t) = xswiftec_inv(&x, &u, case) {
+            let t = if F.sgn0(&t) == odd { t } else { F.neg(&t) };
+            let mut out = [0u8; 64];
+            out[..32].copy_from_slice(&to_bytes(&u));
+            out[32..].copy_from_slice(&to_bytes(&t));
+            return Ok(out);
+        }
+    }
+}
+
+/// Generates a secret key at random and outputs it together with the
+/// ElligatorSwift encoding of its public key.
+pub fn ellswift_create(rng: &mut dyn RngCore) -> ([u8; 32], [u8; 64]) {
+    let mut seckey = [0u8; 32];
+    let k = loop {
+        rng.fill_bytes(&mut seckey);
+        if let Ok(k) = secret_key(&seckey) {
+            break k;
+        }
+    };
+    let (x, y) = mul(&G, &k).unwrap();
+    let mut pubkey = [0u8; 33];
+    pubkey[0] = if F.sgn0(&y) { 0x03 } else { 0x02 };
+    pubkey[1..].copy_from_slice(&to_bytes(&x));
+    let enc = ellswift_encode(&pubkey, rng).expect("k*G is a point of the curve");
+    (seckey, enc)
+}
+
+/// Outputs the x-coordinate of seckey * P, where P is the point decoded from
+/// `theirs`. The parity of y is ignored, as the x-coordinate of the product
+/// does not depend on it.
+pub fn ellswift_ecdh_xonly(theirs: &[u8; 64], seckey: &[u8; 32]) -> Result<[u8; 32], Error> {
+    let k = secret_key(seckey)?;
+    let u = BigUint::from_bytes_be(&theirs[..32]);
+    let t = BigUint::from_bytes_be(&theirs[32..]);
+    let p = lift_x(&xswiftec(&u, &t), false);
+    // The group has prime order, so k * P is not the point at infinity.
+    let (x, _) = mul(&p, &k).unwrap();
+    Ok(to_bytes(&x))
+}
+
+/// Computes the shared secret of the v2 transport protocol of BIP324, i.e.,
+/// the tagged hash "bip324_ellswift_xonly_ecdh" of both encodings, the one of
+/// the initiator first, followed by the x-only ECDH output.
+pub fn ellswift_xdh(
+    ours: &[u8; 64],
+    theirs: &[u8; 64],
+    seckey: &[u8; 32],
+    initiating: bool,
+) -> Result<[u8; 32], Error> {
+    let x = ellswift_ecdh_xonly(theirs, seckey)?;
+    let (a, b) = if initiating {
+        (ours, theirs)
+    } else {
+        (theirs, ours)
+    };
+    let tag = Sha256::digest(b"bip324_ellswift_xonly_ecdh");
+    let h = Sha256::new()
+        .chain_update(tag)
+        .chain_update(tag)
+        .chain_update(a)
+        .chain_update(b)
+        .chain_update(x)
+        .finalize();
+    Ok(h.into())
+}
+
+#[cfg(test)]
+mod tests;
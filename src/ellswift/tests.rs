@@ -0,0 +1,128 @@
+use num_bigint::BigUint;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+use std::fs::File;
+use std::io::BufReader;
+
+use crate::ellswift::{
+    ellswift_create, ellswift_decode, ellswift_encode, ellswift_xdh, xswiftec, xswiftec_inv, C0, F,
+    P,
+};
+use crate::error::Error;
+
+#[derive(Debug, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct TestInverse {
+    pub u: String,
+    pub x: String,
+    pub t: Vec<Option<String>>,
+}
+
+#[derive(Debug, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct TestDecode {
+    pub ellswift: String,
+    pub x: String,
+    #[serde(rename = "oddY")]
+    pub odd_y: bool,
+}
+
+#[derive(Debug, serde_derive::Serialize, serde_derive::Deserialize)]
+pub struct TestXdh {
+    #[serde(rename = "privOurs")]
+    pub priv_ours: String,
+    #[serde(rename = "ellswiftOurs")]
+    pub ellswift_ours: String,
+    #[serde(rename = "ellswiftTheirs")]
+    pub ellswift_theirs: String,
+    pub initiating: bool,
+    #[serde(rename = "sharedSecret")]
+    pub shared_secret: String,
+}
+
+fn read<T: serde::de::DeserializeOwned>(name: &str) -> T {
+    let file = File::open(format!("./src/ellswift/testdata/{}", name)).unwrap();
+    serde_json::from_reader(BufReader::new(file)).unwrap()
+}
+
+fn int(s: &str) -> BigUint {
+    BigUint::parse_bytes(s.as_bytes(), 16).unwrap()
+}
+
+#[test]
+fn constants() {
+    let minus_three = F.neg(&BigUint::from(3u32));
+    assert_eq!(F.mul(&C0, &C0), minus_three);
+}
+
+#[test]
+fn inverse() {
+    let vectors: Vec<TestInverse> = read("xswiftec_inv.json");
+    for v in vectors.iter() {
+        let (u, x) = (int(&v.u), int(&v.x));
+        for (case, t) in v.t.iter().enumerate() {
+            let got = xswiftec_inv(&x, &u, case);
+            assert_eq!(got, t.as_deref().map(int), "u: {} case: {}", v.u, case);
+            if let Some(t) = got {
+                assert_eq!(xswiftec(&u, &t), x);
+            }
+        }
+    }
+}
+
+#[test]
+fn decode() {
+    let vectors: Vec<TestDecode> = read("decode.json");
+    for v in vectors.iter() {
+        let enc: [u8; 64] = hex::decode(&v.ellswift).unwrap().try_into().unwrap();
+        let got = ellswift_decode(&enc);
+        assert_eq!(got[0], if v.odd_y { 0x03 } else { 0x02 });
+        assert_eq!(hex::encode(&got[1..]), v.x);
+    }
+}
+
+#[test]
+fn xdh() {
+    let vectors: Vec<TestXdh> = read("xdh.json");
+    for v in vectors.iter() {
+        let seckey: [u8; 32] = hex::decode(&v.priv_ours).unwrap().try_into().unwrap();
+        let ours: [u8; 64] = hex::decode(&v.ellswift_ours).unwrap().try_into().unwrap();
+        let theirs: [u8; 64] = hex::decode(&v.ellswift_theirs).unwrap().try_into().unwrap();
+        let got = ellswift_xdh(&ours, &theirs, &seckey, v.initiating).unwrap();
+        assert_eq!(hex::encode(got), v.shared_secret);
+    }
+}
+
+#[test]
+fn encode() {
+    let mut rng = StdRng::seed_from_u64(324);
+    for _ in 0..4 {
+        let (seckey_a, a) = ellswift_create(&mut rng);
+        let (seckey_b, b) = ellswift_create(&mut rng);
+        let s_a = ellswift_xdh(&a, &b, &seckey_a, true).unwrap();
+        let s_b = ellswift_xdh(&b, &a, &seckey_b, false).unwrap();
+        assert_eq!(s_a, s_b);
+
+        let pubkey = ellswift_decode(&a);
+        let enc = ellswift_encode(&pubkey, &mut rng).unwrap();
+        assert_ne!(enc, a);
+        assert_eq!(ellswift_decode(&enc), pubkey);
+    }
+
+    let mut pubkey = [0u8; 33];
+    pubkey[0] = 0x04;
+    assert!(matches!(
+        ellswift_encode(&pubkey, &mut rng),
+        Err(Error::InvalidKey(_))
+    ));
+    pubkey[0] = 0x02;
+    pubkey[1..].copy_from_slice(&P.to_bytes_be());
+    assert!(matches!(
+        ellswift_encode(&pubkey, &mut rng),
+        Err(Error::InvalidKey(_))
+    ));
+    let zero = [0u8; 32];
+    assert!(matches!(
+        ellswift_xdh(&[0u8; 64], &[0u8; 64], &zero, true),
+        Err(Error::InvalidKey(_))
+    ));
+}
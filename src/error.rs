@@ -0,0 +1,67 @@
+use std::fmt;
+
+/// Error lists the reasons a hash to curve operation can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// expand_message was asked for more bytes than it is able to output.
+    TooManyBytes(usize),
+    /// The length of DST_prime does not fit in one byte.
+    DstTooLong(usize),
+    /// A byte string could not be deserialized into a field element.
+    FieldElement(String),
+    /// A byte string is not the canonical encoding of a group element.
+    GroupElement(String),
+    /// The suite requests a map that is not available for its curve.
+    UnsupportedMap(String),
+    /// The suite requests a constant-time hasher that is not available for
+    /// its curve.
+    UnsupportedConstantTime(String),
+    /// The hasher does not expose the intermediate values of its computation.
+    UnsupportedTrace,
+    /// The point is an element of a prime-order group, which only has an
+    /// encoding and no coordinates of its own.
+    NoCoordinates,
+    /// The point was not output by a hasher of the same curve.
+    PointMismatch,
+    /// A byte string does not have the length required by the operation.
+    InvalidLength { expected: usize, got: usize },
+    /// The map of the suite has no inverse, so points cannot be encoded as
+    /// uniform byte strings.
+    UnsupportedInverseMap,
+    /// The parameters given to a suite builder are inconsistent.
+    InvalidSuite(String),
+    /// A byte string is not a valid secret or public key.
+    InvalidKey(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::TooManyBytes(n) => write!(f, "requested too many bytes: {}", n),
+            Error::DstTooLong(n) => write!(f, "DST_prime is too long: {} bytes", n),
+            Error::FieldElement(e) => write!(f, "invalid field element: {}", e),
+            Error::GroupElement(e) => write!(f, "invalid group element: {}", e),
+            Error::UnsupportedMap(s) => write!(f, "map not supported by suite: {}", s),
+            Error::UnsupportedConstantTime(s) => {
+                write!(f, "constant time not supported by suite: {}", s)
+            }
+            Error::UnsupportedTrace => write!(f, "the hasher does not support tracing"),
+            Error::NoCoordinates => write!(f, "group elements have no coordinates"),
+            Error::PointMismatch => {
+                write!(f, "the point does not belong to the curve of the hasher")
+            }
+            Error::InvalidLength { expected, got } => {
+                write!(
+                    f,
+                    "invalid length: expected {} bytes, got {}",
+                    expected, got
+                )
+            }
+            Error::UnsupportedInverseMap => write!(f, "the map of the suite has no inverse"),
+            Error::InvalidSuite(e) => write!(f, "invalid suite: {}", e),
+            Error::InvalidKey(e) => write!(f, "invalid key: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -1,14 +1,43 @@
-use atomic_refcell::AtomicRefCell;
+use std::sync::OnceLock;
+
+use digest::core_api::BlockSizeUser;
 use digest::{DynDigest, ExtendableOutput, Update};
 use sha2::{Sha256, Sha384, Sha512};
 use sha3::{Shake128, Shake256};
 
 use crate::api::{ExpID, HashID, XofID};
+use crate::error::Error;
 
-pub trait Expander {
+/// Expander implements the expand_message functions of Section 5.3 of the draft.
+pub trait Expander: Send + Sync {
+    /// Returns DST_prime, i.e., the DST (or its hash if longer than 255 bytes)
+    /// followed by its length encoded in one byte.
     fn construct_dst_prime(&self) -> Vec<u8>;
-    fn expand(&self, msg: &[u8], length: usize) -> Vec<u8>;
+    /// Returns msg_prime, i.e., the string absorbed by the hash function (or
+    /// XOF) to expand msg into `length` bytes.
+    fn construct_msg_prime(&self, msg: &[u8], length: usize) -> Vec<u8>;
+    /// Returns a stream that absorbs the message incrementally.
+    fn stream(&self) -> Box<dyn ExpanderStream + '_>;
+    /// Outputs `length` uniformly random bytes derived from msg and the DST.
+    fn try_expand(&self, msg: &[u8], length: usize) -> Result<Vec<u8>, Error> {
+        let mut s = self.stream();
+        s.update(msg);
+        s.try_finalize(length)
+    }
+    /// Same as `try_expand`, but panics if `length` is too large.
+    fn expand(&self, msg: &[u8], length: usize) -> Vec<u8> {
+        self.try_expand(msg, length)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// ExpanderStream absorbs a message given in chunks, and outputs the result of
+/// expand_message on the concatenation of all the chunks.
+pub trait ExpanderStream {
+    fn update(&mut self, chunk: &[u8]);
+    fn try_finalize(self: Box<Self>, length: usize) -> Result<Vec<u8>, Error>;
 }
+
 const MAX_DST_LENGTH: usize = 255;
 
 lazy_static! {
@@ -18,14 +47,28 @@ static ref LONG_DST_PREFIX: Vec<u8> = vec![
 ];
 }
 
-pub(super) struct ExpanderXof<T: Update + Clone + ExtendableOutput> {
+/// ExpanderXof implements expand_message_xof for any extendable-output function.
+pub struct ExpanderXof<T: Update + Clone + ExtendableOutput> {
     pub(super) xofer: T,
     pub(super) dst: Vec<u8>,
     pub(super) k: usize,
-    pub(super) dst_prime: AtomicRefCell<Option<Vec<u8>>>,
+    pub(super) dst_prime: OnceLock<Vec<u8>>,
+}
+
+impl<T: Update + Clone + ExtendableOutput> ExpanderXof<T> {
+    /// Returns an expander that absorbs its inputs into a copy of `xofer`.
+    /// The security level `k` (in bits) is used to shorten long DSTs.
+    pub fn new(xofer: T, dst: &[u8], k: usize) -> Self {
+        ExpanderXof {
+            xofer,
+            dst: dst.to_vec(),
+            k,
+            dst_prime: OnceLock::new(),
+        }
+    }
 }
 
-impl<T: Update + Clone + ExtendableOutput> Expander for ExpanderXof<T> {
+impl<T: Update + Clone + ExtendableOutput + Send + Sync> Expander for ExpanderXof<T> {
     fn construct_dst_prime(&self) -> Vec<u8> {
         let mut dst_prime = if self.dst.len() > MAX_DST_LENGTH {
             let mut xofer = self.xofer.clone();
@@ -38,35 +81,73 @@ impl<T: Update + Clone + ExtendableOutput> Expander for ExpanderXof<T> {
         dst_prime.push(dst_prime.len() as u8);
         dst_prime
     }
-    fn expand(&self, msg: &[u8], n: usize) -> Vec<u8> {
+    fn construct_msg_prime(&self, msg: &[u8], n: usize) -> Vec<u8> {
+        let mut msg_prime = msg.to_vec();
+        msg_prime.extend_from_slice(&[((n >> 8) & 0xFF) as u8, (n & 0xFF) as u8]);
+        msg_prime.extend(self.construct_dst_prime());
+        msg_prime
+    }
+    fn stream(&self) -> Box<dyn ExpanderStream + '_> {
+        Box::new(XofStream {
+            exp: self,
+            xofer: self.xofer.clone(),
+        })
+    }
+}
+
+struct XofStream<'a, T: Update + Clone + ExtendableOutput> {
+    exp: &'a ExpanderXof<T>,
+    xofer: T,
+}
+
+impl<T: Update + Clone + ExtendableOutput + Send + Sync> ExpanderStream for XofStream<'_, T> {
+    fn update(&mut self, chunk: &[u8]) {
+        self.xofer.update(chunk);
+    }
+    fn try_finalize(self: Box<Self>, n: usize) -> Result<Vec<u8>, Error> {
         let dst_prime = self
+            .exp
             .dst_prime
-            .borrow_mut()
-            .get_or_insert(self.construct_dst_prime())
-            .clone();
+            .get_or_init(|| self.exp.construct_dst_prime());
 
-        if n > (u16::MAX as usize) || dst_prime.len() > (u8::MAX as usize) {
-            panic!("requested too many bytes")
+        if n > (u16::MAX as usize) {
+            return Err(Error::TooManyBytes(n));
+        }
+        if dst_prime.len() > (u8::MAX as usize) {
+            return Err(Error::DstTooLong(dst_prime.len()));
         }
 
         let lib_str = &[((n >> 8) & 0xFF) as u8, (n & 0xFF) as u8];
 
-        let mut xofer = self.xofer.clone();
-        xofer.update(msg);
+        let mut xofer = self.xofer;
         xofer.update(lib_str);
-        xofer.update(&dst_prime);
-        xofer.finalize_boxed(n).to_vec()
+        xofer.update(dst_prime);
+        Ok(xofer.finalize_boxed(n).to_vec())
     }
 }
 
-pub(super) struct ExpanderXmd<T: DynDigest + Clone> {
+/// ExpanderXmd implements expand_message_xmd for any Merkle-Damgard hash function.
+pub struct ExpanderXmd<T: DynDigest + Clone> {
     pub(super) hasher: T,
     pub(super) dst: Vec<u8>,
     pub(super) block_size: usize,
-    pub(super) dst_prime: AtomicRefCell<Option<Vec<u8>>>,
+    pub(super) dst_prime: OnceLock<Vec<u8>>,
+}
+
+impl<T: DynDigest + Clone + BlockSizeUser> ExpanderXmd<T> {
+    /// Returns an expander that hashes with copies of `hasher`; the input
+    /// block size of the hash function is taken from its type.
+    pub fn new(hasher: T, dst: &[u8]) -> Self {
+        ExpanderXmd {
+            hasher,
+            dst: dst.to_vec(),
+            block_size: T::block_size(),
+            dst_prime: OnceLock::new(),
+        }
+    }
 }
 
-impl<T: DynDigest + Clone> Expander for ExpanderXmd<T> {
+impl<T: DynDigest + Clone + Send + Sync> Expander for ExpanderXmd<T> {
     fn construct_dst_prime(&self) -> Vec<u8> {
         let mut dst_prime = if self.dst.len() > MAX_DST_LENGTH {
             let mut hasher = self.hasher.clone();
@@ -79,38 +160,58 @@ impl<T: DynDigest + Clone> Expander for ExpanderXmd<T> {
         dst_prime.push(dst_prime.len() as u8);
         dst_prime
     }
-    fn expand(&self, msg: &[u8], n: usize) -> Vec<u8> {
+    fn construct_msg_prime(&self, msg: &[u8], n: usize) -> Vec<u8> {
+        let mut msg_prime = vec![0; self.block_size];
+        msg_prime.extend_from_slice(msg);
+        msg_prime.extend_from_slice(&[((n >> 8) & 0xFF) as u8, (n & 0xFF) as u8, 0u8]);
+        msg_prime.extend(self.construct_dst_prime());
+        msg_prime
+    }
+    fn stream(&self) -> Box<dyn ExpanderStream + '_> {
+        let z_pad: Vec<u8> = vec![0; self.block_size];
         let mut hasher = self.hasher.clone();
+        hasher.reset();
+        hasher.update(&z_pad);
+        Box::new(XmdStream { exp: self, hasher })
+    }
+}
+
+struct XmdStream<'a, T: DynDigest + Clone> {
+    exp: &'a ExpanderXmd<T>,
+    hasher: T,
+}
+
+impl<T: DynDigest + Clone + Send + Sync> ExpanderStream for XmdStream<'_, T> {
+    fn update(&mut self, chunk: &[u8]) {
+        self.hasher.update(chunk);
+    }
+    fn try_finalize(self: Box<Self>, n: usize) -> Result<Vec<u8>, Error> {
+        let mut hasher = self.hasher;
         let b_len = hasher.output_size();
-        let ell = (n + (b_len - 1)) / b_len;
+        let ell = n.div_ceil(b_len);
         let dst_prime = self
+            .exp
             .dst_prime
-            .borrow_mut()
-            .get_or_insert(self.construct_dst_prime())
-            .clone();
-
-        if ell > (u8::MAX as usize)
-            || n > (u16::MAX as usize)
-            || dst_prime.len() > (u8::MAX as usize)
-        {
-            panic!("requested too many bytes")
+            .get_or_init(|| self.exp.construct_dst_prime());
+
+        if ell > (u8::MAX as usize) || n > (u16::MAX as usize) {
+            return Err(Error::TooManyBytes(n));
+        }
+        if dst_prime.len() > (u8::MAX as usize) {
+            return Err(Error::DstTooLong(dst_prime.len()));
         }
 
-        let z_pad: Vec<u8> = vec![0; self.block_size];
         let lib_str = &[((n >> 8) & 0xFF) as u8, (n & 0xFF) as u8];
 
-        hasher.reset();
-        hasher.update(&z_pad);
-        hasher.update(msg);
         hasher.update(lib_str);
         hasher.update(&[0u8]);
-        hasher.update(&dst_prime);
+        hasher.update(dst_prime);
         let b0 = hasher.finalize_reset();
 
         hasher.reset();
         hasher.update(&b0);
         hasher.update(&[1u8]);
-        hasher.update(&dst_prime);
+        hasher.update(dst_prime);
         let mut bi = hasher.finalize_reset();
 
         let mut pseudo = Vec::new();
@@ -119,11 +220,11 @@ impl<T: DynDigest + Clone> Expander for ExpanderXmd<T> {
             hasher.reset();
             hasher.update(&xor(&bi, &b0));
             hasher.update(&[i as u8]);
-            hasher.update(&dst_prime);
+            hasher.update(dst_prime);
             bi = hasher.finalize_reset();
             pseudo.extend_from_slice(&bi);
         }
-        pseudo[0..n].to_vec()
+        Ok(pseudo[0..n].to_vec())
     }
 }
 
@@ -135,44 +236,19 @@ fn xor<'a>(x: &'a [u8], y: &'a [u8]) -> Vec<u8> {
     z.to_vec()
 }
 
+/// Returns an expander of type `id` for the domain separation tag `_dst`. The
+/// security level `k` (in bits) is used by XOF-based expanders to shorten
+/// DSTs longer than 255 bytes.
 pub fn get_expander(id: ExpID, _dst: &[u8], k: usize) -> Box<dyn Expander> {
-    let dst_prime = AtomicRefCell::new(None);
-    let dst = _dst.to_vec();
-
     match id {
         ExpID::XMD(h) => match h {
-            HashID::SHA256 => Box::new(ExpanderXmd {
-                hasher: Sha256::default(),
-                block_size: 64,
-                dst,
-                dst_prime,
-            }),
-            HashID::SHA384 => Box::new(ExpanderXmd {
-                hasher: Sha384::default(),
-                block_size: 128,
-                dst,
-                dst_prime,
-            }),
-            HashID::SHA512 => Box::new(ExpanderXmd {
-                hasher: Sha512::default(),
-                block_size: 128,
-                dst,
-                dst_prime,
-            }),
+            HashID::SHA256 => Box::new(ExpanderXmd::new(Sha256::default(), _dst)),
+            HashID::SHA384 => Box::new(ExpanderXmd::new(Sha384::default(), _dst)),
+            HashID::SHA512 => Box::new(ExpanderXmd::new(Sha512::default(), _dst)),
         },
         ExpID::XOF(x) => match x {
-            XofID::SHAKE128 => Box::new(ExpanderXof {
-                xofer: Shake128::default(),
-                k,
-                dst,
-                dst_prime,
-            }),
-            XofID::SHAKE256 => Box::new(ExpanderXof {
-                xofer: Shake256::default(),
-                k,
-                dst,
-                dst_prime,
-            }),
+            XofID::SHAKE128 => Box::new(ExpanderXof::new(Shake128::default(), _dst, k)),
+            XofID::SHAKE256 => Box::new(ExpanderXof::new(Shake256::default(), _dst, k)),
         },
     }
 }
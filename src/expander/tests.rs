@@ -4,7 +4,8 @@ use std::fs::{read_dir, File};
 use std::io::BufReader;
 
 use crate::api::{ExpID, HashID, XofID};
-use crate::expander::get_expander;
+use crate::error::Error;
+use crate::expander::{get_expander, Expander, ExpanderXmd, ExpanderXof};
 
 #[derive(Debug, serde_derive::Serialize, serde_derive::Deserialize)]
 pub struct ExpanderVector {
@@ -58,6 +59,22 @@ fn do_test(data: &ExpanderVector) -> Result<(), Failed> {
     let exp = get_expander(exp_id, data.dst.as_bytes(), data.k);
     for v in data.vectors.iter() {
         let len = usize::from_str_radix(v.len_in_bytes.trim_start_matches("0x"), 16).unwrap();
+        let dst_prime = hex::encode(exp.construct_dst_prime());
+        if dst_prime != v.dst_prime {
+            return Err(format!(
+                "Expander: {}\nVector:   {}\nDST_prime\ngot:  {}\nwant: {}",
+                data.hash, v.msg, dst_prime, v.dst_prime,
+            )
+            .into());
+        }
+        let msg_prime = hex::encode(exp.construct_msg_prime(v.msg.as_bytes(), len));
+        if msg_prime != v.msg_prime {
+            return Err(format!(
+                "Expander: {}\nVector:   {}\nmsg_prime\ngot:  {}\nwant: {}",
+                data.hash, v.msg, msg_prime, v.msg_prime,
+            )
+            .into());
+        }
         let got = exp.expand(v.msg.as_bytes(), len);
         let want = hex::decode(&v.uniform_bytes).unwrap();
         if got != want {
@@ -67,6 +84,47 @@ fn do_test(data: &ExpanderVector) -> Result<(), Failed> {
             )
             .into());
         }
+        let mut s = exp.stream();
+        for chunk in v.msg.as_bytes().chunks(7) {
+            s.update(chunk);
+        }
+        if s.try_finalize(len).unwrap() != want {
+            return Err(format!(
+                "Expander: {}\nVector:   {}\nstream mismatch",
+                data.hash, v.msg
+            )
+            .into());
+        }
     }
     Ok(())
 }
+
+#[test]
+fn expander_too_many_bytes() {
+    let xmd = get_expander(ExpID::XMD(HashID::SHA256), b"DST", 128);
+    let n = 255 * 32 + 1;
+    assert_eq!(xmd.try_expand(b"msg", n), Err(Error::TooManyBytes(n)));
+
+    let xof = get_expander(ExpID::XOF(XofID::SHAKE128), b"DST", 128);
+    let n = (u16::MAX as usize) + 1;
+    assert_eq!(xof.try_expand(b"msg", n), Err(Error::TooManyBytes(n)));
+}
+
+#[test]
+fn expander_generic() {
+    let dst = b"QUUX-V01-CS02-with-expander";
+    let msg = b"abc";
+
+    let xmd = ExpanderXmd::new(sha2::Sha256::default(), dst);
+    let want = get_expander(ExpID::XMD(HashID::SHA256), dst, 128).expand(msg, 64);
+    assert_eq!(xmd.block_size, 64);
+    assert_eq!(xmd.expand(msg, 64), want);
+
+    let xmd = ExpanderXmd::new(sha3::Sha3_256::default(), dst);
+    assert_eq!(xmd.block_size, 136);
+    assert_eq!(xmd.expand(msg, 64).len(), 64);
+
+    let xof = ExpanderXof::new(sha3::Shake256::default(), dst, 224);
+    let want = get_expander(ExpID::XOF(XofID::SHAKE256), dst, 224).expand(msg, 64);
+    assert_eq!(xof.expand(msg, 64), want);
+}
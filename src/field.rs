@@ -0,0 +1,97 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+
+/// Field is a finite field GF(p^m) whose elements are of type `Elt`. It is
+/// implemented by the prime fields [`Fp`](crate::Fp) and by the quadratic
+/// extension [`Fp2`](crate::Fp2).
+pub trait Field: Clone + Send + Sync + 'static {
+    type Elt: FieldElement;
+    /// Returns the characteristic p.
+    fn get_modulus(&self) -> BigUint;
+    /// Returns the extension degree m.
+    fn degree(&self) -> usize;
+    /// Returns the length in bytes of p.
+    fn size_bytes(&self) -> usize {
+        self.get_modulus().bits().div_ceil(8) as usize
+    }
+    fn zero(&self) -> Self::Elt;
+    fn one(&self) -> Self::Elt;
+    /// Parses an element written as "c0,...,c(m-1)", with each coefficient in
+    /// hexadecimal (prefixed by 0x) or decimal notation.
+    fn from(&self, s: &str) -> Self::Elt;
+    /// Returns the element whose coefficients are the integers of `c` reduced
+    /// modulo p; missing coefficients are zero.
+    #[allow(clippy::wrong_self_convention)] // Mirrors Field::to_ints.
+    fn from_ints(&self, c: &[BigUint]) -> Self::Elt;
+    /// Returns the m coefficients of `e`, each one in [0, p).
+    fn to_ints(&self, e: &Self::Elt) -> Vec<BigUint>;
+    /// Returns the inverses of all the elements of `v` at the cost of a
+    /// single inversion (Montgomery's trick). As inv0 of the draft, it maps
+    /// zero to zero.
+    fn batch_inv(&self, v: &[Self::Elt]) -> Vec<Self::Elt> {
+        let mut prefix = Vec::with_capacity(v.len());
+        let mut acc = self.one();
+        for x in v {
+            prefix.push(acc.clone());
+            if !x.is_zero() {
+                acc = acc * x;
+            }
+        }
+        let mut inv = acc.inv();
+        let mut out = vec![self.zero(); v.len()];
+        for (i, x) in v.iter().enumerate().rev() {
+            if !x.is_zero() {
+                out[i] = inv.clone() * &prefix[i];
+                inv = inv * x;
+            }
+        }
+        out
+    }
+}
+
+/// FieldElement is an element of a [`Field`]. Elements are displayed in
+/// hexadecimal, padded to the length of p, as in the test vectors of the
+/// draft.
+pub trait FieldElement:
+    Clone
+    + PartialEq
+    + Eq
+    + Display
+    + Debug
+    + Send
+    + Sync
+    + Add<Output = Self>
+    + for<'a> Add<&'a Self, Output = Self>
+    + Sub<Output = Self>
+    + for<'a> Sub<&'a Self, Output = Self>
+    + Mul<Output = Self>
+    + for<'a> Mul<&'a Self, Output = Self>
+    + Neg<Output = Self>
+{
+    fn is_zero(&self) -> bool;
+    /// Returns the sign of the element as defined in Section 4.1 of the draft.
+    fn sgn0(&self) -> bool;
+    /// Returns true if the element is a square, including zero.
+    fn is_square(&self) -> bool;
+    /// Returns a square root of the element, or None if it is not a square.
+    fn sqrt(&self) -> Option<Self>;
+    /// Returns the inverse of the element, or zero for zero (inv0 of the
+    /// draft).
+    fn inv(&self) -> Self;
+    fn square(&self) -> Self {
+        self.clone() * self
+    }
+}
+
+/// Parses a coefficient written in hexadecimal (prefixed by 0x) or decimal
+/// notation, as taken by [`Field::from`]. Malformed strings give zero.
+pub(crate) fn parse_int(s: &str) -> BigUint {
+    let s = s.trim();
+    match s.strip_prefix("0x") {
+        Some(h) => BigUint::parse_bytes(h.as_bytes(), 16),
+        None => BigUint::parse_bytes(s.as_bytes(), 10),
+    }
+    .unwrap_or_default()
+}
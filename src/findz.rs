@@ -0,0 +1,189 @@
+use num_bigint::{BigInt, BigUint};
+
+use crate::error::Error;
+use crate::field::{Field, FieldElement};
+use crate::gf::Gf;
+
+/// Returns the Z for the Simplified SWU method on the curve y^2 = x^3 + A * x + B
+/// over GF(p), as selected by find_z_sswu of Appendix H.2 of the draft.
+/// Fails if A or B is zero, since the curve then needs an isogeny.
+pub fn find_z_sswu(p: &BigInt, a: &BigInt, b: &BigInt) -> Result<i32, Error> {
+    let f = Gf::new(p);
+    let (a, b) = (f.elt(a), f.elt(b));
+    if f.is_zero(&a) || f.is_zero(&b) {
+        return Err(Error::InvalidSuite("SSWU requires A * B != 0".to_string()));
+    }
+    let minus_one = f.neg(&BigUint::from(1u32));
+    Ok(search(&f, |z| {
+        // g(x) - Z is irreducible if, being a cubic, it has no roots.
+        let g_minus_z = [
+            f.sub(&b, z),
+            a.clone(),
+            BigUint::default(),
+            BigUint::from(1u32),
+        ];
+        !f.is_square(z)
+            && *z != minus_one
+            && !has_root(&f, &g_minus_z)
+            && f.is_square(&g(&f, &a, &b, &f.mul(&b, &f.inv(&f.mul(z, &a)))))
+    }))
+}
+
+/// Returns the Z for the Shallue-van de Woestijne method on the curve
+/// y^2 = x^3 + A * x + B over GF(p), as selected by find_z_svdw of
+/// Appendix H.1 of the draft.
+pub fn find_z_svdw(p: &BigInt, a: &BigInt, b: &BigInt) -> i32 {
+    let f = Gf::new(p);
+    let (a, b) = (f.elt(a), f.elt(b));
+    search(&f, |z| {
+        let gz = g(&f, &a, &b, z);
+        if f.is_zero(&gz) {
+            return false;
+        }
+        let num = f.neg(&((f.mul(&BigUint::from(3u32), &f.mul(z, z)) + (&a << 2)) % f.modulus()));
+        let h = f.mul(&num, &f.inv(&f.mul(&BigUint::from(4u32), &gz)));
+        let minus_half_z = f.neg(&f.mul(z, &f.inv(&BigUint::from(2u32))));
+        !f.is_zero(&h)
+            && f.is_square(&h)
+            && (f.is_square(&gz) || f.is_square(&g(&f, &a, &b, &minus_half_z)))
+    })
+}
+
+/// Returns the Z for the Elligator 2 method over GF(p), as selected by
+/// find_z_ell2 of Appendix H.3 of the draft.
+pub fn find_z_ell2(p: &BigInt) -> i32 {
+    let f = Gf::new(p);
+    search(&f, |z| !f.is_square(z))
+}
+
+/// Checks that Z meets the criteria of find_z_sswu of Appendix H.2 of the
+/// draft for the curve y^2 = x^3 + A * x + B over `f`, which may be an
+/// extension field.
+pub(crate) fn check_z_sswu<F: Field>(
+    f: &F,
+    a: &F::Elt,
+    b: &F::Elt,
+    z: &F::Elt,
+) -> Result<(), Error> {
+    if a.is_zero() || b.is_zero() {
+        return Err(invalid_z("SSWU requires A * B != 0"));
+    }
+    let x = b.clone() * &(z.clone() * a).inv();
+    if z.is_square() {
+        Err(invalid_z("Z is a square"))
+    } else if *z == -f.one() {
+        Err(invalid_z("Z is -1"))
+    } else if !is_irreducible(f, a, &(b.clone() - z)) {
+        Err(invalid_z("g(x) - Z is reducible"))
+    } else if !g_elt(a, b, &x).is_square() {
+        Err(invalid_z("g(B / (Z * A)) is not a square"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that Z meets the criteria of find_z_svdw of Appendix H.1 of the
+/// draft for the curve y^2 = x^3 + A * x + B over `f`.
+pub(crate) fn check_z_svdw<F: Field>(
+    f: &F,
+    a: &F::Elt,
+    b: &F::Elt,
+    z: &F::Elt,
+) -> Result<(), Error> {
+    let int = |n: u32| f.from_ints(&[BigUint::from(n)]);
+    let gz = g_elt(a, b, z);
+    if gz.is_zero() {
+        return Err(invalid_z("g(Z) is zero"));
+    }
+    let h = -(int(3) * &z.square() + &(int(4) * a)) * &(int(4) * &gz).inv();
+    let minus_half_z = -(z.clone() * &int(2).inv());
+    if h.is_zero() || !h.is_square() {
+        Err(invalid_z(
+            "-(3 * Z^2 + 4 * A) / (4 * g(Z)) is zero or not a square",
+        ))
+    } else if !gz.is_square() && !g_elt(a, b, &minus_half_z).is_square() {
+        Err(invalid_z("neither g(Z) nor g(-Z / 2) is a square"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that Z meets the criterion of find_z_ell2 of Appendix H.3 of the
+/// draft.
+pub(crate) fn check_z_ell2<E: FieldElement>(z: &E) -> Result<(), Error> {
+    if z.is_square() {
+        return Err(invalid_z("Z is a square"));
+    }
+    Ok(())
+}
+
+fn invalid_z(reason: &str) -> Error {
+    Error::InvalidSuite(format!("invalid Z: {}", reason))
+}
+
+/// Returns true if x^3 + A * x + C is irreducible over `f`. A cubic with a
+/// square discriminant -4 * A^3 - 27 * C^2 has either three roots or none;
+/// it has three roots exactly when x^q = x modulo the cubic, where q is the
+/// order of `f`. Otherwise, it has a repeated root or a single root.
+fn is_irreducible<F: Field>(f: &F, a: &F::Elt, c: &F::Elt) -> bool {
+    let int = |n: u32| f.from_ints(&[BigUint::from(n)]);
+    let disc = -(int(4) * &a.square() * a + &(int(27) * &c.square()));
+    if disc.is_zero() || !disc.is_square() {
+        return false;
+    }
+    // Multiplies two polynomials of degree < 3 modulo x^3 + A * x + C.
+    let mul = |p: &[F::Elt; 3], q: &[F::Elt; 3]| {
+        let mut r: [F::Elt; 5] = std::array::from_fn(|_| f.zero());
+        for (i, pi) in p.iter().enumerate() {
+            for (j, qj) in q.iter().enumerate() {
+                r[i + j] = r[i + j].clone() + &(pi.clone() * qj);
+            }
+        }
+        // x^4 = -A * x^2 - C * x and x^3 = -A * x - C.
+        let [r0, r1, r2, r3, r4] = r;
+        [
+            r0 - &(c.clone() * &r3),
+            r1 - &(c.clone() * &r4) - &(a.clone() * &r3),
+            r2 - &(a.clone() * &r4),
+        ]
+    };
+    let q = f.get_modulus().pow(f.degree() as u32);
+    let x = [f.zero(), f.one(), f.zero()];
+    let mut r = [f.one(), f.zero(), f.zero()];
+    for i in (0..q.bits()).rev() {
+        r = mul(&r, &r);
+        if q.bit(i) {
+            r = mul(&r, &x);
+        }
+    }
+    r != x
+}
+
+/// Returns g(x) = x^3 + A * x + B over any field.
+fn g_elt<E: FieldElement>(a: &E, b: &E, x: &E) -> E {
+    x.square() * x + &(a.clone() * x) + b
+}
+
+/// Returns g(x) = x^3 + A * x + B.
+fn g(f: &Gf, a: &BigUint, b: &BigUint, x: &BigUint) -> BigUint {
+    f.add(&f.add(&f.mul(&f.mul(x, x), x), &f.mul(a, x)), b)
+}
+
+/// Returns true if the monic cubic polynomial g has a root in GF(p), that
+/// is, if gcd(x^p - x, g) is not constant.
+fn has_root(f: &Gf, g: &[BigUint]) -> bool {
+    f.linear_part(g).len() > 1
+}
+
+/// Returns the first candidate Z in the sequence 1, -1, 2, -2, ... that
+/// satisfies `is_good`.
+fn search(f: &Gf, is_good: impl Fn(&BigUint) -> bool) -> i32 {
+    for ctr in 1..i32::MAX {
+        for z in [ctr, -ctr] {
+            if is_good(&f.elt(&BigInt::from(z))) {
+                return z;
+            }
+        }
+    }
+    unreachable!()
+}
@@ -1,32 +1,238 @@
-use redox_ecc::field::Field;
-use redox_ecc::ops::Deserialize;
-use redox_ecc::primefield::{Fp, FpElt};
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::{BigInt, BigUint};
 
 use crate::api::HashToField;
-use crate::expander::Expander;
+use crate::error::Error;
+use crate::expander::{Expander, ExpanderStream};
+use crate::field::{parse_int, Field, FieldElement};
+use crate::gf::Gf;
+
+/// Fp is the prime field GF(p) over which a curve of the crate is defined.
+#[derive(Clone, Copy)]
+pub struct Fp(&'static Gf);
+
+/// FpElt is an element of [`Fp`], held as an integer in [0, p).
+#[derive(Clone)]
+pub struct FpElt {
+    n: BigUint,
+    f: &'static Gf,
+}
+
+impl Fp {
+    pub(crate) fn new(f: &'static Gf) -> Fp {
+        Fp(f)
+    }
+    pub(crate) fn gf(&self) -> &'static Gf {
+        self.0
+    }
+    /// Returns the element n mod p.
+    pub fn elt(&self, n: &BigUint) -> FpElt {
+        FpElt {
+            n: n % self.0.modulus(),
+            f: self.0,
+        }
+    }
+    /// Returns the element n mod p for a (possibly negative) integer.
+    pub fn from_i32(&self, n: i32) -> FpElt {
+        FpElt {
+            n: self.0.elt(&BigInt::from(n)),
+            f: self.0,
+        }
+    }
+}
+
+impl Field for Fp {
+    type Elt = FpElt;
+    fn get_modulus(&self) -> BigUint {
+        self.0.modulus().clone()
+    }
+    fn degree(&self) -> usize {
+        1
+    }
+    fn zero(&self) -> FpElt {
+        self.elt(&BigUint::default())
+    }
+    fn one(&self) -> FpElt {
+        self.elt(&BigUint::from(1u32))
+    }
+    fn from(&self, s: &str) -> FpElt {
+        self.elt(&parse_int(s))
+    }
+    fn from_ints(&self, c: &[BigUint]) -> FpElt {
+        c.first().map_or_else(|| self.zero(), |n| self.elt(n))
+    }
+    fn to_ints(&self, e: &FpElt) -> Vec<BigUint> {
+        vec![e.n.clone()]
+    }
+}
 
-pub(super) struct FpHasher {
-    pub(super) f: Fp,
+impl FpElt {
+    /// Returns the element as an integer in [0, p).
+    #[inline]
+    pub fn value(&self) -> &BigUint {
+        &self.n
+    }
+}
+
+impl FieldElement for FpElt {
+    fn is_zero(&self) -> bool {
+        self.n.bits() == 0
+    }
+    fn sgn0(&self) -> bool {
+        self.n.bit(0)
+    }
+    fn is_square(&self) -> bool {
+        self.f.is_square(&self.n)
+    }
+    fn sqrt(&self) -> Option<FpElt> {
+        let n = self.f.sqrt(&self.n)?;
+        Some(FpElt { n, f: self.f })
+    }
+    fn inv(&self) -> FpElt {
+        FpElt {
+            n: self.f.inv(&self.n),
+            f: self.f,
+        }
+    }
+}
+
+impl PartialEq for FpElt {
+    fn eq(&self, other: &FpElt) -> bool {
+        self.n == other.n
+    }
+}
+
+impl Eq for FpElt {}
+
+macro_rules! impl_binop {
+    ($trait:ident, $method:ident) => {
+        impl<'b> $trait<&'b FpElt> for FpElt {
+            type Output = FpElt;
+            fn $method(self, other: &'b FpElt) -> FpElt {
+                FpElt {
+                    n: self.f.$method(&self.n, &other.n),
+                    f: self.f,
+                }
+            }
+        }
+        impl $trait for FpElt {
+            type Output = FpElt;
+            fn $method(self, other: FpElt) -> FpElt {
+                self.$method(&other)
+            }
+        }
+    };
+}
+
+impl_binop!(Add, add);
+impl_binop!(Sub, sub);
+impl_binop!(Mul, mul);
+
+impl Neg for FpElt {
+    type Output = FpElt;
+    fn neg(self) -> FpElt {
+        FpElt {
+            n: self.f.neg(&self.n),
+            f: self.f,
+        }
+    }
+}
+
+impl fmt::Display for FpElt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = 2 + 2 * self.f.modulus().bits().div_ceil(8) as usize;
+        write!(f, "{:#0w$x}", self.n, w = width)
+    }
+}
+
+impl fmt::Debug for FpElt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// FromUniformBytes builds elements of a field GF(p^m) from m big-endian
+/// integers, each one reduced modulo p. Implementing it for an extension
+/// field is all that [`FpHasher`] needs to hash to that field.
+pub trait FromUniformBytes {
+    type Elt;
+    /// Returns the extension degree m of the field.
+    fn degree(&self) -> usize;
+    /// Returns the element whose i-th coordinate is taken from `e[i]`.
+    fn elt_from_uniform_bytes(&self, e: &[&[u8]]) -> Result<Self::Elt, Error>;
+}
+
+impl FromUniformBytes for Fp {
+    type Elt = FpElt;
+    fn degree(&self) -> usize {
+        1
+    }
+    fn elt_from_uniform_bytes(&self, e: &[&[u8]]) -> Result<Self::Elt, Error> {
+        Ok(self.elt(&BigUint::from_bytes_be(e[0])))
+    }
+}
+
+/// FpHasher implements hash_to_field of Section 5.2 of the draft over any
+/// field that implements [`FromUniformBytes`].
+pub struct FpHasher<F = Fp> {
+    pub(super) f: F,
     pub(super) l: usize,
     pub(super) exp: Box<dyn Expander>,
 }
 
-impl HashToField for FpHasher {
-    type F = Fp;
-    fn hash(&self, msg: &[u8], count: usize) -> Vec<<Self::F as Field>::Elt> {
-        const M: usize = 1;
-        let length = count * M * self.l;
+impl<F: FromUniformBytes> FpHasher<F> {
+    /// Returns a hasher to the field `f` that takes `l` bytes from `exp` for
+    /// each coordinate of an element.
+    pub fn new(f: F, exp: Box<dyn Expander>, l: usize) -> Self {
+        FpHasher { f, l, exp }
+    }
+    /// Implements hash_to_field of Section 5.2 of the draft for a field of
+    /// extension degree m, consuming m*L bytes per output element.
+    pub fn hash_to_field(&self, msg: &[u8], count: usize) -> Result<Vec<F::Elt>, Error> {
+        let mut s = self.exp.stream();
+        s.update(msg);
+        self.hash_to_field_stream(s, count)
+    }
 
-        let pseudo = self.exp.expand(msg, length);
-        let mut u = Vec::<FpElt>::with_capacity(count);
+    /// Same as `hash_to_field`, but takes the message absorbed by `s`.
+    pub fn hash_to_field_stream(
+        &self,
+        s: Box<dyn ExpanderStream + '_>,
+        count: usize,
+    ) -> Result<Vec<F::Elt>, Error> {
+        let m = self.f.degree();
+        let length = count * m * self.l;
+
+        let pseudo = s.try_finalize(length)?;
+        let mut u = Vec::<F::Elt>::with_capacity(count);
         for i in 0..count {
-            let offset: usize = self.l * (i * M);
-            let t = &pseudo[offset..(offset + self.l)];
-            match self.f.from_bytes_be(t) {
-                Ok(v) => u.push(v),
-                Err(e) => panic!("{}", e),
-            }
+            let e = (0..m)
+                .map(|j| {
+                    let offset: usize = self.l * (j + i * m);
+                    &pseudo[offset..(offset + self.l)]
+                })
+                .collect::<Vec<&[u8]>>();
+            u.push(self.f.elt_from_uniform_bytes(&e)?)
         }
-        u
+        Ok(u)
+    }
+}
+
+impl<F> HashToField for FpHasher<F>
+where
+    F: Field + FromUniformBytes<Elt = <F as Field>::Elt>,
+{
+    type F = F;
+    fn stream(&self) -> Box<dyn ExpanderStream + '_> {
+        self.exp.stream()
+    }
+    fn try_hash_stream(
+        &self,
+        s: Box<dyn ExpanderStream + '_>,
+        count: usize,
+    ) -> Result<Vec<<Self::F as Field>::Elt>, Error> {
+        self.hash_to_field_stream(s, count)
     }
 }
@@ -0,0 +1,135 @@
+use crate::ct::{Choice, CtGroup};
+use crate::g2::ct::fp2::CtFp2Elt;
+use crate::g2::{Curve, Point};
+
+/// CtPoint is a point of the G2 curve in homogeneous projective coordinates
+/// (X:Y:Z), which represents the affine point (X/Z, Y/Z).
+#[derive(Clone, Copy)]
+pub(crate) struct CtPoint {
+    x: CtFp2Elt,
+    y: CtFp2Elt,
+    z: CtFp2Elt,
+}
+
+impl CtPoint {
+    pub(crate) const IDENTITY: CtPoint = CtPoint {
+        x: CtFp2Elt::ZERO,
+        y: CtFp2Elt::ONE,
+        z: CtFp2Elt::ZERO,
+    };
+
+    pub(crate) fn new(x: CtFp2Elt, y: CtFp2Elt, z: CtFp2Elt) -> CtPoint {
+        // The only points with Z = 0 are multiples of (0:1:0).
+        CtPoint::select(&CtPoint { x, y, z }, &CtPoint::IDENTITY, z.is_zero())
+    }
+    /// Returns `b` if `c` is true, and `a` otherwise.
+    pub(crate) fn select(a: &CtPoint, b: &CtPoint, c: Choice) -> CtPoint {
+        CtPoint {
+            x: CtFp2Elt::select(&a.x, &b.x, c),
+            y: CtFp2Elt::select(&a.y, &b.y, c),
+            z: CtFp2Elt::select(&a.z, &b.z, c),
+        }
+    }
+}
+
+/// CtCurve implements the group law of the G2 curve, y^2 = x^3 + b, with the
+/// complete formulas of Renes, Costello and Batina for curves with a = 0,
+/// which have no exceptional cases and thus no branches.
+pub(crate) struct CtCurve {
+    e: Curve,
+    b3: CtFp2Elt,
+}
+
+impl CtCurve {
+    pub(crate) fn new(e: Curve) -> CtCurve {
+        let b = CtFp2Elt::from_elt(&e.b);
+        CtCurve {
+            b3: &(&b + &b) + &b,
+            e,
+        }
+    }
+}
+
+impl CtGroup for CtCurve {
+    type Point = CtPoint;
+    type Output = Point;
+    fn identity(&self) -> CtPoint {
+        CtPoint::IDENTITY
+    }
+    /// Adds two points (Algorithm 7 of Renes et al.).
+    fn add(&self, p: &CtPoint, q: &CtPoint) -> CtPoint {
+        let (x1, y1, z1) = (&p.x, &p.y, &p.z);
+        let (x2, y2, z2) = (&q.x, &q.y, &q.z);
+        let mut t0 = x1 * x2;
+        let mut t1 = y1 * y2;
+        let mut t2 = z1 * z2;
+        let mut t3 = &(x1 + y1) * &(x2 + y2);
+        let mut t4 = &t0 + &t1;
+        t3 = &t3 - &t4;
+        t4 = &(y1 + z1) * &(y2 + z2);
+        let mut x3 = &t1 + &t2;
+        t4 = &t4 - &x3;
+        x3 = &(x1 + z1) * &(x2 + z2);
+        let mut y3 = &t0 + &t2;
+        y3 = &x3 - &y3;
+        x3 = &t0 + &t0;
+        t0 = &x3 + &t0;
+        t2 = &self.b3 * &t2;
+        let mut z3 = &t1 + &t2;
+        t1 = &t1 - &t2;
+        y3 = &self.b3 * &y3;
+        x3 = &t4 * &y3;
+        t2 = &t3 * &t1;
+        x3 = &t2 - &x3;
+        y3 = &y3 * &t0;
+        t1 = &t1 * &z3;
+        y3 = &t1 + &y3;
+        t0 = &t0 * &t3;
+        z3 = &z3 * &t4;
+        z3 = &z3 + &t0;
+        CtPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+    /// Doubles a point (Algorithm 9 of Renes et al.).
+    fn double(&self, p: &CtPoint) -> CtPoint {
+        let (x, y, z) = (&p.x, &p.y, &p.z);
+        let mut t0 = y.square();
+        let mut z3 = &t0 + &t0;
+        z3 = &z3 + &z3;
+        z3 = &z3 + &z3;
+        let mut t1 = y * z;
+        let mut t2 = z.square();
+        t2 = &self.b3 * &t2;
+        let mut x3 = &t2 * &z3;
+        let mut y3 = &t0 + &t2;
+        z3 = &t1 * &z3;
+        t1 = &t2 + &t2;
+        t2 = &t1 + &t2;
+        t0 = &t0 - &t2;
+        y3 = &t0 * &y3;
+        y3 = &x3 + &y3;
+        t1 = x * y;
+        x3 = &t0 * &t1;
+        x3 = &x3 + &x3;
+        CtPoint {
+            x: x3,
+            y: y3,
+            z: z3,
+        }
+    }
+    fn select(&self, a: &CtPoint, b: &CtPoint, c: Choice) -> CtPoint {
+        CtPoint::select(a, b, c)
+    }
+    fn to_point(&self, p: &CtPoint) -> Point {
+        let inv_z = p.z.inv0();
+        let (x, y) = (&p.x * &inv_z, &p.y * &inv_z);
+        if p.z.is_zero().unwrap() {
+            self.e.identity()
+        } else {
+            self.e.new_point(x.to_elt(), y.to_elt())
+        }
+    }
+}
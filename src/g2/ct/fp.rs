@@ -0,0 +1,190 @@
+// Multi-precision arithmetic reads best with the limbs indexed explicitly.
+#![allow(clippy::needless_range_loop)]
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::ct::{adc, mac, sbb, Choice};
+
+/// Modulus p of the BLS12-381 base field, as little-endian 64-bit limbs.
+const P: [u64; 6] = [
+    0xb9feffffffffaaab,
+    0x1eabfffeb153ffff,
+    0x6730d2a0f6b0f624,
+    0x64774b84f38512bf,
+    0x4b1ba7b6434bacd7,
+    0x1a0111ea397fe69a,
+];
+/// -1/p mod 2^64.
+const INV: u64 = 0x89f3fffcfffcfffd;
+/// R = 2^384 mod p, i.e., one in Montgomery form.
+const R: [u64; 6] = [
+    0x760900000002fffd,
+    0xebf4000bc40c0002,
+    0x5f48985753c758ba,
+    0x77ce585370525745,
+    0x5c071a97a256ec6d,
+    0x15f65ec3fa80e493,
+];
+/// R^2 mod p, which converts integers below 2^384 into Montgomery form.
+const R2: [u64; 6] = [
+    0xf4df1f341c341746,
+    0x0a76e6a609d104f1,
+    0x8de5476c4c95b6d5,
+    0x67eb88a9939d83c0,
+    0x9a793e85b519952d,
+    0x11988fe592cae3aa,
+];
+
+/// CtFpElt is an element of the BLS12-381 base field in Montgomery form. Its
+/// arithmetic runs in time independent of the values of the elements.
+#[derive(Clone, Copy)]
+pub(crate) struct CtFpElt([u64; 6]);
+
+impl CtFpElt {
+    pub(crate) const ZERO: CtFpElt = CtFpElt([0; 6]);
+    pub(crate) const ONE: CtFpElt = CtFpElt(R);
+
+    /// Interprets `b` as a big-endian integer of any length and reduces it
+    /// modulo p. Only the length of `b` affects the running time.
+    pub(crate) fn from_bytes_be(b: &[u8]) -> CtFpElt {
+        let r2 = CtFpElt(R2);
+        // 2^64 in Montgomery form.
+        let shift = &CtFpElt([0, 1, 0, 0, 0, 0]) * &r2;
+        let head = b.len() % 8;
+        let chunks = std::iter::once(&b[..head]).chain(b[head..].chunks(8));
+        chunks
+            .filter(|c| !c.is_empty())
+            .fold(CtFpElt::ZERO, |acc, chunk| {
+                let mut word = [0u8; 8];
+                word[8 - chunk.len()..].copy_from_slice(chunk);
+                let word = CtFpElt([u64::from_be_bytes(word), 0, 0, 0, 0, 0]);
+                &(&acc * &shift) + &(&word * &r2)
+            })
+    }
+    /// Returns the canonical big-endian encoding of the element in 48 bytes.
+    pub(crate) fn to_bytes_be(self) -> [u8; 48] {
+        let c = self.canonical();
+        let mut out = [0u8; 48];
+        for (i, limb) in c.iter().enumerate() {
+            out[40 - 8 * i..48 - 8 * i].copy_from_slice(&limb.to_be_bytes());
+        }
+        out
+    }
+    /// Returns the element out of Montgomery form.
+    fn canonical(&self) -> [u64; 6] {
+        (self * &CtFpElt([1, 0, 0, 0, 0, 0])).0
+    }
+    pub(crate) fn is_zero(&self) -> Choice {
+        let acc = self.0.iter().fold(0, |acc, l| acc | l);
+        // The top bit of acc | -acc is set if and only if acc is not zero.
+        Choice::from_bit(!((acc | acc.wrapping_neg()) >> 63))
+    }
+    pub(crate) fn ct_eq(&self, other: &CtFpElt) -> Choice {
+        (self - other).is_zero()
+    }
+    /// Returns `b` if `c` is true, and `a` otherwise.
+    pub(crate) fn select(a: &CtFpElt, b: &CtFpElt, c: Choice) -> CtFpElt {
+        let mut out = [0u64; 6];
+        for (i, o) in out.iter_mut().enumerate() {
+            *o = a.0[i] ^ (c.mask() & (a.0[i] ^ b.0[i]));
+        }
+        CtFpElt(out)
+    }
+    /// Returns the least significant bit of the canonical representative.
+    pub(crate) fn sgn0(&self) -> Choice {
+        Choice::from_bit(self.canonical()[0])
+    }
+    pub(crate) fn square(&self) -> CtFpElt {
+        self * self
+    }
+    /// Computes self^e for a public exponent `e` given as little-endian limbs.
+    pub(crate) fn pow(&self, e: &[u64]) -> CtFpElt {
+        let mut acc = CtFpElt::ONE;
+        for limb in e.iter().rev() {
+            for i in (0..64).rev() {
+                acc = acc.square();
+                if (limb >> i) & 1 == 1 {
+                    acc = &acc * self;
+                }
+            }
+        }
+        acc
+    }
+    /// Returns 1/self, or zero if self is zero, using Fermat's little theorem.
+    pub(crate) fn inv0(&self) -> CtFpElt {
+        let mut e = P;
+        e[0] -= 2;
+        self.pow(&e)
+    }
+    /// Subtracts p if the 384-bit value `a` (plus `carry` times 2^384) is not
+    /// smaller than p.
+    fn reduce(a: [u64; 6], carry: u64) -> CtFpElt {
+        let (mut d, mut borrow) = ([0u64; 6], 0);
+        for i in 0..6 {
+            (d[i], borrow) = sbb(a[i], P[i], borrow);
+        }
+        let (_, borrow) = sbb(carry, 0, borrow);
+        CtFpElt::select(&CtFpElt(d), &CtFpElt(a), Choice::from_bit(borrow))
+    }
+}
+
+impl Add for &CtFpElt {
+    type Output = CtFpElt;
+    fn add(self, other: &CtFpElt) -> CtFpElt {
+        let (mut s, mut carry) = ([0u64; 6], 0);
+        for i in 0..6 {
+            (s[i], carry) = adc(self.0[i], other.0[i], carry);
+        }
+        CtFpElt::reduce(s, carry)
+    }
+}
+
+impl Sub for &CtFpElt {
+    type Output = CtFpElt;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: &CtFpElt) -> CtFpElt {
+        let (mut d, mut borrow) = ([0u64; 6], 0);
+        for i in 0..6 {
+            (d[i], borrow) = sbb(self.0[i], other.0[i], borrow);
+        }
+        // Adds p back if the subtraction wrapped around.
+        let mask = 0u64.wrapping_sub(borrow);
+        let mut carry = 0;
+        for i in 0..6 {
+            (d[i], carry) = adc(d[i], P[i] & mask, carry);
+        }
+        CtFpElt(d)
+    }
+}
+
+impl Neg for &CtFpElt {
+    type Output = CtFpElt;
+    fn neg(self) -> CtFpElt {
+        &CtFpElt::ZERO - self
+    }
+}
+
+impl Mul for &CtFpElt {
+    type Output = CtFpElt;
+    /// Montgomery multiplication with the CIOS method.
+    fn mul(self, other: &CtFpElt) -> CtFpElt {
+        let mut t = [0u64; 8];
+        for i in 0..6 {
+            let mut c = 0;
+            for j in 0..6 {
+                (t[j], c) = mac(t[j], self.0[j], other.0[i], c);
+            }
+            (t[6], t[7]) = adc(t[6], c, 0);
+            let m = t[0].wrapping_mul(INV);
+            let (_, mut c) = mac(t[0], m, P[0], 0);
+            for j in 1..6 {
+                (t[j - 1], c) = mac(t[j], m, P[j], c);
+            }
+            (t[5], c) = adc(t[6], c, 0);
+            t[6] = t[7] + c;
+        }
+        let mut out = [0u64; 6];
+        out.copy_from_slice(&t[..6]);
+        CtFpElt::reduce(out, t[6])
+    }
+}
@@ -0,0 +1,158 @@
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+
+use crate::ct::Choice;
+use crate::error::Error;
+use crate::fp::FromUniformBytes;
+use crate::g2::ct::fp::CtFpElt;
+use crate::g2::fp2::Fp2Elt;
+
+/// CtFp2 is the field [`Fp2`](crate::g2::Fp2) with constant-time arithmetic.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct CtFp2;
+
+impl CtFp2 {
+    /// Interprets each slice as a big-endian integer and reduces it modulo p.
+    #[allow(clippy::wrong_self_convention)] // Mirrors Fp2::from_bytes_be.
+    pub(crate) fn from_bytes_be(&self, c0: &[u8], c1: &[u8]) -> CtFp2Elt {
+        CtFp2Elt {
+            c0: CtFpElt::from_bytes_be(c0),
+            c1: CtFpElt::from_bytes_be(c1),
+        }
+    }
+}
+
+impl FromUniformBytes for CtFp2 {
+    type Elt = CtFp2Elt;
+    fn degree(&self) -> usize {
+        2
+    }
+    fn elt_from_uniform_bytes(&self, e: &[&[u8]]) -> Result<Self::Elt, Error> {
+        Ok(self.from_bytes_be(e[0], e[1]))
+    }
+}
+
+/// CtFp2Elt is an element c0 + c1*I of [`CtFp2`].
+#[derive(Clone, Copy)]
+pub(crate) struct CtFp2Elt {
+    c0: CtFpElt,
+    c1: CtFpElt,
+}
+
+impl CtFp2Elt {
+    pub(crate) const ZERO: CtFp2Elt = CtFp2Elt {
+        c0: CtFpElt::ZERO,
+        c1: CtFpElt::ZERO,
+    };
+    pub(crate) const ONE: CtFp2Elt = CtFp2Elt {
+        c0: CtFpElt::ONE,
+        c1: CtFpElt::ZERO,
+    };
+
+    pub(crate) fn from_elt(e: &Fp2Elt) -> CtFp2Elt {
+        CtFp2.from_bytes_be(&e.c0.to_bytes_be(), &e.c1.to_bytes_be())
+    }
+    /// Converts the element into an [`Fp2Elt`], whose arithmetic is not
+    /// constant time.
+    pub(crate) fn to_elt(self) -> Fp2Elt {
+        Fp2Elt {
+            c0: BigUint::from_bytes_be(&self.c0.to_bytes_be()),
+            c1: BigUint::from_bytes_be(&self.c1.to_bytes_be()),
+        }
+    }
+    pub(crate) fn is_zero(&self) -> Choice {
+        self.c0.is_zero().and(self.c1.is_zero())
+    }
+    pub(crate) fn ct_eq(&self, other: &CtFp2Elt) -> Choice {
+        self.c0.ct_eq(&other.c0).and(self.c1.ct_eq(&other.c1))
+    }
+    /// Returns `b` if `c` is true, and `a` otherwise. This is CMOV(a, b, c)
+    /// of the draft.
+    pub(crate) fn select(a: &CtFp2Elt, b: &CtFp2Elt, c: Choice) -> CtFp2Elt {
+        CtFp2Elt {
+            c0: CtFpElt::select(&a.c0, &b.c0, c),
+            c1: CtFpElt::select(&a.c1, &b.c1, c),
+        }
+    }
+    /// Returns the sign of the element as defined in Section 4.1 of the draft.
+    pub(crate) fn sgn0(&self) -> Choice {
+        let sign_0 = self.c0.sgn0();
+        let zero_0 = self.c0.is_zero();
+        let sign_1 = self.c1.sgn0();
+        sign_0.or(zero_0.and(sign_1))
+    }
+    pub(crate) fn square(&self) -> CtFp2Elt {
+        // (c0 + c1*I)^2 = (c0 + c1)(c0 - c1) + 2*c0*c1*I.
+        let t = &self.c0 * &self.c1;
+        CtFp2Elt {
+            c0: &(&self.c0 + &self.c1) * &(&self.c0 - &self.c1),
+            c1: &t + &t,
+        }
+    }
+    /// Computes self^e for a public exponent `e` given as little-endian limbs.
+    pub(crate) fn pow(&self, e: &[u64]) -> CtFp2Elt {
+        let mut acc = CtFp2Elt::ONE;
+        for limb in e.iter().rev() {
+            for i in (0..64).rev() {
+                acc = acc.square();
+                if (limb >> i) & 1 == 1 {
+                    acc = &acc * self;
+                }
+            }
+        }
+        acc
+    }
+    /// Returns 1/self, or zero if self is zero, as inv0 of the draft.
+    pub(crate) fn inv0(&self) -> CtFp2Elt {
+        let norm = &self.c0.square() + &self.c1.square();
+        let t = norm.inv0();
+        CtFp2Elt {
+            c0: &self.c0 * &t,
+            c1: -&(&self.c1 * &t),
+        }
+    }
+}
+
+impl Add for &CtFp2Elt {
+    type Output = CtFp2Elt;
+    fn add(self, other: &CtFp2Elt) -> CtFp2Elt {
+        CtFp2Elt {
+            c0: &self.c0 + &other.c0,
+            c1: &self.c1 + &other.c1,
+        }
+    }
+}
+
+impl Sub for &CtFp2Elt {
+    type Output = CtFp2Elt;
+    fn sub(self, other: &CtFp2Elt) -> CtFp2Elt {
+        CtFp2Elt {
+            c0: &self.c0 - &other.c0,
+            c1: &self.c1 - &other.c1,
+        }
+    }
+}
+
+impl Mul for &CtFp2Elt {
+    type Output = CtFp2Elt;
+    fn mul(self, other: &CtFp2Elt) -> CtFp2Elt {
+        let v0 = &self.c0 * &other.c0;
+        let v1 = &self.c1 * &other.c1;
+        let t = &(&self.c0 + &self.c1) * &(&other.c0 + &other.c1);
+        CtFp2Elt {
+            c0: &v0 - &v1,
+            c1: &(&t - &v0) - &v1,
+        }
+    }
+}
+
+impl Neg for &CtFp2Elt {
+    type Output = CtFp2Elt;
+    fn neg(self) -> CtFp2Elt {
+        CtFp2Elt {
+            c0: -&self.c0,
+            c1: -&self.c1,
+        }
+    }
+}
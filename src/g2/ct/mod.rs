@@ -0,0 +1,174 @@
+//! Constant-time implementation of the suites of BLS12-381 G2, on top of the
+//! [`ct`](crate::ct) module: field elements are held in fixed-size limbs and
+//! the map to curve follows the straight-line procedure of Appendix F.2 of
+//! the draft.
+
+use num_bigint::BigUint;
+
+use crate::ct::{Choice, CtMap};
+use crate::field::Field;
+use crate::g2::fp2::{Fp2, Fp2Elt};
+use crate::g2::Curve;
+use crate::isogeny::BLS12381G2_ISOGENY;
+use crate::map::Isogeny;
+
+mod curve;
+mod fp;
+mod fp2;
+
+use crate::g2::ct::curve::{CtCurve, CtPoint};
+pub(crate) use crate::g2::ct::fp2::{CtFp2, CtFp2Elt};
+
+/// SqrtRatio implements sqrt_ratio of Appendix F.2.1.1 of the draft, which
+/// works for any field, over Fp2.
+struct SqrtRatio {
+    c1: usize,
+    c3: Vec<u64>,
+    c4: Vec<u64>,
+    c5: Vec<u64>,
+    c6: CtFp2Elt,
+    c7: CtFp2Elt,
+}
+
+impl SqrtRatio {
+    fn new(z: &CtFp2Elt) -> SqrtRatio {
+        let p = Fp2.get_modulus();
+        let one = BigUint::from(1u32);
+        let q1 = &p * &p - 1u32;
+        let c1 = q1.trailing_zeros().unwrap() as usize;
+        let c2: BigUint = &q1 >> c1;
+        let c3: BigUint = (&c2 - 1u32) >> 1u32;
+        let c4: BigUint = (&one << c1) - 1u32;
+        let c5: BigUint = &one << (c1 - 1);
+        let c7: BigUint = (&c2 + 1u32) >> 1u32;
+        SqrtRatio {
+            c1,
+            c3: c3.to_u64_digits(),
+            c4: c4.to_u64_digits(),
+            c5: c5.to_u64_digits(),
+            c6: z.pow(&c2.to_u64_digits()),
+            c7: z.pow(&c7.to_u64_digits()),
+        }
+    }
+
+    /// Returns (true, sqrt(u/v)) if u/v is square, and (false, sqrt(Z*u/v))
+    /// otherwise.
+    fn sqrt_ratio(&self, u: &CtFp2Elt, v: &CtFp2Elt) -> (Choice, CtFp2Elt) {
+        let mut tv1 = self.c6;
+        let mut tv2 = v.pow(&self.c4);
+        let mut tv3 = tv2.square();
+        tv3 = &tv3 * v;
+        let mut tv5 = u * &tv3;
+        tv5 = tv5.pow(&self.c3);
+        tv5 = &tv5 * &tv2;
+        tv2 = &tv5 * v;
+        tv3 = &tv5 * u;
+        let mut tv4 = &tv3 * &tv2;
+        tv5 = tv4.pow(&self.c5);
+        let is_qr = tv5.ct_eq(&CtFp2Elt::ONE);
+        tv2 = &tv3 * &self.c7;
+        tv5 = &tv4 * &tv1;
+        tv3 = CtFp2Elt::select(&tv2, &tv3, is_qr);
+        tv4 = CtFp2Elt::select(&tv5, &tv4, is_qr);
+        for i in (2..=self.c1).rev() {
+            tv5 = tv4;
+            for _ in 0..i - 2 {
+                tv5 = tv5.square();
+            }
+            let e1 = tv5.ct_eq(&CtFp2Elt::ONE);
+            tv2 = &tv3 * &tv1;
+            tv1 = tv1.square();
+            tv5 = &tv4 * &tv1;
+            tv3 = CtFp2Elt::select(&tv2, &tv3, e1);
+            tv4 = CtFp2Elt::select(&tv5, &tv4, e1);
+        }
+        (is_qr, tv3)
+    }
+}
+
+/// CtSSWU is the Simplified SWU map to the 3-isogenous curve E' followed by
+/// the 3-isogeny to the G2 curve, as the map of the suites of BLS12-381 G2
+/// but in constant time.
+pub(crate) struct CtSSWU {
+    curve: CtCurve,
+    a: CtFp2Elt,
+    b: CtFp2Elt,
+    z: CtFp2Elt,
+    sqrt: SqrtRatio,
+    x_num: Vec<CtFp2Elt>,
+    x_den: Vec<CtFp2Elt>,
+    y_num: Vec<CtFp2Elt>,
+    y_den: Vec<CtFp2Elt>,
+}
+
+impl CtSSWU {
+    pub(crate) fn new(e: Curve, z: &Fp2Elt) -> CtSSWU {
+        let iso = Isogeny::new(&Fp2, &BLS12381G2_ISOGENY);
+        let elts = |v: &[Fp2Elt]| v.iter().map(CtFp2Elt::from_elt).collect();
+        let z = CtFp2Elt::from_elt(z);
+        CtSSWU {
+            curve: CtCurve::new(e),
+            a: CtFp2Elt::from_elt(&Fp2.from(BLS12381G2_ISOGENY.a)),
+            b: CtFp2Elt::from_elt(&Fp2.from(BLS12381G2_ISOGENY.b)),
+            sqrt: SqrtRatio::new(&z),
+            z,
+            x_num: elts(&iso.x_num),
+            x_den: elts(&iso.x_den),
+            y_num: elts(&iso.y_num),
+            y_den: elts(&iso.y_den),
+        }
+    }
+
+    /// Evaluates the 3-isogeny on the affine point (x', y') of E'. The output
+    /// is projective, so no inversion is needed.
+    fn isogeny(&self, x: &CtFp2Elt, y: &CtFp2Elt) -> CtPoint {
+        let horner = |coeffs: &[CtFp2Elt]| {
+            coeffs
+                .iter()
+                .rev()
+                .fold(CtFp2Elt::ZERO, |acc, k| &(&acc * x) + k)
+        };
+        let (xn, xd) = (horner(&self.x_num), horner(&self.x_den));
+        let (yn, yd) = (horner(&self.y_num), horner(&self.y_den));
+        CtPoint::new(&xn * &yd, &(y * &yn) * &xd, &xd * &yd)
+    }
+}
+
+impl CtMap for CtSSWU {
+    type Elt = CtFp2Elt;
+    type Group = CtCurve;
+    fn group(&self) -> &CtCurve {
+        &self.curve
+    }
+    /// Implements the straight-line map_to_curve_simple_swu of Appendix F.2
+    /// of the draft, and evaluates the isogeny on its output.
+    fn map(&self, u: &CtFp2Elt) -> CtPoint {
+        let (a, b, z) = (&self.a, &self.b, &self.z);
+        let mut tv1 = u.square();
+        tv1 = z * &tv1;
+        let mut tv2 = tv1.square();
+        tv2 = &tv2 + &tv1;
+        let mut tv3 = &tv2 + &CtFp2Elt::ONE;
+        tv3 = b * &tv3;
+        let mut tv4 = CtFp2Elt::select(z, &-&tv2, tv2.is_zero().not());
+        tv4 = a * &tv4;
+        tv2 = tv3.square();
+        let mut tv6 = tv4.square();
+        let mut tv5 = a * &tv6;
+        tv2 = &tv2 + &tv5;
+        tv2 = &tv2 * &tv3;
+        tv6 = &tv6 * &tv4;
+        tv5 = b * &tv6;
+        tv2 = &tv2 + &tv5;
+        let mut x = &tv1 * &tv3;
+        let (is_gx1_square, y1) = self.sqrt.sqrt_ratio(&tv2, &tv6);
+        let mut y = &tv1 * u;
+        y = &y * &y1;
+        x = CtFp2Elt::select(&x, &tv3, is_gx1_square);
+        y = CtFp2Elt::select(&y, &y1, is_gx1_square);
+        let e1 = u.sgn0().eq(y.sgn0());
+        y = CtFp2Elt::select(&-&y, &y, e1);
+        x = &x * &tv4.inv0();
+        self.isogeny(&x, &y)
+    }
+}
@@ -0,0 +1,273 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num_bigint::BigUint;
+
+use crate::error::Error;
+use crate::field::{parse_int, Field, FieldElement};
+use crate::fp::FromUniformBytes;
+
+lazy_static! {
+    static ref MODULUS: BigUint = BigUint::parse_bytes(
+        b"1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab",
+        16
+    )
+    .unwrap();
+}
+
+/// Fp2 is the quadratic extension Fp\[I\]/(I^2+1) of the BLS12-381 base field.
+#[derive(Clone, Copy, Default)]
+pub struct Fp2;
+
+/// Fp2Elt is an element c0 + c1*I of [`Fp2`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct Fp2Elt {
+    pub(super) c0: BigUint,
+    pub(super) c1: BigUint,
+}
+
+impl Fp2 {
+    pub fn elt(&self, c0: BigUint, c1: BigUint) -> Fp2Elt {
+        Fp2Elt {
+            c0: c0 % &*MODULUS,
+            c1: c1 % &*MODULUS,
+        }
+    }
+    /// Returns c0 + c1*I for small (possibly negative) integers.
+    pub fn from_i32(&self, c0: i32, c1: i32) -> Fp2Elt {
+        Fp2Elt {
+            c0: from_i32(c0),
+            c1: from_i32(c1),
+        }
+    }
+    /// Interprets each slice as a big-endian integer and reduces it modulo p.
+    pub fn from_bytes_be(&self, c0: &[u8], c1: &[u8]) -> Fp2Elt {
+        self.elt(BigUint::from_bytes_be(c0), BigUint::from_bytes_be(c1))
+    }
+}
+
+impl Field for Fp2 {
+    type Elt = Fp2Elt;
+    fn get_modulus(&self) -> BigUint {
+        MODULUS.clone()
+    }
+    fn degree(&self) -> usize {
+        2
+    }
+    fn zero(&self) -> Fp2Elt {
+        self.elt(BigUint::default(), BigUint::default())
+    }
+    fn one(&self) -> Fp2Elt {
+        self.elt(BigUint::from(1u32), BigUint::default())
+    }
+    fn from(&self, s: &str) -> Fp2Elt {
+        let mut it = s.split(',').map(parse_int);
+        let c0 = it.next().unwrap_or_default();
+        let c1 = it.next().unwrap_or_default();
+        self.elt(c0, c1)
+    }
+    fn from_ints(&self, c: &[BigUint]) -> Fp2Elt {
+        let mut it = c.iter().cloned();
+        let c0 = it.next().unwrap_or_default();
+        let c1 = it.next().unwrap_or_default();
+        self.elt(c0, c1)
+    }
+    fn to_ints(&self, e: &Fp2Elt) -> Vec<BigUint> {
+        vec![e.c0.clone(), e.c1.clone()]
+    }
+}
+
+impl FromUniformBytes for Fp2 {
+    type Elt = Fp2Elt;
+    fn degree(&self) -> usize {
+        2
+    }
+    fn elt_from_uniform_bytes(&self, e: &[&[u8]]) -> Result<Self::Elt, Error> {
+        Ok(self.from_bytes_be(e[0], e[1]))
+    }
+}
+
+fn from_i32(n: i32) -> BigUint {
+    let v = BigUint::from(n.unsigned_abs());
+    if n < 0 {
+        &*MODULUS - v
+    } else {
+        v
+    }
+}
+
+impl Fp2Elt {
+    #[inline]
+    pub fn c0(&self) -> &BigUint {
+        &self.c0
+    }
+    #[inline]
+    pub fn c1(&self) -> &BigUint {
+        &self.c1
+    }
+    /// Returns the norm c0^2 + c1^2, which lies in the base field.
+    fn norm(&self) -> BigUint {
+        (&self.c0 * &self.c0 + &self.c1 * &self.c1) % &*MODULUS
+    }
+    /// Returns the conjugate c0 - c1*I, i.e., the image under the Frobenius.
+    pub(super) fn conj(&self) -> Fp2Elt {
+        Fp2Elt {
+            c0: self.c0.clone(),
+            c1: (&*MODULUS - &self.c1) % &*MODULUS,
+        }
+    }
+}
+
+impl FieldElement for Fp2Elt {
+    fn is_zero(&self) -> bool {
+        self.c0.bits() == 0 && self.c1.bits() == 0
+    }
+    fn sgn0(&self) -> bool {
+        let sign_0 = self.c0.bit(0);
+        let zero_0 = self.c0.bits() == 0;
+        let sign_1 = self.c1.bit(0);
+        sign_0 || (zero_0 && sign_1)
+    }
+    fn is_square(&self) -> bool {
+        is_square_fp(&self.norm())
+    }
+    fn inv(&self) -> Fp2Elt {
+        let p: &BigUint = &MODULUS;
+        let n = self.norm().modpow(&(p - 2u32), p);
+        Fp2Elt {
+            c0: &self.c0 * &n % p,
+            c1: (p - &self.c1) * &n % p,
+        }
+    }
+    /// Since p = 3 mod 4, the root is computed with the complex method.
+    fn sqrt(&self) -> Option<Fp2Elt> {
+        let p: &BigUint = &MODULUS;
+        let half = (p + 1u32) >> 1;
+        let root = if self.c1.bits() == 0 {
+            if is_square_fp(&self.c0) {
+                Fp2Elt {
+                    c0: sqrt_fp(&self.c0),
+                    c1: BigUint::default(),
+                }
+            } else {
+                Fp2Elt {
+                    c0: BigUint::default(),
+                    c1: sqrt_fp(&(p - &self.c0)),
+                }
+            }
+        } else {
+            let alpha = sqrt_fp(&self.norm());
+            let mut delta = (&self.c0 + &alpha) * &half % p;
+            if !is_square_fp(&delta) {
+                delta = (&self.c0 + p - &alpha) * &half % p;
+            }
+            let x0 = sqrt_fp(&delta);
+            let x1 = &self.c1 * (&x0 << 1u32).modpow(&(p - 2u32), p) % p;
+            Fp2Elt { c0: x0, c1: x1 }
+        };
+        if &root.square() == self {
+            Some(root)
+        } else {
+            None
+        }
+    }
+}
+
+fn is_square_fp(a: &BigUint) -> bool {
+    let p: &BigUint = &MODULUS;
+    let ls = a.modpow(&((p - 1u32) >> 1), p);
+    ls.bits() <= 1
+}
+
+fn sqrt_fp(a: &BigUint) -> BigUint {
+    let p: &BigUint = &MODULUS;
+    a.modpow(&((p + 1u32) >> 2), p)
+}
+
+impl Add<&Fp2Elt> for &Fp2Elt {
+    type Output = Fp2Elt;
+    fn add(self, other: &Fp2Elt) -> Fp2Elt {
+        let p: &BigUint = &MODULUS;
+        Fp2Elt {
+            c0: (&self.c0 + &other.c0) % p,
+            c1: (&self.c1 + &other.c1) % p,
+        }
+    }
+}
+
+impl Sub<&Fp2Elt> for &Fp2Elt {
+    type Output = Fp2Elt;
+    fn sub(self, other: &Fp2Elt) -> Fp2Elt {
+        let p: &BigUint = &MODULUS;
+        Fp2Elt {
+            c0: (&self.c0 + p - &other.c0) % p,
+            c1: (&self.c1 + p - &other.c1) % p,
+        }
+    }
+}
+
+impl Mul<&Fp2Elt> for &Fp2Elt {
+    type Output = Fp2Elt;
+    fn mul(self, other: &Fp2Elt) -> Fp2Elt {
+        let p: &BigUint = &MODULUS;
+        let t0 = &self.c0 * &other.c0;
+        let t1 = &self.c1 * &other.c1;
+        let t2 = (&self.c0 + &self.c1) * (&other.c0 + &other.c1);
+        Fp2Elt {
+            c0: (&t0 + p * p - &t1) % p,
+            c1: (t2 - t0 - t1) % p,
+        }
+    }
+}
+
+impl Neg for &Fp2Elt {
+    type Output = Fp2Elt;
+    fn neg(self) -> Fp2Elt {
+        let p: &BigUint = &MODULUS;
+        Fp2Elt {
+            c0: (p - &self.c0) % p,
+            c1: (p - &self.c1) % p,
+        }
+    }
+}
+
+macro_rules! forward_owned_binop {
+    ($trait:ident, $method:ident) => {
+        impl $trait for Fp2Elt {
+            type Output = Fp2Elt;
+            fn $method(self, other: Fp2Elt) -> Fp2Elt {
+                (&self).$method(&other)
+            }
+        }
+        impl<'b> $trait<&'b Fp2Elt> for Fp2Elt {
+            type Output = Fp2Elt;
+            fn $method(self, other: &'b Fp2Elt) -> Fp2Elt {
+                (&self).$method(other)
+            }
+        }
+    };
+}
+
+forward_owned_binop!(Add, add);
+forward_owned_binop!(Sub, sub);
+forward_owned_binop!(Mul, mul);
+
+impl Neg for Fp2Elt {
+    type Output = Fp2Elt;
+    fn neg(self) -> Fp2Elt {
+        -&self
+    }
+}
+
+impl fmt::Display for Fp2Elt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = 2 + 2 * Fp2.size_bytes();
+        write!(f, "{:#0w$x},{:#0w$x}", self.c0, self.c1, w = width)
+    }
+}
+
+impl fmt::Debug for Fp2Elt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,166 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use num_bigint::BigUint;
+
+use crate::api::{
+    CheckMap, ClearCofactor, CtEncoder, Encoding, ExpID, GetHashToCurve, HashID, HashToCurve,
+    HashToField, LazyInverseMap, MapID, ModulusBits, Suite,
+};
+use crate::ct::CtEncoding;
+use crate::curve::{weierstrass, GetCurve, MapToCurve};
+use crate::error::Error;
+use crate::expander::get_expander;
+use crate::field::Field;
+use crate::findz::check_z_sswu;
+use crate::fp::FpHasher;
+use crate::isogeny::BLS12381G2_ISOGENY;
+use crate::map::SSWUAB0;
+use crate::register_in_map;
+
+mod cofactor;
+mod ct;
+mod fp2;
+
+pub use crate::g2::cofactor::Bls12381G2Cofactor;
+use crate::g2::ct::{CtFp2, CtSSWU};
+pub use crate::g2::fp2::{Fp2, Fp2Elt};
+
+/// Curve is the G2 curve of BLS12-381, y^2 = x^3 + 4 * (1 + I), defined
+/// over [`Fp2`].
+pub type Curve = weierstrass::Curve<Fp2>;
+
+/// Point is a point on the G2 [`Curve`].
+pub type Point = weierstrass::Point<Fp2>;
+
+/// G2CurveID identifies curves defined over [`Fp2`].
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct G2CurveID(&'static str);
+
+pub static BLS12381G2: G2CurveID = G2CurveID("BLS12-381 G2");
+
+impl GetCurve for G2CurveID {
+    type E = Curve;
+    fn get(&self) -> Curve {
+        let f = Fp2;
+        Curve {
+            a: f.zero(),
+            b: f.from_i32(4, 4),
+            r: BigUint::parse_bytes(
+                b"73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+                16,
+            )
+            .unwrap(),
+            h: BigUint::parse_bytes(
+                b"5d543a95414e7f1091d50792876a202cd91de4547085abaa68a205b2e5a7ddfa628f1cb4d9e82ef21537e293a6691ae1616ec6e786f0c70cf1c38e31c7238e5",
+                16,
+            )
+            .unwrap(),
+            f,
+        }
+    }
+}
+
+impl ModulusBits for G2CurveID {
+    fn modulus_bits(&self) -> usize {
+        self.get().get_field().get_modulus().bits() as usize
+    }
+}
+
+impl CheckMap for G2CurveID {
+    fn check_map(&self, map: MapID) -> Result<(), Error> {
+        let f = Fp2;
+        match map {
+            MapID::SSWUAB0FP2(c0, c1) => check_z_sswu(
+                &f,
+                &f.from(BLS12381G2_ISOGENY.a),
+                &f.from(BLS12381G2_ISOGENY.b),
+                &f.from_i32(c0, c1),
+            ),
+            _ => Err(Error::InvalidSuite(format!(
+                "{:?} is not a map to {}",
+                map, self
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for G2CurveID {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl GetHashToCurve for Suite<G2CurveID> {
+    type E = Curve;
+    fn try_get(&self, dst: &[u8]) -> Result<Box<dyn HashToCurve<E = Self::E>>, Error> {
+        let curve = self.curve.get();
+        let f = curve.get_field();
+        let z = match self.map {
+            MapID::SSWUAB0FP2(c0, c1) => f.from_i32(c0, c1),
+            _ => return Err(Error::UnsupportedMap(self.name.to_string())),
+        };
+        let ct: Option<Box<dyn CtEncoder<Point = Point>>> = if self.ct {
+            let exp = get_expander(self.exp, dst, self.k);
+            let hash_to_field = FpHasher {
+                f: CtFp2,
+                exp,
+                l: self.l,
+            };
+            Some(Box::new(CtEncoding::new(
+                hash_to_field,
+                Box::new(CtSSWU::new(curve.clone(), &z)),
+                h_eff(),
+            )))
+        } else {
+            None
+        };
+        let map_to_curve: Box<dyn MapToCurve<E = Curve>> =
+            Box::new(SSWUAB0::new(curve.clone(), &BLS12381G2_ISOGENY, z));
+        let exp = get_expander(self.exp, dst, self.k);
+        let hash_to_field: Box<dyn HashToField<F = Fp2>> = Box::new(FpHasher { f, exp, l: self.l });
+        let clear_cofactor: Box<dyn ClearCofactor<Point = Point>> =
+            Box::new(Bls12381G2Cofactor::new());
+        Ok(Box::new(Encoding {
+            curve,
+            hash_to_field,
+            map_to_curve,
+            clear_cofactor,
+            inverse_map: LazyInverseMap::new(None),
+            l: self.l,
+            ro: self.ro,
+            ct,
+        }))
+    }
+}
+
+/// Returns the effective cofactor h_eff of Section 8.8.2 of the draft, which
+/// is used instead of the cofactor for clearing points into G2.
+fn h_eff() -> BigUint {
+    BigUint::parse_bytes(
+        b"bc69f08f2ee75b3584c6a0ea91b352888e2a8e9145ad7689986ff031508ffe1329c2f178731db956d82bf015d1212b02ec0ec69d7477c1ae954cbc06689f6a359894c0adebbf6b4e8020005aaa95551",
+        16,
+    )
+    .unwrap()
+}
+
+lazy_static! {
+    pub static ref SUITES_BLS12381G2: HashMap<String, Suite<G2CurveID>> =
+        register_in_map!([BLS12381G2_XMDSHA256_SSWU_NU_, BLS12381G2_XMDSHA256_SSWU_RO_]);
+}
+
+pub static BLS12381G2_XMDSHA256_SSWU_NU_: Suite<G2CurveID> = Suite {
+    name: Cow::Borrowed("BLS12381G2_XMD:SHA-256_SSWU_NU_"),
+    curve: BLS12381G2,
+    map: MapID::SSWUAB0FP2(-2, -1),
+    k: 128,
+    exp: ExpID::XMD(HashID::SHA256),
+    l: 64,
+    ro: false,
+    ct: false,
+};
+pub static BLS12381G2_XMDSHA256_SSWU_RO_: Suite<G2CurveID> = Suite {
+    name: Cow::Borrowed("BLS12381G2_XMD:SHA-256_SSWU_RO_"),
+    ro: true,
+    ..BLS12381G2_XMDSHA256_SSWU_NU_
+};
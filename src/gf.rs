@@ -0,0 +1,226 @@
+use num_bigint::{BigInt, BigUint};
+
+/// Gf implements the arithmetic modulo a prime p needed to select Z, to
+/// invert the maps and to build the prime-order groups.
+pub(crate) struct Gf {
+    p: BigUint,
+}
+
+impl Gf {
+    pub(crate) fn new(p: &BigInt) -> Self {
+        Gf {
+            p: p.magnitude().clone(),
+        }
+    }
+    pub(crate) fn modulus(&self) -> &BigUint {
+        &self.p
+    }
+    pub(crate) fn elt(&self, x: &BigInt) -> BigUint {
+        let p = BigInt::from(self.p.clone());
+        (((x % &p) + &p) % &p).magnitude().clone()
+    }
+    pub(crate) fn neg(&self, x: &BigUint) -> BigUint {
+        (&self.p - x % &self.p) % &self.p
+    }
+    pub(crate) fn add(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        (x + y) % &self.p
+    }
+    pub(crate) fn sub(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        (x + self.neg(y)) % &self.p
+    }
+    pub(crate) fn mul(&self, x: &BigUint, y: &BigUint) -> BigUint {
+        (x * y) % &self.p
+    }
+    pub(crate) fn pow(&self, x: &BigUint, e: &BigUint) -> BigUint {
+        x.modpow(e, &self.p)
+    }
+    pub(crate) fn inv(&self, x: &BigUint) -> BigUint {
+        x.modpow(&(&self.p - 2u32), &self.p)
+    }
+    pub(crate) fn is_zero(&self, x: &BigUint) -> bool {
+        (x % &self.p) == BigUint::default()
+    }
+    /// Returns true if x is a square in GF(p), including x = 0.
+    pub(crate) fn is_square(&self, x: &BigUint) -> bool {
+        let e = (&self.p - 1u32) >> 1;
+        let t = x.modpow(&e, &self.p);
+        t == BigUint::default() || t == BigUint::from(1u32)
+    }
+    /// Returns the sign of x as defined in Section 4.1 of the draft.
+    pub(crate) fn sgn0(&self, x: &BigUint) -> bool {
+        (x % &self.p).bit(0)
+    }
+    /// Returns a square root of x, or None if x is not a square. For
+    /// p = 3 mod 4 the root is x^((p+1)/4); other primes use the
+    /// Tonelli-Shanks algorithm.
+    pub(crate) fn sqrt(&self, x: &BigUint) -> Option<BigUint> {
+        let one = BigUint::from(1u32);
+        let x = x % &self.p;
+        if self.p.bit(0) && self.p.bit(1) {
+            let r = x.modpow(&((&self.p + 1u32) >> 2), &self.p);
+            return (self.mul(&r, &r) == x).then_some(r);
+        }
+        if !self.is_square(&x) {
+            return None;
+        }
+        if self.is_zero(&x) {
+            return Some(x);
+        }
+        let s = (&self.p - 1u32).trailing_zeros().unwrap_or_default();
+        let q = (&self.p - 1u32) >> s;
+        let mut z = BigUint::from(2u32);
+        while self.is_square(&z) {
+            z += 1u32;
+        }
+        let mut m = s;
+        let mut c = z.modpow(&q, &self.p);
+        let mut t = x.modpow(&q, &self.p);
+        let mut r = x.modpow(&((&q + 1u32) >> 1), &self.p);
+        while t != one {
+            let mut i = 0;
+            let mut t2 = t.clone();
+            while t2 != one {
+                t2 = self.mul(&t2, &t2);
+                i += 1;
+            }
+            let b = c.modpow(&(BigUint::from(1u32) << (m - i - 1)), &self.p);
+            m = i;
+            c = self.mul(&b, &b);
+            t = self.mul(&t, &c);
+            r = self.mul(&r, &b);
+        }
+        Some(r)
+    }
+    /// Removes the leading zero coefficients of a polynomial.
+    fn poly_trim(&self, mut a: Vec<BigUint>) -> Vec<BigUint> {
+        while a.last().is_some_and(|x| self.is_zero(x)) {
+            a.pop();
+        }
+        a
+    }
+    /// Returns a mod b, where polynomials are given from the lowest degree
+    /// coefficient, and b is non-zero with no leading zeros.
+    fn poly_rem(&self, a: Vec<BigUint>, b: &[BigUint]) -> Vec<BigUint> {
+        self.poly_div_rem(a, b).1
+    }
+    /// Returns the quotient and the remainder of the division of a by b.
+    fn poly_div_rem(&self, mut a: Vec<BigUint>, b: &[BigUint]) -> (Vec<BigUint>, Vec<BigUint>) {
+        let lead = &b[b.len() - 1];
+        let inv = if *lead == BigUint::from(1u32) {
+            lead.clone()
+        } else {
+            self.inv(lead)
+        };
+        let mut q = vec![BigUint::default(); (a.len() + 1).saturating_sub(b.len())];
+        while a.len() >= b.len() {
+            let c = self.mul(&a[a.len() - 1], &inv);
+            let minus_c = self.neg(&c);
+            let s = a.len() - b.len();
+            for (i, bi) in b.iter().enumerate() {
+                a[s + i] = (&a[s + i] + &minus_c * bi) % &self.p;
+            }
+            q[s] = c;
+            a = self.poly_trim(a);
+        }
+        (q, a)
+    }
+    fn poly_mul_rem(&self, a: &[BigUint], b: &[BigUint], f: &[BigUint]) -> Vec<BigUint> {
+        if a.is_empty() || b.is_empty() {
+            return Vec::new();
+        }
+        // The products are summed before being reduced.
+        let mut c = vec![BigUint::default(); a.len() + b.len() - 1];
+        for (i, ai) in a.iter().enumerate() {
+            for (j, bj) in b.iter().enumerate() {
+                c[i + j] += ai * bj;
+            }
+        }
+        let c = c.into_iter().map(|ci| ci % &self.p).collect();
+        self.poly_rem(self.poly_trim(c), f)
+    }
+    /// Returns a^e mod f.
+    fn poly_pow_rem(&self, a: &[BigUint], e: &BigUint, f: &[BigUint]) -> Vec<BigUint> {
+        let mut r = vec![BigUint::from(1u32)];
+        for i in (0..e.bits()).rev() {
+            r = self.poly_mul_rem(&r, &r, f);
+            if e.bit(i) {
+                r = self.poly_mul_rem(&r, a, f);
+            }
+        }
+        r
+    }
+    /// Divides a non-zero polynomial by its leading coefficient.
+    fn poly_monic(&self, a: Vec<BigUint>) -> Vec<BigUint> {
+        let inv = self.inv(&a[a.len() - 1]);
+        a.iter().map(|c| self.mul(c, &inv)).collect()
+    }
+    /// Returns the monic gcd of a and b, which must not both be zero.
+    fn poly_gcd(&self, mut a: Vec<BigUint>, mut b: Vec<BigUint>) -> Vec<BigUint> {
+        while !b.is_empty() {
+            let t = self.poly_rem(a, &b);
+            a = b;
+            b = t;
+        }
+        self.poly_monic(a)
+    }
+    /// Returns a - c, where c is a constant.
+    fn poly_sub_const(&self, mut a: Vec<BigUint>, c: &BigUint) -> Vec<BigUint> {
+        if a.is_empty() {
+            a.push(BigUint::default());
+        }
+        a[0] = self.sub(&a[0], c);
+        self.poly_trim(a)
+    }
+    /// Evaluates the polynomial f at x.
+    pub(crate) fn poly_eval(&self, f: &[BigUint], x: &BigUint) -> BigUint {
+        f.iter()
+            .rev()
+            .fold(BigUint::default(), |r, c| self.add(&self.mul(&r, x), c))
+    }
+    /// Returns gcd(x^p - x, f), the product of the distinct linear factors of
+    /// f, which must be monic so that reductions modulo f need no inversion.
+    pub(crate) fn linear_part(&self, f: &[BigUint]) -> Vec<BigUint> {
+        let one = BigUint::from(1u32);
+        let x = vec![BigUint::default(), one.clone()];
+        let mut r = self.poly_pow_rem(&x, &self.p, f);
+        r.resize(2.max(r.len()), BigUint::default());
+        r[1] = self.sub(&r[1], &one);
+        self.poly_gcd(f.to_vec(), self.poly_trim(r))
+    }
+    /// Returns the roots in GF(p) of the non-zero polynomial f in increasing
+    /// order, found with the algorithm of Cantor and Zassenhaus: the product
+    /// of the linear factors of f is split by its gcd with
+    /// (x + a)^((p - 1) / 2) - 1 for a = 0, 1, 2, ... until only linear
+    /// factors are left.
+    pub(crate) fn roots(&self, f: &[BigUint]) -> Vec<BigUint> {
+        let f = self.poly_trim(f.to_vec());
+        if f.len() < 2 {
+            return Vec::new();
+        }
+        let f = self.poly_monic(f);
+        let one = BigUint::from(1u32);
+        let e = (&self.p - 1u32) >> 1;
+        let mut a = BigUint::default();
+        let mut roots = Vec::new();
+        let mut factors = vec![self.linear_part(&f)];
+        while let Some(g) = factors.pop() {
+            match g.len() {
+                0 | 1 => {}
+                2 => roots.push(self.neg(&g[0])),
+                _ => {
+                    let h = self.poly_pow_rem(&[a.clone(), one.clone()], &e, &g);
+                    let d = self.poly_gcd(g.clone(), self.poly_sub_const(h, &one));
+                    a += 1u32;
+                    if d.len() > 1 && d.len() < g.len() {
+                        factors.push(self.poly_div_rem(g, &d).0);
+                        factors.push(d);
+                    } else {
+                        factors.push(g);
+                    }
+                }
+            }
+        }
+        roots.sort();
+        roots
+    }
+}
@@ -0,0 +1,134 @@
+use num_bigint::{BigInt, BigUint};
+
+use crate::gf::Gf;
+use crate::group::edwards::{abs, from_canonical_bytes_le, to_bytes_le, Curve, Point};
+
+/// Length in bytes of an encoded element.
+pub(super) const ENCODED_LEN: usize = 56;
+
+lazy_static! {
+    static ref F: Gf = Gf::new(&((BigInt::from(1) << 448u32) - (BigInt::from(1) << 224u32) - 1));
+    static ref D: BigUint = F.elt(&BigInt::from(-39081));
+    pub(super) static ref CURVE: Curve = Curve {
+        f: &F,
+        a: ONE.clone(),
+        d: D.clone(),
+    };
+    static ref ONE: BigUint = BigUint::from(1u32);
+    static ref ONE_MINUS_D: BigUint = BigUint::from(39082u32);
+    static ref ONE_MINUS_TWO_D: BigUint = BigUint::from(78163u32);
+    static ref SQRT_MINUS_D: BigUint = sqrt_ratio_m1(&F.neg(&D), &ONE).1;
+    static ref INVSQRT_MINUS_D: BigUint = F.inv(&SQRT_MINUS_D);
+}
+
+/// Returns the element whose encoding is the generator given in Section 5.4
+/// of RFC 9496.
+pub(super) fn generator() -> Point {
+    let b = hex::decode(
+        "6666666666666666666666666666666666666666666666666666666633333333333333333333333333333333333333333333333333333333",
+    )
+    .unwrap();
+    decode(&b).unwrap()
+}
+
+/// Implements SQRT_RATIO_M1 of Section 5.2 of RFC 9496. It outputs whether
+/// u/v is square, and the non-negative square root of u/v if it is.
+fn sqrt_ratio_m1(u: &BigUint, v: &BigUint) -> (bool, BigUint) {
+    let f = &*F;
+    let e = (f.modulus() - 3u32) >> 2u32;
+    let r = f.mul(u, &f.pow(&f.mul(u, v), &e));
+    let check = f.mul(v, &f.mul(&r, &r));
+    (check == *u, abs(f, &r))
+}
+
+/// Implements the decoding of Section 5.3.1 of RFC 9496.
+pub(super) fn decode(b: &[u8]) -> Option<Point> {
+    let f = &*F;
+    if b.len() != ENCODED_LEN {
+        return None;
+    }
+    let s = from_canonical_bytes_le(f, b)?;
+    if f.sgn0(&s) {
+        return None;
+    }
+    let ss = f.mul(&s, &s);
+    let u1 = f.add(&ONE, &ss);
+    let u1_sqr = f.mul(&u1, &u1);
+    let u2 = f.sub(&u1_sqr, &f.mul(&f.mul(&BigUint::from(4u32), &D), &ss));
+    let (was_square, invsqrt) = sqrt_ratio_m1(&ONE, &f.mul(&u2, &u1_sqr));
+    let u3 = f.mul(&f.mul(&f.add(&s, &s), &invsqrt), &f.mul(&u1, &SQRT_MINUS_D));
+    let u3 = abs(f, &u3);
+    let x = f.mul(&f.mul(&u3, &invsqrt), &f.mul(&u2, &INVSQRT_MINUS_D));
+    let y = f.mul(&f.mul(&f.sub(&ONE, &ss), &invsqrt), &u1);
+    let t = f.mul(&x, &y);
+    if !was_square {
+        return None;
+    }
+    Some(Point {
+        x,
+        y,
+        z: ONE.clone(),
+        t,
+    })
+}
+
+/// Implements the encoding of Section 5.3.2 of RFC 9496.
+pub(super) fn encode(p: &Point) -> Vec<u8> {
+    let f = &*F;
+    let u1 = f.mul(&f.add(&p.x, &p.t), &f.sub(&p.x, &p.t));
+    let x2 = f.mul(&p.x, &p.x);
+    let (_, invsqrt) = sqrt_ratio_m1(&ONE, &f.mul(&f.mul(&u1, &ONE_MINUS_D), &x2));
+    let ratio = abs(f, &f.mul(&f.mul(&invsqrt, &u1), &SQRT_MINUS_D));
+    let u2 = f.sub(&f.mul(&f.mul(&INVSQRT_MINUS_D, &ratio), &p.z), &p.t);
+    let s = f.mul(&f.mul(&ONE_MINUS_D, &invsqrt), &f.mul(&p.x, &u2));
+    to_bytes_le(&abs(f, &s), ENCODED_LEN)
+}
+
+/// Implements the equality check of Section 5.3.3 of RFC 9496.
+pub(super) fn equal(p: &Point, q: &Point) -> bool {
+    F.mul(&p.x, &q.y) == F.mul(&p.y, &q.x)
+}
+
+/// Implements the one-way map MAP of Section 5.3.4 of RFC 9496.
+fn map(t: &BigUint) -> Point {
+    let f = &*F;
+    let r = f.neg(&f.mul(t, t));
+    let u0 = f.mul(&D, &f.sub(&r, &ONE));
+    let u1 = f.mul(&f.add(&u0, &ONE), &f.sub(&u0, &r));
+    let (was_square, v) = sqrt_ratio_m1(&ONE_MINUS_TWO_D, &f.mul(&f.add(&r, &ONE), &u1));
+    let (v_prime, sgn) = if was_square {
+        (v, ONE.clone())
+    } else {
+        (f.mul(t, &v), f.neg(&ONE))
+    };
+    let s = f.mul(&v_prime, &f.add(&r, &ONE));
+    let ss = f.mul(&s, &s);
+    let s_abs = abs(f, &s);
+    let w0 = f.add(&s_abs, &s_abs);
+    let w1 = f.add(&ss, &ONE);
+    let w2 = f.sub(&ss, &ONE);
+    let w3 = f.mul(
+        &f.mul(&v_prime, &s),
+        &f.mul(&f.sub(&r, &ONE), &ONE_MINUS_TWO_D),
+    );
+    let w3 = f.add(&w3, &sgn);
+    let p = Point {
+        x: f.mul(&w0, &w3),
+        y: f.mul(&w2, &w1),
+        z: f.mul(&w1, &w3),
+        t: f.mul(&w0, &w2),
+    };
+    debug_assert!(CURVE.is_on_curve(&p));
+    p
+}
+
+/// Derives an element from 112 uniformly random bytes, as in Section 5.3.4
+/// of RFC 9496. Each half is reduced into a field element and mapped with
+/// MAP; the output is their sum.
+pub(super) fn from_uniform_bytes(b: &[u8]) -> Point {
+    let t: Vec<BigUint> = b
+        .chunks(ENCODED_LEN)
+        .map(|half| BigUint::from_bytes_le(half) % F.modulus())
+        .collect();
+    CURVE.add(&map(&t[0]), &map(&t[1]))
+}
@@ -0,0 +1,104 @@
+use num_bigint::BigUint;
+
+use crate::gf::Gf;
+
+/// Point is a point (X:Y:Z:T) of a [`Curve`] in extended coordinates, with
+/// x = X/Z, y = Y/Z and x*y = T/Z. It is `pub` only so that the sealed
+/// trait of the groups can name it; this module is private.
+#[derive(Clone)]
+pub struct Point {
+    pub(super) x: BigUint,
+    pub(super) y: BigUint,
+    pub(super) z: BigUint,
+    pub(super) t: BigUint,
+}
+
+/// Curve is the twisted Edwards curve a*x^2 + y^2 = 1 + d*x^2*y^2 over the
+/// field `f`. Both edwards25519 (a = -1) and edwards448 (a = 1) have a square
+/// and d non-square, so the addition law is complete.
+pub struct Curve {
+    pub(super) f: &'static Gf,
+    pub(super) a: BigUint,
+    pub(super) d: BigUint,
+}
+
+impl Curve {
+    pub(super) fn identity(&self) -> Point {
+        let (zero, one) = (BigUint::default(), BigUint::from(1u32));
+        Point {
+            x: zero.clone(),
+            y: one.clone(),
+            z: one,
+            t: zero,
+        }
+    }
+    /// Adds points with the unified formula add-2008-hwcd of Hisil et al.
+    pub(super) fn add(&self, p: &Point, q: &Point) -> Point {
+        let f = self.f;
+        let a = f.mul(&p.x, &q.x);
+        let b = f.mul(&p.y, &q.y);
+        let c = f.mul(&self.d, &f.mul(&p.t, &q.t));
+        let d = f.mul(&p.z, &q.z);
+        let e = f.mul(&f.add(&p.x, &p.y), &f.add(&q.x, &q.y));
+        let e = f.sub(&f.sub(&e, &a), &b);
+        let ff = f.sub(&d, &c);
+        let g = f.add(&d, &c);
+        let h = f.sub(&b, &f.mul(&self.a, &a));
+        Point {
+            x: f.mul(&e, &ff),
+            y: f.mul(&g, &h),
+            z: f.mul(&ff, &g),
+            t: f.mul(&e, &h),
+        }
+    }
+    pub(super) fn neg(&self, p: &Point) -> Point {
+        Point {
+            x: self.f.neg(&p.x),
+            y: p.y.clone(),
+            z: p.z.clone(),
+            t: self.f.neg(&p.t),
+        }
+    }
+    /// Computes k*P with a left-to-right double-and-add.
+    pub(super) fn mul(&self, p: &Point, k: &BigUint) -> Point {
+        let mut q = self.identity();
+        for i in (0..k.bits()).rev() {
+            q = self.add(&q, &q);
+            if k.bit(i) {
+                q = self.add(&q, p);
+            }
+        }
+        q
+    }
+    pub(super) fn is_on_curve(&self, p: &Point) -> bool {
+        let f = self.f;
+        let (x2, y2, z2) = (f.mul(&p.x, &p.x), f.mul(&p.y, &p.y), f.mul(&p.z, &p.z));
+        let lhs = f.mul(&f.add(&f.mul(&self.a, &x2), &y2), &z2);
+        let rhs = f.add(&f.mul(&z2, &z2), &f.mul(&self.d, &f.mul(&x2, &y2)));
+        lhs == rhs && f.mul(&p.x, &p.y) == f.mul(&p.z, &p.t)
+    }
+}
+
+/// Implements CT_ABS of RFC 9496, which returns the non-negative one of x and
+/// -x. As for IS_NEGATIVE, an element is negative if it is odd, which is sgn0
+/// of the draft.
+pub(super) fn abs(f: &Gf, x: &BigUint) -> BigUint {
+    if f.sgn0(x) {
+        f.neg(x)
+    } else {
+        x.clone()
+    }
+}
+
+/// Interprets `b` as a little-endian integer, which must be lower than p.
+pub(super) fn from_canonical_bytes_le(f: &Gf, b: &[u8]) -> Option<BigUint> {
+    let x = BigUint::from_bytes_le(b);
+    (x < *f.modulus()).then_some(x)
+}
+
+/// Outputs a field element as a little-endian integer of `len` bytes.
+pub(super) fn to_bytes_le(x: &BigUint, len: usize) -> Vec<u8> {
+    let mut b = x.to_bytes_le();
+    b.resize(len, 0);
+    b
+}
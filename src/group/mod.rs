@@ -0,0 +1,350 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Mul, Neg};
+
+use num_bigint::BigUint;
+
+use crate::api::{ExpID, HashID, MapID, Suite, XofID};
+use crate::error::Error;
+use crate::expander::{get_expander, Expander, ExpanderStream};
+use crate::register_in_map;
+
+mod decaf448;
+mod edwards;
+mod ristretto255;
+
+use crate::group::edwards::{Curve, Point};
+
+/// PrimeOrderGroup is implemented by the prime-order groups of RFC 9496,
+/// which are built from the points of an Edwards curve that has a cofactor.
+/// Each group has its own [`Element`] type, so that elements of different
+/// groups cannot be mixed.
+pub trait PrimeOrderGroup:
+    sealed::Sealed + Copy + Debug + Display + PartialEq + Eq + Send + Sync + 'static
+{
+    /// Returns the length in bytes of encoded elements.
+    fn encoded_len(&self) -> usize;
+    /// Returns the number of uniformly random bytes taken by
+    /// `element_from_uniform_bytes`.
+    fn uniform_bytes_len(&self) -> usize {
+        2 * self.encoded_len()
+    }
+    /// Returns the order of the group.
+    fn get_order(&self) -> BigUint;
+    fn identity(&self) -> Element<Self> {
+        self.element(self.curve().identity())
+    }
+    fn generator(&self) -> Element<Self> {
+        self.element(self.generator_point())
+    }
+    /// Decodes an element from its canonical encoding, failing for any other
+    /// byte string.
+    fn decode(&self, b: &[u8]) -> Result<Element<Self>, Error> {
+        match self.decode_point(b) {
+            Some(p) => Ok(self.element(p)),
+            None => Err(Error::GroupElement(hex::encode(b))),
+        }
+    }
+    /// Derives an element from uniformly random bytes with the one-way map of
+    /// RFC 9496. Fails if `b` is not `uniform_bytes_len` bytes long.
+    fn element_from_uniform_bytes(&self, b: &[u8]) -> Result<Element<Self>, Error> {
+        if b.len() != self.uniform_bytes_len() {
+            return Err(Error::InvalidLength {
+                expected: self.uniform_bytes_len(),
+                got: b.len(),
+            });
+        }
+        Ok(self.element(self.map_uniform_bytes(b)))
+    }
+}
+
+mod sealed {
+    use crate::api::MapID;
+    use crate::group::edwards::{Curve, Point};
+    use crate::group::Element;
+
+    /// Sealed holds the arithmetic of the groups on the points that represent
+    /// their elements, which is private to this module.
+    pub trait Sealed: Sized {
+        fn curve(&self) -> &'static Curve;
+        fn generator_point(&self) -> Point;
+        fn decode_point(&self, b: &[u8]) -> Option<Point>;
+        fn encode_point(&self, p: &Point) -> Vec<u8>;
+        fn equal(&self, p: &Point, q: &Point) -> bool;
+        fn map_uniform_bytes(&self, b: &[u8]) -> Point;
+        /// Returns true if `map` is the one-way map of the group.
+        fn is_map(&self, map: MapID) -> bool;
+        fn element(&self, p: Point) -> Element<Self>;
+    }
+}
+
+/// Ristretto255 is the ristretto255 group, built from edwards25519.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ristretto255;
+
+/// Decaf448 is the decaf448 group, built from edwards448.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Decaf448;
+
+macro_rules! impl_group {
+    ($group:ident, $module:ident, $map:path, $name:expr) => {
+        impl sealed::Sealed for $group {
+            fn curve(&self) -> &'static Curve {
+                &$module::CURVE
+            }
+            fn generator_point(&self) -> Point {
+                $module::generator()
+            }
+            fn decode_point(&self, b: &[u8]) -> Option<Point> {
+                $module::decode(b)
+            }
+            fn encode_point(&self, p: &Point) -> Vec<u8> {
+                $module::encode(p)
+            }
+            fn equal(&self, p: &Point, q: &Point) -> bool {
+                $module::equal(p, q)
+            }
+            fn map_uniform_bytes(&self, b: &[u8]) -> Point {
+                $module::from_uniform_bytes(b)
+            }
+            fn is_map(&self, map: MapID) -> bool {
+                matches!(map, $map)
+            }
+            fn element(&self, p: Point) -> Element<Self> {
+                Element { group: *self, p }
+            }
+        }
+
+        impl std::fmt::Display for $group {
+            fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, $name)
+            }
+        }
+    };
+}
+
+impl_group!(Ristretto255, ristretto255, MapID::R255MAP, "ristretto255");
+impl_group!(Decaf448, decaf448, MapID::D448MAP, "decaf448");
+
+impl PrimeOrderGroup for Ristretto255 {
+    fn encoded_len(&self) -> usize {
+        ristretto255::ENCODED_LEN
+    }
+    fn get_order(&self) -> BigUint {
+        (BigUint::from(1u32) << 252u32)
+            + BigUint::parse_bytes(b"27742317777372353535851937790883648493", 10).unwrap()
+    }
+}
+
+impl PrimeOrderGroup for Decaf448 {
+    fn encoded_len(&self) -> usize {
+        decaf448::ENCODED_LEN
+    }
+    fn get_order(&self) -> BigUint {
+        (BigUint::from(1u32) << 446u32)
+            - BigUint::parse_bytes(
+                b"13818066809895115352007386748515426880336692474882178609894547503885",
+                10,
+            )
+            .unwrap()
+    }
+}
+
+/// Element is an element of the group `G`. It is represented by one of the
+/// points of the curve in its class, so elements must be compared with `==`
+/// or through their encodings.
+#[derive(Clone)]
+pub struct Element<G> {
+    group: G,
+    p: Point,
+}
+
+impl<G: PrimeOrderGroup> Element<G> {
+    #[inline]
+    pub fn get_group(&self) -> G {
+        self.group
+    }
+    /// Outputs the canonical encoding of the element.
+    pub fn encode(&self) -> Vec<u8> {
+        self.group.encode_point(&self.p)
+    }
+}
+
+impl<G: PrimeOrderGroup> PartialEq for Element<G> {
+    fn eq(&self, other: &Element<G>) -> bool {
+        self.group.equal(&self.p, &other.p)
+    }
+}
+
+impl<G: PrimeOrderGroup> Eq for Element<G> {}
+
+impl<G: PrimeOrderGroup> Add<&Element<G>> for &Element<G> {
+    type Output = Element<G>;
+    fn add(self, other: &Element<G>) -> Element<G> {
+        self.group
+            .element(self.group.curve().add(&self.p, &other.p))
+    }
+}
+
+impl<G: PrimeOrderGroup> Add for Element<G> {
+    type Output = Element<G>;
+    fn add(self, other: Element<G>) -> Element<G> {
+        &self + &other
+    }
+}
+
+impl<G: PrimeOrderGroup> Neg for Element<G> {
+    type Output = Element<G>;
+    fn neg(self) -> Element<G> {
+        self.group.element(self.group.curve().neg(&self.p))
+    }
+}
+
+impl<G: PrimeOrderGroup> Mul<&BigUint> for Element<G> {
+    type Output = Element<G>;
+    fn mul(self, k: &BigUint) -> Element<G> {
+        self.group.element(self.group.curve().mul(&self.p, k))
+    }
+}
+
+impl<G: PrimeOrderGroup> Display for Element<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", hex::encode(self.encode()))
+    }
+}
+
+impl<G: PrimeOrderGroup> Debug for Element<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}({})", self.group, self)
+    }
+}
+
+/// HashToGroup hashes arbitrary strings to elements of a prime-order group,
+/// as [`HashToCurve`](crate::HashToCurve) does to points of a curve. It
+/// implements hash_to_ristretto255 and hash_to_decaf448 of Appendix B of the
+/// draft: expand_message outputs 2*L uniform bytes, which are given to the
+/// one-way map of the group.
+pub trait HashToGroup: Send + Sync {
+    type G: PrimeOrderGroup;
+    fn get_group(&self) -> Self::G;
+    fn is_random_oracle(&self) -> bool;
+    /// Returns a stream that absorbs the message to be hashed.
+    fn stream(&self) -> Box<dyn ExpanderStream + '_>;
+    /// Outputs an element from the message absorbed by `s`.
+    fn try_hash_stream(&self, s: Box<dyn ExpanderStream + '_>) -> Result<Element<Self::G>, Error>;
+    fn try_hash(&self, msg: &[u8]) -> Result<Element<Self::G>, Error> {
+        let mut s = self.stream();
+        s.update(msg);
+        self.try_hash_stream(s)
+    }
+    fn hash(&self, msg: &[u8]) -> Element<Self::G> {
+        self.try_hash(msg).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Hashes every message of `msgs` as `try_hash` does, one after the
+    /// other, as elements need no inversion that could be shared across the
+    /// batch.
+    fn try_hash_batch(&self, msgs: &[&[u8]]) -> Result<Vec<Element<Self::G>>, Error> {
+        msgs.iter().map(|msg| self.try_hash(msg)).collect()
+    }
+    fn hash_batch(&self, msgs: &[&[u8]]) -> Vec<Element<Self::G>> {
+        self.try_hash_batch(msgs).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// GetHashToGroup returns the hashers of the suites of a prime-order group,
+/// as [`GetHashToCurve`](crate::GetHashToCurve) does for curves.
+pub trait GetHashToGroup {
+    type G: PrimeOrderGroup;
+    fn try_get(&self, dst: &[u8]) -> Result<Box<dyn HashToGroup<G = Self::G>>, Error>;
+    fn get(&self, dst: &[u8]) -> Box<dyn HashToGroup<G = Self::G>> {
+        self.try_get(dst).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Hashes the messages of `msgs` in parallel, outputting the elements in
+    /// the same order as `msgs`.
+    #[cfg(feature = "rayon")]
+    fn try_par_hash(&self, dst: &[u8], msgs: &[&[u8]]) -> Result<Vec<Element<Self::G>>, Error> {
+        use rayon::prelude::*;
+        let h = self.try_get(dst)?;
+        msgs.par_iter().map(|msg| h.try_hash(msg)).collect()
+    }
+    #[cfg(feature = "rayon")]
+    fn par_hash(&self, dst: &[u8], msgs: &[&[u8]]) -> Vec<Element<Self::G>> {
+        self.try_par_hash(dst, msgs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+/// GroupEncoding is the hasher of the suites of a prime-order group.
+struct GroupEncoding<G> {
+    group: G,
+    exp: Box<dyn Expander>,
+    l: usize,
+    ro: bool,
+}
+
+impl<G: PrimeOrderGroup> HashToGroup for GroupEncoding<G> {
+    type G = G;
+    fn get_group(&self) -> G {
+        self.group
+    }
+    fn is_random_oracle(&self) -> bool {
+        self.ro
+    }
+    fn stream(&self) -> Box<dyn ExpanderStream + '_> {
+        self.exp.stream()
+    }
+    fn try_hash_stream(&self, s: Box<dyn ExpanderStream + '_>) -> Result<Element<G>, Error> {
+        let uniform_bytes = s.try_finalize(2 * self.l)?;
+        self.group.element_from_uniform_bytes(&uniform_bytes)
+    }
+}
+
+impl<G: PrimeOrderGroup> GetHashToGroup for Suite<G> {
+    type G = G;
+    fn try_get(&self, dst: &[u8]) -> Result<Box<dyn HashToGroup<G = G>>, Error> {
+        if self.ct {
+            // The arithmetic of this module is not constant time.
+            return Err(Error::UnsupportedConstantTime(self.name.to_string()));
+        }
+        if !self.curve.is_map(self.map) {
+            return Err(Error::UnsupportedMap(self.name.to_string()));
+        }
+        let exp = get_expander(self.exp, dst, self.k);
+        Ok(Box::new(GroupEncoding {
+            group: self.curve,
+            exp,
+            l: self.l,
+            ro: self.ro,
+        }))
+    }
+}
+
+lazy_static! {
+    pub static ref SUITES_RISTRETTO255: HashMap<String, Suite<Ristretto255>> =
+        register_in_map!([RISTRETTO255_XMDSHA512_R255MAP_RO_]);
+    pub static ref SUITES_DECAF448: HashMap<String, Suite<Decaf448>> =
+        register_in_map!([DECAF448_XOFSHAKE256_D448MAP_RO_]);
+}
+
+pub static RISTRETTO255_XMDSHA512_R255MAP_RO_: Suite<Ristretto255> = Suite {
+    name: Cow::Borrowed("ristretto255_XMD:SHA-512_R255MAP_RO_"),
+    curve: Ristretto255,
+    map: MapID::R255MAP,
+    exp: ExpID::XMD(HashID::SHA512),
+    k: 128,
+    l: 32,
+    ro: true,
+    ct: false,
+};
+
+pub static DECAF448_XOFSHAKE256_D448MAP_RO_: Suite<Decaf448> = Suite {
+    name: Cow::Borrowed("decaf448_XOF:SHAKE256_D448MAP_RO_"),
+    curve: Decaf448,
+    map: MapID::D448MAP,
+    exp: ExpID::XOF(XofID::SHAKE256),
+    k: 224,
+    l: 56,
+    ro: true,
+    ct: false,
+};
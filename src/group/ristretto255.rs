@@ -0,0 +1,180 @@
+use num_bigint::{BigInt, BigUint};
+
+use crate::gf::Gf;
+use crate::group::edwards::{abs, from_canonical_bytes_le, to_bytes_le, Curve, Point};
+
+/// Length in bytes of an encoded element.
+pub(super) const ENCODED_LEN: usize = 32;
+
+lazy_static! {
+    static ref F: Gf = Gf::new(&((BigInt::from(1) << 255u32) - 19));
+    static ref D: BigUint = F.mul(
+        &F.elt(&BigInt::from(-121665)),
+        &F.inv(&BigUint::from(121666u32))
+    );
+    pub(super) static ref CURVE: Curve = Curve {
+        f: &F,
+        a: F.elt(&BigInt::from(-1)),
+        d: D.clone(),
+    };
+    static ref ONE: BigUint = BigUint::from(1u32);
+    static ref SQRT_M1: BigUint = F.pow(&BigUint::from(2u32), &((F.modulus() - 1u32) >> 2u32));
+    // This is the negative square root of a*d - 1, as fixed by the RFC.
+    static ref SQRT_AD_MINUS_ONE: BigUint = BigUint::parse_bytes(
+        b"25063068953384623474111414158702152701244531502492656460079210482610430750235",
+        10
+    )
+    .unwrap();
+    static ref INVSQRT_A_MINUS_D: BigUint = sqrt_ratio_m1(&ONE, &F.sub(&F.neg(&ONE), &D)).1;
+    static ref ONE_MINUS_D_SQ: BigUint = F.sub(&ONE, &F.mul(&D, &D));
+    static ref D_MINUS_ONE_SQ: BigUint = F.pow(&F.sub(&D, &ONE), &BigUint::from(2u32));
+}
+
+/// Returns the base point of edwards25519, which generates the group.
+pub(super) fn generator() -> Point {
+    let x = BigUint::parse_bytes(
+        b"15112221349535400772501151409588531511454012693041857206046113283949847762202",
+        10,
+    )
+    .unwrap();
+    let y = BigUint::parse_bytes(
+        b"46316835694926478169428394003475163141307993866256225615783033603165251855960",
+        10,
+    )
+    .unwrap();
+    Point {
+        t: F.mul(&x, &y),
+        x,
+        y,
+        z: ONE.clone(),
+    }
+}
+
+/// Implements SQRT_RATIO_M1 of Section 4.2 of RFC 9496. It outputs whether
+/// u/v is square, and the non-negative square root of either u/v or
+/// SQRT_M1*u/v.
+fn sqrt_ratio_m1(u: &BigUint, v: &BigUint) -> (bool, BigUint) {
+    let f = &*F;
+    let v3 = f.mul(&f.mul(v, v), v);
+    let v7 = f.mul(&f.mul(&v3, &v3), v);
+    let e = (f.modulus() - 5u32) >> 3u32;
+    let r = f.mul(&f.mul(u, &v3), &f.pow(&f.mul(u, &v7), &e));
+    let check = f.mul(v, &f.mul(&r, &r));
+    let correct_sign_sqrt = check == *u;
+    let flipped_sign_sqrt = check == f.neg(u);
+    let flipped_sign_sqrt_i = check == f.mul(&f.neg(u), &SQRT_M1);
+    let r = if flipped_sign_sqrt || flipped_sign_sqrt_i {
+        f.mul(&SQRT_M1, &r)
+    } else {
+        r
+    };
+    (correct_sign_sqrt || flipped_sign_sqrt, abs(f, &r))
+}
+
+/// Implements the decoding of Section 4.3.1 of RFC 9496.
+pub(super) fn decode(b: &[u8]) -> Option<Point> {
+    let f = &*F;
+    if b.len() != ENCODED_LEN {
+        return None;
+    }
+    let s = from_canonical_bytes_le(f, b)?;
+    if f.sgn0(&s) {
+        return None;
+    }
+    let ss = f.mul(&s, &s);
+    let u1 = f.sub(&ONE, &ss);
+    let u2 = f.add(&ONE, &ss);
+    let u2_sqr = f.mul(&u2, &u2);
+    let v = f.sub(&f.neg(&f.mul(&D, &f.mul(&u1, &u1))), &u2_sqr);
+    let (was_square, invsqrt) = sqrt_ratio_m1(&ONE, &f.mul(&v, &u2_sqr));
+    let den_x = f.mul(&invsqrt, &u2);
+    let den_y = f.mul(&f.mul(&invsqrt, &den_x), &v);
+    let x = abs(f, &f.mul(&f.add(&s, &s), &den_x));
+    let y = f.mul(&u1, &den_y);
+    let t = f.mul(&x, &y);
+    if !was_square || f.sgn0(&t) || f.is_zero(&y) {
+        return None;
+    }
+    Some(Point {
+        x,
+        y,
+        z: ONE.clone(),
+        t,
+    })
+}
+
+/// Implements the encoding of Section 4.3.2 of RFC 9496.
+pub(super) fn encode(p: &Point) -> Vec<u8> {
+    let f = &*F;
+    let u1 = f.mul(&f.add(&p.z, &p.y), &f.sub(&p.z, &p.y));
+    let u2 = f.mul(&p.x, &p.y);
+    let (_, invsqrt) = sqrt_ratio_m1(&ONE, &f.mul(&u1, &f.mul(&u2, &u2)));
+    let den1 = f.mul(&invsqrt, &u1);
+    let den2 = f.mul(&invsqrt, &u2);
+    let z_inv = f.mul(&f.mul(&den1, &den2), &p.t);
+    let rotate = f.sgn0(&f.mul(&p.t, &z_inv));
+    let (x, y, den_inv) = if rotate {
+        let ix0 = f.mul(&p.x, &SQRT_M1);
+        let iy0 = f.mul(&p.y, &SQRT_M1);
+        (iy0, ix0, f.mul(&den1, &INVSQRT_A_MINUS_D))
+    } else {
+        (p.x.clone(), p.y.clone(), den2)
+    };
+    let y = if f.sgn0(&f.mul(&x, &z_inv)) {
+        f.neg(&y)
+    } else {
+        y
+    };
+    let s = abs(f, &f.mul(&den_inv, &f.sub(&p.z, &y)));
+    to_bytes_le(&s, ENCODED_LEN)
+}
+
+/// Implements the equality check of Section 4.3.3 of RFC 9496.
+pub(super) fn equal(p: &Point, q: &Point) -> bool {
+    let f = &*F;
+    f.mul(&p.x, &q.y) == f.mul(&p.y, &q.x) || f.mul(&p.y, &q.y) == f.mul(&p.x, &q.x)
+}
+
+/// Implements the one-way map MAP of Section 4.3.4 of RFC 9496.
+fn map(t: &BigUint) -> Point {
+    let f = &*F;
+    let r = f.mul(&SQRT_M1, &f.mul(t, t));
+    let u = f.mul(&f.add(&r, &ONE), &ONE_MINUS_D_SQ);
+    let v = f.mul(&f.sub(&f.neg(&ONE), &f.mul(&r, &D)), &f.add(&r, &D));
+    let (was_square, s) = sqrt_ratio_m1(&u, &v);
+    let s_prime = f.neg(&abs(f, &f.mul(&s, t)));
+    let (s, c) = if was_square {
+        (s, f.neg(&ONE))
+    } else {
+        (s_prime, r.clone())
+    };
+    let n = f.sub(&f.mul(&f.mul(&c, &f.sub(&r, &ONE)), &D_MINUS_ONE_SQ), &v);
+    let w0 = f.mul(&f.add(&s, &s), &v);
+    let w1 = f.mul(&n, &SQRT_AD_MINUS_ONE);
+    let ss = f.mul(&s, &s);
+    let w2 = f.sub(&ONE, &ss);
+    let w3 = f.add(&ONE, &ss);
+    let p = Point {
+        x: f.mul(&w0, &w3),
+        y: f.mul(&w2, &w1),
+        z: f.mul(&w1, &w3),
+        t: f.mul(&w0, &w2),
+    };
+    debug_assert!(CURVE.is_on_curve(&p));
+    p
+}
+
+/// Derives an element from 64 uniformly random bytes, as in Section 4.3.4
+/// of RFC 9496. Each half is reduced into a field element, after clearing its
+/// most significant bit, and mapped with MAP; the output is their sum.
+pub(super) fn from_uniform_bytes(b: &[u8]) -> Point {
+    let t: Vec<BigUint> = b
+        .chunks(ENCODED_LEN)
+        .map(|half| {
+            let mut half = half.to_vec();
+            half[ENCODED_LEN - 1] &= 0x7f;
+            BigUint::from_bytes_le(&half) % F.modulus()
+        })
+        .collect();
+    CURVE.add(&map(&t[0]), &map(&t[1]))
+}
@@ -0,0 +1,434 @@
+use num_bigint::BigUint;
+
+use crate::api::InverseMap;
+use crate::curve::edwards::{Curve as EdCurve, Point as EdPoint};
+use crate::curve::montgomery::{Curve as MtCurve, Point as MtPoint};
+use crate::curve::weierstrass::{Curve as WeCurve, Point as WePoint};
+use crate::curve::{EcPoint, EllipticCurve};
+use crate::error::Error;
+use crate::field::{parse_int, Field};
+use crate::fp::{Fp, FpElt};
+use crate::gf::Gf;
+use crate::isogeny::IsogenyMap;
+
+/// Returns the affine coordinates of a point as integers, or None for the
+/// identity.
+fn affine<P: EcPoint<Elt = FpElt>>(p: &P) -> Option<(BigUint, BigUint)> {
+    if p.is_identity() {
+        return None;
+    }
+    let (x, y) = p.to_affine()?;
+    Some((x.value().clone(), y.value().clone()))
+}
+
+/// Sswu holds the parameters of the Simplified SWU map of Section 6.6.2 of the
+/// draft on the curve y^2 = x^3 + A * x + B.
+struct Sswu {
+    f: &'static Gf,
+    a: BigUint,
+    b: BigUint,
+    z: BigUint,
+}
+
+impl Sswu {
+    fn new(f: Fp, a: &FpElt, b: &FpElt, z: &FpElt) -> Self {
+        Sswu {
+            f: f.gf(),
+            a: a.value().clone(),
+            b: b.value().clone(),
+            z: z.value().clone(),
+        }
+    }
+    /// The x-coordinate of a point is either x1 or x2 = Z * u^2 * x1, and in
+    /// both cases Z * u^2 is a root of a quadratic polynomial, so there are
+    /// four branches. The sign of u is that of y.
+    fn preimage(&self, x: &BigUint, y: &BigUint, branch: usize) -> Option<BigUint> {
+        let f = self.f;
+        let ax_b = f.add(&f.mul(&self.a, x), &self.b);
+        // w = Z * u^2 is a root of c2 * w^2 + c1 * w + c0.
+        let (c2, c1, c0) = if branch & 2 == 0 {
+            // x = x1 = (-B / A) * (1 + 1 / (w^2 + w)).
+            (ax_b.clone(), ax_b, self.b.clone())
+        } else {
+            // x = x2 = w * x1.
+            (self.b.clone(), ax_b.clone(), ax_b)
+        };
+        if f.is_zero(&c2) {
+            return None;
+        }
+        let disc = f.sub(
+            &f.mul(&c1, &c1),
+            &f.mul(&BigUint::from(4u32), &f.mul(&c2, &c0)),
+        );
+        let root = f.sqrt(&disc)?;
+        let root = if branch & 1 == 0 { root } else { f.neg(&root) };
+        let w = f.mul(
+            &f.sub(&root, &c1),
+            &f.inv(&f.mul(&BigUint::from(2u32), &c2)),
+        );
+        let u = f.sqrt(&f.mul(&w, &f.inv(&self.z)))?;
+        Some(if f.sgn0(&u) == f.sgn0(y) {
+            u
+        } else {
+            f.neg(&u)
+        })
+    }
+}
+
+/// SSWUInverse inverts the Simplified SWU map on a curve with A and B
+/// non-zero. It has the four branches of [`Sswu::preimage`].
+pub(crate) struct SSWUInverse {
+    curve: WeCurve,
+    sswu: Sswu,
+}
+
+impl SSWUInverse {
+    pub(crate) fn new(curve: WeCurve, z: i32) -> Self {
+        let f = curve.get_field();
+        let sswu = Sswu::new(f, curve.a(), curve.b(), &f.from_i32(z));
+        SSWUInverse { curve, sswu }
+    }
+}
+
+impl InverseMap for SSWUInverse {
+    type E = WeCurve;
+    fn branches(&self) -> usize {
+        4
+    }
+    fn preimage(&self, p: &WePoint, branch: usize) -> Option<FpElt> {
+        let (x, y) = affine(p)?;
+        let u = self.sswu.preimage(&x, &y, branch)?;
+        Some(self.curve.get_field().elt(&u))
+    }
+}
+
+/// SSWUAB0Inverse inverts the Simplified SWU map for AB == 0 of Section 6.6.3
+/// of the draft, which maps to an isogenous curve E' and then applies an
+/// isogeny of degree d. The x-coordinates of the preimages of a point by the
+/// isogeny are the roots of x_num - x * x_den, of which there are at most d.
+/// The branch selects one of them, taken in increasing order, and one of the
+/// four branches of the Simplified SWU map on E', so there are 4 * d branches.
+pub(crate) struct SSWUAB0Inverse {
+    curve: WeCurve,
+    sswu: Sswu,
+    x_num: Vec<BigUint>,
+    x_den: Vec<BigUint>,
+    y_num: Vec<BigUint>,
+    y_den: Vec<BigUint>,
+}
+
+impl SSWUAB0Inverse {
+    pub(crate) fn new(curve: WeCurve, iso: &IsogenyMap, z: i32) -> Self {
+        let f = curve.get_field();
+        let poly = |c: &[&str]| c.iter().map(|s| parse_int(s)).collect();
+        let sswu = Sswu::new(f, &f.from(iso.a), &f.from(iso.b), &f.from_i32(z));
+        SSWUAB0Inverse {
+            curve,
+            sswu,
+            x_num: poly(iso.x_num),
+            x_den: poly(iso.x_den),
+            y_num: poly(iso.y_num),
+            y_den: poly(iso.y_den),
+        }
+    }
+}
+
+impl InverseMap for SSWUAB0Inverse {
+    type E = WeCurve;
+    fn branches(&self) -> usize {
+        4 * (self.x_num.len() - 1)
+    }
+    fn preimage(&self, p: &WePoint, branch: usize) -> Option<FpElt> {
+        let f = self.sswu.f;
+        let (x, y) = affine(p)?;
+        let mut g = self.x_num.clone();
+        for (gi, di) in g.iter_mut().zip(&self.x_den) {
+            *gi = f.sub(gi, &f.mul(&x, di));
+        }
+        let x0 = f.roots(&g).into_iter().nth(branch / 4)?;
+        let y_num = f.poly_eval(&self.y_num, &x0);
+        if f.is_zero(&y_num) {
+            return None;
+        }
+        let y0 = f.mul(&f.mul(&y, &f.poly_eval(&self.y_den, &x0)), &f.inv(&y_num));
+        let u = self.sswu.preimage(&x0, &y0, branch % 4)?;
+        Some(self.curve.get_field().elt(&u))
+    }
+}
+
+/// SVDWInverse inverts the Shallue-van de Woestijne map of Section 6.6.1 of
+/// the draft on the curve y^2 = g(x) = x^3 + A * x + B. With w = c1 * u^2,
+/// the map outputs x1 = c2 - t, x2 = c2 + t for t = c3 * u / (1 + w), or
+/// x3 = Z + c4 * ((1 + w) / (1 - w))^2. The first two cases are quadratic in
+/// u and the third one in sqrt((x3 - Z) / c4), so there are six branches.
+pub(crate) struct SVDWInverse {
+    curve: WeCurve,
+    f: &'static Gf,
+    z: BigUint,
+    c1: BigUint,
+    c2: BigUint,
+    c3: BigUint,
+    c4: BigUint,
+}
+
+impl SVDWInverse {
+    /// Fails if -g(Z) * (3 * Z^2 + 4 * A) is not square, as the map then
+    /// has no constant c3.
+    pub(crate) fn new(curve: WeCurve, z: i32) -> Result<Self, Error> {
+        let f = curve.get_field().gf();
+        let (a, b) = (curve.a().value(), curve.b().value());
+        let z = curve.get_field().from_i32(z).value().clone();
+        let z2 = f.mul(&z, &z);
+        let gz = f.add(&f.mul(&f.add(&z2, a), &z), b);
+        // h = 3 * Z^2 + 4 * A.
+        let h = f.add(
+            &f.mul(&BigUint::from(3u32), &z2),
+            &f.mul(&BigUint::from(4u32), a),
+        );
+        let c2 = f.neg(&f.mul(&z, &f.inv(&BigUint::from(2u32))));
+        let c3 = f.sqrt(&f.neg(&f.mul(&gz, &h))).ok_or_else(|| {
+            Error::InvalidSuite(String::from(
+                "SVDW requires -g(Z) * (3 * Z^2 + 4 * A) to be square",
+            ))
+        })?;
+        let c3 = if f.sgn0(&c3) { f.neg(&c3) } else { c3 };
+        let c4 = f.neg(&f.mul(&f.mul(&BigUint::from(4u32), &gz), &f.inv(&h)));
+        Ok(SVDWInverse {
+            curve,
+            z,
+            c1: gz,
+            c2,
+            c3,
+            c4,
+            f,
+        })
+    }
+}
+
+impl InverseMap for SVDWInverse {
+    type E = WeCurve;
+    fn branches(&self) -> usize {
+        6
+    }
+    fn preimage(&self, p: &WePoint, branch: usize) -> Option<FpElt> {
+        let f = self.f;
+        let (x, y) = affine(p)?;
+        let u = if branch < 4 {
+            let t = if branch < 2 {
+                f.sub(&self.c2, &x)
+            } else {
+                f.sub(&x, &self.c2)
+            };
+            if f.is_zero(&t) {
+                // u = 0 is the only root.
+                if branch & 1 == 1 {
+                    return None;
+                }
+                t
+            } else {
+                // u is a root of c1 * t * u^2 - c3 * u + t.
+                let a = f.mul(&self.c1, &t);
+                let disc = f.sub(
+                    &f.mul(&self.c3, &self.c3),
+                    &f.mul(&BigUint::from(4u32), &f.mul(&a, &t)),
+                );
+                let root = f.sqrt(&disc)?;
+                let root = if branch & 1 == 0 { root } else { f.neg(&root) };
+                f.mul(
+                    &f.add(&self.c3, &root),
+                    &f.inv(&f.mul(&BigUint::from(2u32), &a)),
+                )
+            }
+        } else {
+            let r = f.sqrt(&f.mul(&f.sub(&x, &self.z), &f.inv(&self.c4)))?;
+            let r = if branch & 1 == 0 { r } else { f.neg(&r) };
+            let one = BigUint::from(1u32);
+            // r = (1 + w) / (1 - w).
+            let w = f.mul(&f.sub(&r, &one), &f.inv(&f.add(&r, &one)));
+            let u = f.sqrt(&f.mul(&w, &f.inv(&self.c1)))?;
+            if f.sgn0(&u) == f.sgn0(&y) {
+                u
+            } else {
+                f.neg(&u)
+            }
+        };
+        // The sign of u is that of y.
+        if f.sgn0(&u) != f.sgn0(&y) {
+            return None;
+        }
+        Some(self.curve.get_field().elt(&u))
+    }
+}
+
+/// Ell2 holds the parameters of the Elligator 2 map of Section 6.7.1 of the
+/// draft on the curve K * t^2 = s^3 + J * s^2 + s, with K = 1.
+struct Ell2 {
+    f: &'static Gf,
+    j: BigUint,
+    z: BigUint,
+}
+
+impl Ell2 {
+    /// Fails unless the curve has K = 1, as all curves of the suites do.
+    fn new(curve: &MtCurve, z: i32) -> Result<Self, Error> {
+        let f = curve.get_field();
+        if *curve.b() != f.one() {
+            return Err(Error::InvalidSuite(String::from(
+                "the inverse of Elligator 2 requires K = 1",
+            )));
+        }
+        Ok(Ell2 {
+            f: f.gf(),
+            j: curve.a().value().clone(),
+            z: f.from_i32(z).value().clone(),
+        })
+    }
+    /// The sign of t tells whether s is x1 or x2 = -x1 - J of the map, and
+    /// the branch gives the sign of u, since u and -u have the same image.
+    fn preimage(&self, s: &BigUint, t: &BigUint, branch: usize) -> Option<BigUint> {
+        let f = self.f;
+        let x1 = if f.sgn0(t) {
+            s.clone()
+        } else {
+            f.neg(&f.add(s, &self.j))
+        };
+        if f.is_zero(&x1) {
+            return None;
+        }
+        // x1 = -J / (1 + Z * u^2).
+        let u2 = f.neg(&f.mul(&f.add(&x1, &self.j), &f.inv(&f.mul(&self.z, &x1))));
+        let u = f.sqrt(&u2)?;
+        Some(if f.sgn0(&u) == (branch == 1) {
+            u
+        } else {
+            f.neg(&u)
+        })
+    }
+}
+
+/// Ell2Inverse inverts the Elligator 2 map on Montgomery curves. There are two
+/// branches, one for each sign of u.
+pub(crate) struct Ell2Inverse {
+    curve: MtCurve,
+    ell2: Ell2,
+}
+
+impl Ell2Inverse {
+    pub(crate) fn new(curve: MtCurve, z: i32) -> Result<Self, Error> {
+        let ell2 = Ell2::new(&curve, z)?;
+        Ok(Ell2Inverse { curve, ell2 })
+    }
+}
+
+impl InverseMap for Ell2Inverse {
+    type E = MtCurve;
+    fn branches(&self) -> usize {
+        2
+    }
+    fn preimage(&self, p: &MtPoint, branch: usize) -> Option<FpElt> {
+        let (s, t) = affine(p)?;
+        let u = self.ell2.preimage(&s, &t, branch)?;
+        Some(self.curve.get_field().elt(&u))
+    }
+}
+
+/// Edwards25519Inverse inverts the Elligator 2 map on edwards25519, which
+/// sends points of curve25519 to edwards25519 with the rational map of
+/// Appendix D.1 of the draft.
+pub(crate) struct Edwards25519Inverse {
+    curve: EdCurve,
+    c1: BigUint,
+    ell2: Ell2,
+}
+
+impl Edwards25519Inverse {
+    /// Returns the inverse through the Montgomery curve `mt`, which is
+    /// curve25519. Fails if -(J + 2) is not square.
+    pub(crate) fn new(curve: EdCurve, mt: &MtCurve, z: i32) -> Result<Self, Error> {
+        let ell2 = Ell2::new(mt, z)?;
+        let f = ell2.f;
+        // c1 = sqrt(-(J + 2)), with sgn0(c1) = 0.
+        let c1 = f
+            .sqrt(&f.neg(&f.add(&ell2.j, &BigUint::from(2u32))))
+            .ok_or_else(|| {
+                Error::InvalidSuite(String::from("edwards25519 requires -(A + 2) to be square"))
+            })?;
+        let c1 = if f.sgn0(&c1) { f.neg(&c1) } else { c1 };
+        Ok(Edwards25519Inverse { curve, c1, ell2 })
+    }
+}
+
+impl InverseMap for Edwards25519Inverse {
+    type E = EdCurve;
+    fn branches(&self) -> usize {
+        2
+    }
+    fn preimage(&self, p: &EdPoint, branch: usize) -> Option<FpElt> {
+        let f = self.ell2.f;
+        let (x, y) = affine(p)?;
+        let one = BigUint::from(1u32);
+        if f.is_zero(&x) || f.is_zero(&f.sub(&one, &y)) {
+            return None;
+        }
+        // x = c1 * s / t and y = (s - 1) / (s + 1).
+        let s = f.mul(&f.add(&one, &y), &f.inv(&f.sub(&one, &y)));
+        let t = f.mul(&f.mul(&self.c1, &s), &f.inv(&x));
+        let u = self.ell2.preimage(&s, &t, branch)?;
+        Some(self.curve.get_field().elt(&u))
+    }
+}
+
+/// Edwards448Inverse inverts the Elligator 2 map on edwards448, which sends
+/// points (s, t) of curve448 to edwards448 with the 4-isogeny of RFC 7748:
+/// x = 4 * t * (s^2 - 1) / (s^4 - 2 * s^2 + 4 * t^2 + 1) and
+/// y = -(s^5 - 2 * s^3 - 4 * s * t^2 + s) / (s^5 - 2 * s^2 * t^2 - 2 * s^3 - 2 * t^2 + s).
+/// Replacing t^2 by s^3 + J * s^2 + s, the equation of y becomes a quartic
+/// in s. The branch selects one of its roots, taken in increasing order, and
+/// the sign of u, so there are eight branches.
+pub(crate) struct Edwards448Inverse {
+    curve: EdCurve,
+    ell2: Ell2,
+}
+
+impl Edwards448Inverse {
+    /// Returns the inverse through the Montgomery curve `mt`, which is
+    /// curve448.
+    pub(crate) fn new(curve: EdCurve, mt: &MtCurve, z: i32) -> Result<Self, Error> {
+        let ell2 = Ell2::new(mt, z)?;
+        Ok(Edwards448Inverse { curve, ell2 })
+    }
+}
+
+impl InverseMap for Edwards448Inverse {
+    type E = EdCurve;
+    fn branches(&self) -> usize {
+        8
+    }
+    fn preimage(&self, p: &EdPoint, branch: usize) -> Option<FpElt> {
+        let f = self.ell2.f;
+        let (x, y) = affine(p)?;
+        let int = |n: u32| BigUint::from(n);
+        let j = &self.ell2.j;
+        // (1 - y) * s^4 - (4 + 2 * J * y) * (s^3 + s)
+        //   - (2 + 4 * J + 6 * y) * s^2 + (1 - y) = 0.
+        let c0 = f.sub(&int(1), &y);
+        let c1 = f.neg(&f.add(&int(4), &f.mul(&f.mul(&int(2), j), &y)));
+        let c2 = f.neg(&f.add(&f.add(&int(2), &f.mul(&int(4), j)), &f.mul(&int(6), &y)));
+        let quartic = [c0.clone(), c1.clone(), c2, c1, c0];
+        let s = f.roots(&quartic).into_iter().nth(branch / 2)?;
+        let s2 = f.mul(&s, &s);
+        let den = f.mul(&int(4), &f.sub(&s2, &int(1)));
+        if f.is_zero(&den) {
+            return None;
+        }
+        // t = x * (s^4 - 2 * s^2 + 4 * t^2 + 1) / (4 * (s^2 - 1)).
+        let t2 = f.mul(&s, &f.add(&f.add(&s2, &f.mul(j, &s)), &int(1)));
+        let num = f.add(
+            &f.sub(&f.mul(&s2, &s2), &f.mul(&int(2), &s2)),
+            &f.add(&f.mul(&int(4), &t2), &int(1)),
+        );
+        let t = f.mul(&f.mul(&x, &num), &f.inv(&den));
+        let u = self.ell2.preimage(&s, &t, branch % 2)?;
+        Some(self.curve.get_field().elt(&u))
+    }
+}
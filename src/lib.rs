@@ -5,6 +5,30 @@
 //! This implementation is **not** protected against any kind of attack, including
 //! side-channel attacks. Do not use this code for securing any application.
 //!
+//! The suites of curves have a constant-time mode, selected with
+//! [`Suite::constant_time`], which is meant for prototypes that hash secret
+//! messages. Field elements are held in fixed-size limbs, hash_to_field
+//! reduces without branches, the maps follow the straight-line procedures of
+//! Appendix F of the draft, and cofactors are cleared with a fixed-window
+//! multiplication. Its running time does not depend on the message, as
+//! checked by `cargo bench --bench dudect -- --suite NAME`, but it has not
+//! been audited.
+//!
+//! The suites of ristretto255 and decaf448 have no constant-time mode: their
+//! groups rely on the arithmetic of num-bigint, which is not constant time.
+//! They fail with [`Error::UnsupportedConstantTime`] rather than silently
+//! leak.
+//!
+//! ```
+//!  use h2c_rust_ref::{GetHashToCurve, GetHashToGroup};
+//!  use h2c_rust_ref::{P256_XMDSHA256_SSWU_RO_, RISTRETTO255_XMDSHA512_R255MAP_RO_};
+//!  let dst = b"Domain separation tag";
+//!  let ct = P256_XMDSHA256_SSWU_RO_.constant_time(true);
+//!  let p = ct.get(dst).hash(b"secret message");
+//!  assert!(p == P256_XMDSHA256_SSWU_RO_.get(dst).hash(b"secret message"));
+//!  assert!(RISTRETTO255_XMDSHA512_R255MAP_RO_.constant_time(true).try_get(dst).is_err());
+//! ```
+//!
 //! # Hash to Curve
 //!
 //! ```
@@ -13,19 +37,236 @@
 //!  let msg = b"Message string";
 //!  let dst = b"Domain separation tag";
 //!
-//!  let suite = P256_XMDSHA256_SSWU_RO_;
+//!  let suite = &P256_XMDSHA256_SSWU_RO_;
 //!  let h = suite.get(dst);
 //!  let mut p = h.hash(msg);
 //!  p.normalize();
 //!  println!("enc: {} {}", suite, p);
 //!
-//!  let suite = EDWARDS25519_XMDSHA512_ELL2_RO_;
+//!  let suite = &EDWARDS25519_XMDSHA512_ELL2_RO_;
 //!  let h = suite.get(dst);
 //!  let mut p = h.hash(msg);
 //!  p.normalize();
 //!  println!("enc: {} {}", suite, p);
 //! ```
 //!
+//! A handle also exposes `hash_to_curve`, `encode_to_curve` and
+//! `map_to_curve` directly, so the `_RO_` and `_NU_` variants of a suite
+//! can be used interchangeably for either encoding.
+//!
+//! ```
+//!  use h2c_rust_ref::GetHashToCurve;
+//!  use h2c_rust_ref::{P256_XMDSHA256_SSWU_NU_, P256_XMDSHA256_SSWU_RO_};
+//!  let msg = b"Message string";
+//!  let dst = b"Domain separation tag";
+//!
+//!  let h = P256_XMDSHA256_SSWU_NU_.get(dst);
+//!  let p = h.hash_to_curve(msg);
+//!  let q = h.encode_to_curve(msg);
+//!  assert!(p == P256_XMDSHA256_SSWU_RO_.get(dst).hash(msg));
+//!  assert!(q == h.hash(msg));
+//! ```
+//!
+//! # Suite Registry
+//!
+//! Suites of any curve family can be looked up by their suite ID.
+//!
+//! ```
+//!  use h2c_rust_ref::SUITES;
+//!  let msg = b"Message string";
+//!  let dst = b"Domain separation tag";
+//!
+//!  let suite = SUITES.get("edwards25519_XMD:SHA-512_ELL2_RO_").unwrap();
+//!  let h = suite.get(dst);
+//!  let mut p = h.hash(msg);
+//!  p.normalize();
+//!  println!("enc: {} (k: {}, L: {}) {}", suite, suite.k(), suite.l(), p);
+//! ```
+//!
+//! # Custom Suites
+//!
+//! [`SuiteBuilder::build`] checks that the map applies to the curve, that its
+//! Z meets the criteria of Appendix H of the draft, and that the suite ID
+//! names the expander and ends with `_RO_` or `_NU_` as the encoding does.
+//!
+//! ```
+//!  use h2c_rust_ref::{find_z_sswu, ExpID, GetHashToCurve, HashID, MapID, Suite};
+//!  use h2c_rust_ref::P256_XMDSHA256_SSWU_RO_;
+//!  use num_bigint::BigInt;
+//!  let one = BigInt::from(1);
+//!  let p = (&one << 256) - (&one << 224) + (&one << 192) + (&one << 96) - 1;
+//!  let a = BigInt::from(-3);
+//!  let b = BigInt::parse_bytes(
+//!      b"5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+//!      16,
+//!  )
+//!  .unwrap();
+//!
+//!  let curve = *P256_XMDSHA256_SSWU_RO_.curve();
+//!  let map = MapID::SSWU(find_z_sswu(&p, &a, &b).unwrap());
+//!  let exp = ExpID::XMD(HashID::SHA256);
+//!  let suite = Suite::builder("P256_XMD:SHA-256_SSWU_RO_", curve, map, exp)
+//!      .random_oracle(true)
+//!      .build()
+//!      .unwrap();
+//!  let h = suite.get(b"Domain separation tag");
+//!  let p = h.hash(b"Message string");
+//! ```
+//!
+//! # Hash to Scalar
+//!
+//! ```
+//!  use h2c_rust_ref::P256_XMDSHA256_SSWU_RO_;
+//!  let msg = b"Message string";
+//!  let dst = b"Domain separation tag";
+//!
+//!  let h = P256_XMDSHA256_SSWU_RO_.get_scalar(dst);
+//!  let k = h.hash(msg, 1);
+//!  assert!(k[0] < *h.get_order());
+//! ```
+//!
+//! # Point Serialization
+//!
+//! ```
+//!  use h2c_rust_ref::{serialize_sec1, GetHashToCurve, P256_XMDSHA256_SSWU_RO_};
+//!  let h = P256_XMDSHA256_SSWU_RO_.get(b"Domain separation tag");
+//!  let p = h.hash(b"Message string");
+//!  let bytes = serialize_sec1(h.get_curve(), &p, true);
+//!  assert_eq!(bytes.len(), 33);
+//! ```
+//!
+//! # Clearing the Cofactor
+//!
+//! Hashers clear the cofactor with a fast method for their curve: a
+//! multiplication by the short h_eff = 1 - z for BLS12-381 G1, the
+//! endomorphism psi for BLS12-381 G2 (Appendix G.3 of the draft), doublings
+//! for curves whose cofactor is a power of two, and nothing for curves of
+//! prime order, such as the NIST curves and secp256k1. Other curves fall back
+//! to multiplying by the cofactor. The method is exposed by `clear_cofactor`.
+//!
+//! [`SigmaCofactor`] clears the cofactor of BLS12-381 G1 with the endomorphism
+//! sigma instead. It maps onto G1 too, but its points are not those of the
+//! draft, so the suites keep h_eff.
+//!
+//! ```
+//!  use h2c_rust_ref::{GetHashToCurve, BLS12381G1_XMDSHA256_SSWU_RO_};
+//!  let h = BLS12381G1_XMDSHA256_SSWU_RO_.get(b"Domain separation tag");
+//!  let trace = h.trace(b"Message string");
+//!  assert!(h.clear_cofactor(trace.r) == trace.p);
+//! ```
+//!
+//! # Inverse Maps
+//!
+//! The map of a suite can be inverted: given a point and a branch, the
+//! inverse map outputs a field element that map_to_curve sends to that point,
+//! if any. The Simplified SWU map of P-256, P-384 and P-521 has four
+//! branches, the SVDW map has six, and Elligator 2 on curve25519, curve448
+//! and edwards25519 has two. The isogenous maps are inverted through the
+//! isogeny: the SSWU map of secp256k1 has twelve branches, that of
+//! BLS12-381 G1 has 44, and the map of edwards448 has eight. The suites of
+//! BLS12-381 G2 have no inverse map.
+//!
+//! ```
+//!  use h2c_rust_ref::{GetHashToCurve, CURVE25519_XMDSHA512_ELL2_RO_};
+//!  let h = CURVE25519_XMDSHA512_ELL2_RO_.get(b"Domain separation tag");
+//!  let q = h.trace(b"Message string").q[0].clone();
+//!  let u = (0..h.inverse_branches()).find_map(|b| h.inverse_map(&q, b));
+//!  assert!(h.map_to_curve(&u.unwrap()) == q);
+//! ```
+//!
+//! # Elligator Squared
+//!
+//! Using the inverse map, the suites encode any point of the curve as a
+//! string of 2*L bytes that looks random, which hides points sent over the
+//! wire. The encoding is randomized, and decoding fails only on strings of
+//! the wrong length.
+//!
+//! ```
+//!  use h2c_rust_ref::{GetHashToCurve, P256_XMDSHA256_SSWU_RO_};
+//!  let h = P256_XMDSHA256_SSWU_RO_.get(b"Domain separation tag");
+//!  let p = h.hash(b"Message string");
+//!  let b = h.encode_uniform(&p, &mut rand::thread_rng());
+//!  assert!(h.decode_uniform(&b) == p);
+//! ```
+//!
+//! # ElligatorSwift
+//!
+//! BIP324 encodes secp256k1 public keys as 64 bytes that look random with
+//! ElligatorSwift, a variant of the SVDW map that covers all x-coordinates
+//! with a single pair (u, t). Keys are exchanged with an x-only ECDH.
+//!
+//! ```
+//!  use h2c_rust_ref::{ellswift_create, ellswift_xdh};
+//!  let mut rng = rand::thread_rng();
+//!  let (sk_a, a) = ellswift_create(&mut rng);
+//!  let (sk_b, b) = ellswift_create(&mut rng);
+//!  let s_a = ellswift_xdh(&a, &b, &sk_a, true).unwrap();
+//!  let s_b = ellswift_xdh(&b, &a, &sk_b, false).unwrap();
+//!  assert_eq!(s_a, s_b);
+//! ```
+//!
+//! # Batch Hashing
+//!
+//! `hash_batch` hashes a slice of messages. The inversions of map_to_curve
+//! (SSWU, SVDW and Elligator 2) and those of the final normalization are
+//! shared across the batch with Montgomery's trick.
+//!
+//! ```
+//!  use h2c_rust_ref::{GetHashToCurve, BLS12381G2_XMDSHA256_SSWU_RO_};
+//!  let h = BLS12381G2_XMDSHA256_SSWU_RO_.get(b"Domain separation tag");
+//!  let msgs: [&[u8]; 2] = [b"one", b"two"];
+//!  let p = h.hash_batch(&msgs);
+//!  assert!(p[1] == h.hash(b"two"));
+//! ```
+//!
+//! # Parallel Hashing
+//!
+//! With the `rayon` feature, a slice of messages can be hashed in parallel.
+//! The points are output in the same order as the messages.
+//!
+//! ```
+//!  # #[cfg(feature = "rayon")]
+//!  # {
+//!  use h2c_rust_ref::{GetHashToCurve, P256_XMDSHA256_SSWU_RO_};
+//!  let dst = b"Domain separation tag";
+//!  let msgs: [&[u8]; 3] = [b"one", b"two", b"three"];
+//!  let p = P256_XMDSHA256_SSWU_RO_.par_hash(dst, &msgs);
+//!  assert!(p[2] == P256_XMDSHA256_SSWU_RO_.get(dst).hash(b"three"));
+//!  # }
+//! ```
+//!
+//! # Prime-Order Groups
+//!
+//! The ristretto255 and decaf448 groups of RFC 9496 have their own suites,
+//! which output group elements instead of curve points. Each group has its
+//! own [`Element`] type, and its hashers implement [`HashToGroup`]. They are
+//! part of [`SUITES`] as [`AnySuite::Ristretto255`] and
+//! [`AnySuite::Decaf448`], but they cannot be traced, since they have no
+//! field elements.
+//!
+//! ```
+//!  use h2c_rust_ref::{GetHashToGroup, HashToGroup, PrimeOrderGroup};
+//!  use h2c_rust_ref::RISTRETTO255_XMDSHA512_R255MAP_RO_;
+//!  let h = RISTRETTO255_XMDSHA512_R255MAP_RO_.get(b"Domain separation tag");
+//!  let p = h.hash(b"Message string");
+//!  let q = h.get_group().decode(&p.encode()).unwrap();
+//!  assert!(p == q);
+//! ```
+//!
+//! # Expand Message
+//!
+//! ```
+//!  use h2c_rust_ref::{get_expander, ExpID, HashID};
+//!  let msg = b"Message string";
+//!  let dst = b"Domain separation tag";
+//!
+//!  let exp = get_expander(ExpID::XMD(HashID::SHA256), dst, 128);
+//!  let uniform_bytes = exp.expand(msg, 32);
+//!  let dst_prime = exp.construct_dst_prime();
+//!  assert_eq!(uniform_bytes.len(), 32);
+//!  assert_eq!(dst_prime.last(), Some(&(dst.len() as u8)));
+//! ```
+//!
 
 #[macro_use]
 extern crate lazy_static;
@@ -33,29 +274,87 @@ extern crate lazy_static;
 mod macros;
 
 mod api;
+mod cofactor;
+mod ct;
+mod curve;
 mod edw;
+mod ellswift;
+mod error;
 mod expander;
+mod field;
+mod findz;
 mod fp;
+mod g2;
+mod gf;
+mod group;
+mod inverse;
+mod isogeny;
+mod map;
 mod mont;
+mod registry;
+mod scalar;
+mod serialize;
+mod squared;
+mod vectors;
 mod weier;
 
-pub use crate::api::{GetHashToCurve, HashToCurve, HashToField, Suite};
+pub use crate::api::{
+    CheckMap, ClearCofactor, CurveTrace, ExpID, GetHashToCurve, HashID, HashToCurve, HashToField,
+    Hasher, InverseMap, MapID, ModulusBits, Suite, SuiteBuilder, Trace, XofID,
+};
+pub use crate::cofactor::{
+    Bls12381G1Cofactor, DoubleCofactor, MulByCofactor, NoCofactor, SigmaCofactor,
+};
+pub use crate::curve::edwards::{Curve as EdCurve, Point as EdPoint};
+pub use crate::curve::montgomery::{Curve as MtCurve, Point as MtPoint};
+pub use crate::curve::weierstrass::{Curve as WeCurve, Point as WePoint};
+pub use crate::curve::{EcPoint, EllipticCurve, GetCurve, MapToCurve};
 pub use crate::edw::{
-    EDWARDS25519_XMDSHA256_ELL2_NU_, EDWARDS25519_XMDSHA256_ELL2_RO_,
+    EdCurveID, EDWARDS25519_XMDSHA256_ELL2_NU_, EDWARDS25519_XMDSHA256_ELL2_RO_,
     EDWARDS25519_XMDSHA512_ELL2_NU_, EDWARDS25519_XMDSHA512_ELL2_RO_,
     EDWARDS448_XOFSHAKE256_ELL2_NU_, EDWARDS448_XOFSHAKE256_ELL2_RO_, SUITES_EDWARDS,
 };
+pub use crate::ellswift::{
+    ellswift_create, ellswift_decode, ellswift_ecdh_xonly, ellswift_encode, ellswift_xdh, xswiftec,
+    xswiftec_inv,
+};
+pub use crate::error::Error;
+pub use crate::expander::{get_expander, Expander, ExpanderStream, ExpanderXmd, ExpanderXof};
+pub use crate::field::{Field, FieldElement};
+pub use crate::findz::{find_z_ell2, find_z_sswu, find_z_svdw};
+pub use crate::fp::{Fp, FpElt, FpHasher, FromUniformBytes};
+pub use crate::g2::{
+    Bls12381G2Cofactor, Curve as G2Curve, Fp2, Fp2Elt, G2CurveID, Point as G2Point, BLS12381G2,
+    BLS12381G2_XMDSHA256_SSWU_NU_, BLS12381G2_XMDSHA256_SSWU_RO_, SUITES_BLS12381G2,
+};
+pub use crate::group::{
+    Decaf448, Element, GetHashToGroup, HashToGroup, PrimeOrderGroup, Ristretto255,
+    DECAF448_XOFSHAKE256_D448MAP_RO_, RISTRETTO255_XMDSHA512_R255MAP_RO_, SUITES_DECAF448,
+    SUITES_RISTRETTO255,
+};
 pub use crate::mont::{
-    CURVE25519_XMDSHA256_ELL2_NU_, CURVE25519_XMDSHA256_ELL2_RO_, CURVE25519_XMDSHA512_ELL2_NU_,
-    CURVE25519_XMDSHA512_ELL2_RO_, CURVE448_XOFSHAKE256_ELL2_NU_, CURVE448_XOFSHAKE256_ELL2_RO_,
-    SUITES_MONTGOMERY,
+    MtCurveID, CURVE25519_XMDSHA256_ELL2_NU_, CURVE25519_XMDSHA256_ELL2_RO_,
+    CURVE25519_XMDSHA512_ELL2_NU_, CURVE25519_XMDSHA512_ELL2_RO_, CURVE448_XOFSHAKE256_ELL2_NU_,
+    CURVE448_XOFSHAKE256_ELL2_RO_, SUITES_MONTGOMERY,
+};
+pub use crate::registry::{AnyHashToCurve, AnyPoint, AnySuite, AnyTrace, SUITES};
+pub use crate::scalar::ScalarHasher;
+pub use crate::serialize::{
+    serialize_rfc7748, serialize_rfc8032, serialize_sec1, serialize_zcash_g1, serialize_zcash_g2,
+};
+pub use crate::vectors::{
+    default_expanders, default_messages, expander_file_name, expander_vectors, map_name,
+    suite_expander_names, suite_file_name, suite_vectors,
 };
 pub use crate::weier::{
-    BLS12381G1_XMDSHA256_SSWU_NU_, BLS12381G1_XMDSHA256_SSWU_RO_, BLS12381G1_XMDSHA256_SVDW_NU_,
-    BLS12381G1_XMDSHA256_SVDW_RO_, P256_XMDSHA256_SSWU_NU_, P256_XMDSHA256_SSWU_RO_,
-    P256_XMDSHA256_SVDW_NU_, P256_XMDSHA256_SVDW_RO_, P384_XMDSHA384_SSWU_NU_,
+    WeCurveID, BLS12381G1_XMDSHA256_SSWU_NU_, BLS12381G1_XMDSHA256_SSWU_RO_,
+    BLS12381G1_XMDSHA256_SVDW_NU_, BLS12381G1_XMDSHA256_SVDW_RO_, P256_XMDSHA256_SSWU_NU_,
+    P256_XMDSHA256_SSWU_RO_, P256_XMDSHA256_SVDW_NU_, P256_XMDSHA256_SVDW_RO_,
+    P256_XOFSHAKE128_SSWU_NU_, P256_XOFSHAKE128_SSWU_RO_, P384_XMDSHA384_SSWU_NU_,
     P384_XMDSHA384_SSWU_RO_, P384_XMDSHA384_SVDW_NU_, P384_XMDSHA384_SVDW_RO_,
-    P521_XMDSHA512_SSWU_NU_, P521_XMDSHA512_SSWU_RO_, P521_XMDSHA512_SVDW_NU_,
-    P521_XMDSHA512_SVDW_RO_, SECP256K1_XMDSHA256_SSWU_NU_, SECP256K1_XMDSHA256_SSWU_RO_,
-    SECP256K1_XMDSHA256_SVDW_NU_, SECP256K1_XMDSHA256_SVDW_RO_, SUITES_WEIERSTRASS,
+    P384_XOFSHAKE256_SSWU_NU_, P384_XOFSHAKE256_SSWU_RO_, P521_XMDSHA512_SSWU_NU_,
+    P521_XMDSHA512_SSWU_RO_, P521_XMDSHA512_SVDW_NU_, P521_XMDSHA512_SVDW_RO_,
+    P521_XOFSHAKE256_SSWU_NU_, P521_XOFSHAKE256_SSWU_RO_, SECP256K1_XMDSHA256_SSWU_NU_,
+    SECP256K1_XMDSHA256_SSWU_RO_, SECP256K1_XMDSHA256_SVDW_NU_, SECP256K1_XMDSHA256_SVDW_RO_,
+    SECP256K1_XOFSHAKE128_SSWU_NU_, SECP256K1_XOFSHAKE128_SSWU_RO_, SUITES_WEIERSTRASS,
 };
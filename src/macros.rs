@@ -5,7 +5,7 @@ macro_rules! register_in_map {
         {
             let mut h = HashMap::new();
             $(
-                h.insert(String::from($elem.name), $elem);
+                h.insert($elem.name.to_string(), $elem.clone());
             )+
             h
         }
@@ -0,0 +1,154 @@
+use crate::curve::{edwards, montgomery, EllipticCurve, MapToCurve};
+use crate::error::Error;
+use crate::field::{Field, FieldElement};
+use crate::fp::{Fp, FpElt};
+
+/// Ell2 is the Elligator 2 map of Section 6.7.1 of the draft, for the
+/// Montgomery curve K * t^2 = s^3 + J * s^2 + s.
+pub(crate) struct Ell2 {
+    pub(crate) e: montgomery::Curve,
+    pub(crate) z: FpElt,
+    /// c1 = J/K and c2 = 1/K^2.
+    pub(crate) c1: FpElt,
+    pub(crate) c2: FpElt,
+    pub(crate) k: FpElt,
+}
+
+impl Ell2 {
+    pub(crate) fn new(e: montgomery::Curve, z: FpElt) -> Ell2 {
+        let k = e.b().clone();
+        Ell2 {
+            c1: e.a().clone() * &k.inv(),
+            c2: k.square().inv(),
+            k,
+            e,
+            z,
+        }
+    }
+
+    /// Outputs the affine coordinates (s, t) of the images of all the
+    /// elements of `u`, sharing the inversions of 1 + Z * u^2.
+    fn map_affine_batch(&self, u: &[FpElt]) -> Vec<(FpElt, FpElt)> {
+        let f = self.e.get_field();
+        let g = |x: &FpElt| (x.square() + &(self.c1.clone() * x)) * x + &(self.c2.clone() * x);
+        let tv1: Vec<_> = u
+            .iter()
+            .map(|u| f.one() + &(self.z.clone() * &u.square()))
+            .collect();
+        let mut st = Vec::with_capacity(u.len());
+        for inv_tv1 in f.batch_inv(&tv1) {
+            let x1 = -self.c1.clone() * &inv_tv1;
+            let x1 = if x1.is_zero() { -self.c1.clone() } else { x1 };
+            let (x, y) = match g(&x1).sqrt() {
+                Some(y) => (x1, if y.sgn0() { y } else { -y }),
+                None => {
+                    let x2 = -x1 - &self.c1;
+                    let y = g(&x2).sqrt().expect("either g(x1) or g(x2) is square");
+                    (x2, if y.sgn0() { -y } else { y })
+                }
+            };
+            st.push((x * &self.k, y * &self.k));
+        }
+        st
+    }
+}
+
+impl MapToCurve for Ell2 {
+    type E = montgomery::Curve;
+    fn map(&self, u: &FpElt) -> montgomery::Point {
+        self.map_batch(std::slice::from_ref(u)).remove(0)
+    }
+    fn map_batch(&self, u: &[FpElt]) -> Vec<montgomery::Point> {
+        self.map_affine_batch(u)
+            .into_iter()
+            .map(|(s, t)| self.e.new_point(s, t))
+            .collect()
+    }
+}
+
+/// RationalMap sends the points (s, t) of a Montgomery curve to the twisted
+/// Edwards curve used by a suite, as the fraction (xn/xd, yn/yd).
+pub(crate) enum RationalMap {
+    /// The birational map from curve25519 to edwards25519 of Appendix D.1 of
+    /// the draft, where c1 = sqrt(-486664) with sgn0(c1) = 0.
+    Edwards25519 { c1: FpElt },
+    /// The 4-isogeny from curve448 to edwards448 of RFC 7748.
+    Edwards448,
+}
+
+impl RationalMap {
+    pub(crate) fn edwards25519(e: &montgomery::Curve) -> Result<RationalMap, Error> {
+        let f = e.get_field();
+        let c1 = (f.from_i32(-2) - e.a()).sqrt().ok_or_else(|| {
+            Error::InvalidSuite(String::from("edwards25519 requires -(A + 2) to be square"))
+        })?;
+        Ok(RationalMap::Edwards25519 {
+            c1: if c1.sgn0() { -c1 } else { c1 },
+        })
+    }
+
+    fn apply(&self, f: Fp, s: &FpElt, t: &FpElt) -> [FpElt; 4] {
+        let one = f.one();
+        match self {
+            RationalMap::Edwards25519 { c1 } => [
+                c1.clone() * s,
+                t.clone(),
+                s.clone() - &one,
+                s.clone() + &one,
+            ],
+            RationalMap::Edwards448 => {
+                let (s2, t2) = (s.square(), t.square());
+                let two = one.clone() + &one;
+                let four = two.clone() + &two;
+                let s3 = s2.clone() * s;
+                let s5 = s3.clone() * &s2;
+                [
+                    four.clone() * t * &(s2.clone() - &one),
+                    s2.square() - &(two.clone() * &s2) + &(four.clone() * &t2) + &one,
+                    -(s5.clone() - &(two.clone() * &s3) - &(four * s * &t2) + s),
+                    s5 - &(two.clone() * &s2 * &t2) - &(two.clone() * &s3) - &(two * &t2) + s,
+                ]
+            }
+        }
+    }
+}
+
+/// Ell2Edwards maps to a twisted Edwards curve by composing the Elligator 2
+/// map on a Montgomery curve with a [`RationalMap`], as in Section 6.8 of
+/// the draft. The exceptional cases of the rational map are sent to the
+/// identity.
+pub(crate) struct Ell2Edwards {
+    pub(crate) e: edwards::Curve,
+    pub(crate) ell2: Ell2,
+    pub(crate) ratmap: RationalMap,
+}
+
+impl Ell2Edwards {
+    pub(crate) fn new(e: edwards::Curve, ell2: Ell2, ratmap: RationalMap) -> Ell2Edwards {
+        Ell2Edwards { e, ell2, ratmap }
+    }
+}
+
+impl MapToCurve for Ell2Edwards {
+    type E = edwards::Curve;
+    fn map(&self, u: &FpElt) -> edwards::Point {
+        self.map_batch(std::slice::from_ref(u)).remove(0)
+    }
+    /// The rational map outputs projective points, so Elligator 2 is the only
+    /// step with inversions to share.
+    fn map_batch(&self, u: &[FpElt]) -> Vec<edwards::Point> {
+        let f = self.e.get_field();
+        self.ell2
+            .map_affine_batch(u)
+            .into_iter()
+            .map(|(s, t)| {
+                let [xn, xd, yn, yd] = self.ratmap.apply(f, &s, &t);
+                let z = xd.clone() * &yd;
+                if z.is_zero() {
+                    return self.e.identity();
+                }
+                self.e.projective(xn * &yd, yn * &xd, z)
+            })
+            .collect()
+    }
+}
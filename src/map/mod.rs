@@ -0,0 +1,9 @@
+//! The maps to curve of Section 6 of the draft.
+
+mod ell2;
+mod sswu;
+mod svdw;
+
+pub(crate) use crate::map::ell2::{Ell2, Ell2Edwards, RationalMap};
+pub(crate) use crate::map::sswu::{Isogeny, SSWU, SSWUAB0};
+pub(crate) use crate::map::svdw::SVDW;
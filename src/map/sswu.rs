@@ -0,0 +1,152 @@
+use crate::curve::weierstrass::{Curve, Point};
+use crate::curve::MapToCurve;
+use crate::field::{Field, FieldElement};
+use crate::isogeny::IsogenyMap;
+
+/// Isogeny is the isogeny of an [`IsogenyMap`] with its coefficients parsed
+/// as elements of the field `F`.
+pub(crate) struct Isogeny<F: Field> {
+    pub(crate) x_num: Vec<F::Elt>,
+    pub(crate) x_den: Vec<F::Elt>,
+    pub(crate) y_num: Vec<F::Elt>,
+    pub(crate) y_den: Vec<F::Elt>,
+}
+
+impl<F: Field> Isogeny<F> {
+    pub(crate) fn new(f: &F, iso: &IsogenyMap) -> Isogeny<F> {
+        let elts = |v: &[&str]| v.iter().map(|s| f.from(s)).collect::<Vec<_>>();
+        Isogeny {
+            x_num: elts(iso.x_num),
+            x_den: elts(iso.x_den),
+            y_num: elts(iso.y_num),
+            y_den: elts(iso.y_den),
+        }
+    }
+
+    /// Evaluates the isogeny on the affine point (x', y') of E'. The output
+    /// is Jacobian, with Z = x_den * y_den, so no inversion is needed.
+    fn push(&self, e: &Curve<F>, x: &F::Elt, y: &F::Elt) -> Point<F> {
+        let (xn, xd) = (horner(&self.x_num, x), horner(&self.x_den, x));
+        let (yn, yd) = (horner(&self.y_num, x), horner(&self.y_den, x));
+        let z = xd.clone() * &yd;
+        if z.is_zero() {
+            return e.identity();
+        }
+        // x = X/Z^2 = xn/xd and y = Y/Z^3 = y' * yn/yd.
+        let xd_yd2 = z.clone() * &yd;
+        let x3 = xn * &xd_yd2;
+        let y3 = y.clone() * &yn * &xd.square() * &xd_yd2;
+        e.point(x3, y3, z)
+    }
+}
+
+fn horner<E: FieldElement>(coeffs: &[E], x: &E) -> E {
+    let (last, rest) = coeffs.split_last().expect("polynomials are not empty");
+    rest.iter().rev().fold(last.clone(), |acc, k| acc * x + k)
+}
+
+/// SSWU is the Simplified SWU map of Section 6.6.2 of the draft, for
+/// Weierstrass curves with A * B != 0.
+#[allow(clippy::upper_case_acronyms)] // Named after MapID::SSWU.
+pub(crate) struct SSWU<F: Field> {
+    e: Curve<F>,
+    z: F::Elt,
+    /// c1 = -B/A and c2 = B/(Z*A).
+    c1: F::Elt,
+    c2: F::Elt,
+}
+
+impl<F: Field> SSWU<F> {
+    pub(crate) fn new(e: Curve<F>, z: F::Elt) -> SSWU<F> {
+        let (a, b) = (e.a.clone(), e.b.clone());
+        SSWU {
+            c1: -b.clone() * &a.inv(),
+            c2: b * &(z.clone() * &a).inv(),
+            e,
+            z,
+        }
+    }
+
+    /// Maps every element of `u` to the affine coordinates of a point,
+    /// sharing the field inversions across all of them.
+    fn map_affine_batch(&self, u: &[F::Elt]) -> Vec<(F::Elt, F::Elt)> {
+        let f = self.e.get_field();
+        let zu2: Vec<_> = u.iter().map(|u| self.z.clone() * &u.square()).collect();
+        let tv1: Vec<_> = zu2.iter().map(|zu2| zu2.square() + zu2).collect();
+        let inv_tv1 = f.batch_inv(&tv1);
+        let mut xy = Vec::with_capacity(u.len());
+        for (i, u) in u.iter().enumerate() {
+            // x1 = -B/A * (1 + 1/tv1), or B/(Z*A) if tv1 = 0.
+            let x1 = if tv1[i].is_zero() {
+                self.c2.clone()
+            } else {
+                self.c1.clone() * &(f.one() + &inv_tv1[i])
+            };
+            let x2 = zu2[i].clone() * &x1;
+            let (x, mut y) = match self.e.g(&x1).sqrt() {
+                Some(y1) => (x1, y1),
+                None => {
+                    let y2 = self.e.g(&x2).sqrt();
+                    (x2, y2.expect("either g(x1) or g(x2) is square"))
+                }
+            };
+            if u.sgn0() != y.sgn0() {
+                y = -y;
+            }
+            xy.push((x, y));
+        }
+        xy
+    }
+}
+
+impl<F: Field> MapToCurve for SSWU<F> {
+    type E = Curve<F>;
+    fn map(&self, u: &F::Elt) -> Point<F> {
+        self.map_batch(std::slice::from_ref(u)).remove(0)
+    }
+    fn map_batch(&self, u: &[F::Elt]) -> Vec<Point<F>> {
+        self.map_affine_batch(u)
+            .into_iter()
+            .map(|(x, y)| self.e.new_point(x, y))
+            .collect()
+    }
+}
+
+/// SSWUAB0 is the Simplified SWU method for AB == 0 of Section 6.6.3 of the
+/// draft. It maps field elements to the isogenous curve E' with the
+/// Simplified SWU map, and pushes the result through an [`Isogeny`].
+pub(crate) struct SSWUAB0<F: Field> {
+    e: Curve<F>,
+    sswu: SSWU<F>,
+    iso: Isogeny<F>,
+}
+
+impl<F: Field> SSWUAB0<F> {
+    pub(crate) fn new(e: Curve<F>, iso: &IsogenyMap, z: F::Elt) -> SSWUAB0<F> {
+        let f = e.get_field();
+        let e1 = Curve {
+            a: f.from(iso.a),
+            b: f.from(iso.b),
+            ..e.clone()
+        };
+        SSWUAB0 {
+            sswu: SSWU::new(e1, z),
+            iso: Isogeny::new(&f, iso),
+            e,
+        }
+    }
+}
+
+impl<F: Field> MapToCurve for SSWUAB0<F> {
+    type E = Curve<F>;
+    fn map(&self, u: &F::Elt) -> Point<F> {
+        self.map_batch(std::slice::from_ref(u)).remove(0)
+    }
+    fn map_batch(&self, u: &[F::Elt]) -> Vec<Point<F>> {
+        self.sswu
+            .map_affine_batch(u)
+            .iter()
+            .map(|(x, y)| self.iso.push(&self.e, x, y))
+            .collect()
+    }
+}
@@ -0,0 +1,77 @@
+use crate::curve::weierstrass::{Curve, Point};
+use crate::curve::MapToCurve;
+use crate::error::Error;
+use crate::field::{Field, FieldElement};
+
+/// SVDW is the Shallue-van de Woestijne map of Section 6.6.1 of the draft,
+/// which works for any Weierstrass curve.
+#[allow(clippy::upper_case_acronyms)] // Named after MapID::SVDW.
+pub(crate) struct SVDW<F: Field> {
+    pub(crate) e: Curve<F>,
+    pub(crate) z: F::Elt,
+    pub(crate) c1: F::Elt,
+    pub(crate) c2: F::Elt,
+    pub(crate) c3: F::Elt,
+    pub(crate) c4: F::Elt,
+}
+
+impl<F: Field> SVDW<F> {
+    /// Fails if Z does not meet the criteria of Section 6.6.1 of the draft
+    /// that the constants depend on.
+    pub(crate) fn new(e: Curve<F>, z: F::Elt) -> Result<SVDW<F>, Error> {
+        let f = e.get_field();
+        let gz = e.g(&z);
+        // h = 3 * Z^2 + 4 * A.
+        let h = f.from("3") * &z.square() + &(f.from("4") * &e.a);
+        if gz.is_zero() || h.is_zero() {
+            return Err(Error::InvalidSuite(String::from(
+                "SVDW requires g(Z) != 0 and 3 * Z^2 + 4 * A != 0",
+            )));
+        }
+        let c3 = (-gz.clone() * &h).sqrt().ok_or_else(|| {
+            Error::InvalidSuite(String::from(
+                "SVDW requires -g(Z) * (3 * Z^2 + 4 * A) to be square",
+            ))
+        })?;
+        Ok(SVDW {
+            c2: -z.clone() * &f.from("2").inv(),
+            c3: if c3.sgn0() { -c3 } else { c3 },
+            c4: -f.from("4") * &gz * &h.inv(),
+            c1: gz,
+            e,
+            z,
+        })
+    }
+}
+
+impl<F: Field> MapToCurve for SVDW<F> {
+    type E = Curve<F>;
+    fn map(&self, u: &F::Elt) -> Point<F> {
+        self.map_batch(std::slice::from_ref(u)).remove(0)
+    }
+    /// Shares the inversions of tv1 * tv2 across all the elements of `u`.
+    fn map_batch(&self, u: &[F::Elt]) -> Vec<Point<F>> {
+        let f = self.e.get_field();
+        let w: Vec<_> = u.iter().map(|u| u.square() * &self.c1).collect();
+        let tv1: Vec<_> = w.iter().map(|w| f.one() - w).collect();
+        let tv2: Vec<_> = w.iter().map(|w| f.one() + w).collect();
+        let tv12: Vec<_> = tv1.iter().zip(&tv2).map(|(a, b)| a.clone() * b).collect();
+        let tv3 = f.batch_inv(&tv12);
+        let mut points = Vec::with_capacity(u.len());
+        for (i, u) in u.iter().enumerate() {
+            let tv4 = u.clone() * &tv1[i] * &tv3[i] * &self.c3;
+            let x1 = self.c2.clone() - &tv4;
+            let x2 = self.c2.clone() + &tv4;
+            let x3 = (tv2[i].square() * &tv3[i]).square() * &self.c4 + &self.z;
+            let (x, mut y) = [x1, x2, x3]
+                .into_iter()
+                .find_map(|x| self.e.g(&x).sqrt().map(|y| (x, y)))
+                .expect("g(x3) is square if neither g(x1) nor g(x2) is");
+            if u.sgn0() != y.sgn0() {
+                y = -y;
+            }
+            points.push(self.e.new_point(x, y));
+        }
+        points
+    }
+}
@@ -1,37 +1,123 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-use redox_ecc::ellipticcurve::{EllipticCurve, MapToCurve};
-use redox_ecc::instances::{GetCurve, MtCurveID, CURVE25519, CURVE448};
-use redox_ecc::montgomery::{Curve, Ell2};
-use redox_ecc::ops::FromFactory;
+use num_bigint::BigUint;
 
 use crate::api::{
-    Encoding, ExpID, GetHashToCurve, HashID, HashToCurve, HashToField, MapID, Suite, XofID,
+    CheckMap, ClearCofactor, CtEncoder, Encoding, ExpID, GetHashToCurve, HashID, HashToCurve,
+    HashToField, InverseMapBuilder, LazyInverseMap, MapID, ModulusBits, Suite, XofID,
 };
+use crate::cofactor::DoubleCofactor;
+use crate::ct::{CtEll2, CtEncoding, CtGf};
+use crate::curve::montgomery::{Curve, Point};
+use crate::curve::{EllipticCurve, GetCurve, MapToCurve};
+use crate::error::Error;
 use crate::expander::get_expander;
-use crate::fp::FpHasher;
+use crate::field::{parse_int, Field};
+use crate::findz::check_z_ell2;
+use crate::fp::{Fp, FpHasher};
+use crate::gf::Gf;
+use crate::inverse::Ell2Inverse;
+use crate::map::Ell2;
 use crate::register_in_map;
+use crate::weier::gf;
+
+/// MtCurveID identifies the Montgomery curves of the suites.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MtCurveID {
+    Curve25519,
+    Curve448,
+}
+
+lazy_static! {
+    /// The fields of curve25519 and curve448, which edwards25519 and
+    /// edwards448 share.
+    pub(crate) static ref F25519: Gf = gf("0x7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed");
+    pub(crate) static ref F448: Gf = gf("0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+    /// The orders of the prime-order subgroups of both pairs of curves.
+    pub(crate) static ref R25519: BigUint = parse_int("0x1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed");
+    pub(crate) static ref R448: BigUint = parse_int("0x3fffffffffffffffffffffffffffffffffffffffffffffffffffffff7cca23e9c44edb49aed63690216cc2728dc58f552378c292ab5844f3");
+}
+
+impl GetCurve for MtCurveID {
+    type E = Curve;
+    fn get(&self) -> Curve {
+        let (f, a, r, h) = match self {
+            MtCurveID::Curve25519 => (Fp::new(&F25519), 486662, &*R25519, 8u32),
+            MtCurveID::Curve448 => (Fp::new(&F448), 156326, &*R448, 4u32),
+        };
+        Curve::new(f, f.from_i32(a), f.one(), r.clone(), h.into())
+    }
+}
 
 impl GetHashToCurve for Suite<MtCurveID> {
     type E = Curve;
-    fn get(&self, dst: &[u8]) -> Box<dyn HashToCurve<E = Self::E>> {
+    fn try_get(&self, dst: &[u8]) -> Result<Box<dyn HashToCurve<E = Self::E>>, Error> {
         let curve = self.curve.get();
         let f = curve.get_field();
-        let cofactor = curve.new_scalar(curve.get_cofactor());
-        let map_to_curve: Box<dyn MapToCurve<E = Curve>> = match self.map {
-            MapID::ELL2(z) => Box::new(Ell2::new(curve.clone(), f.from(z))),
-            _ => unimplemented!(),
+        let z = match self.map {
+            MapID::ELL2(z) => z,
+            _ => return Err(Error::UnsupportedMap(self.name.to_string())),
+        };
+        let ell2 = Ell2::new(curve.clone(), f.from_i32(z));
+        let ct: Option<Box<dyn CtEncoder<Point = Point>>> = if self.ct {
+            let g = CtGf::new(f);
+            let map_to_curve = Box::new(CtEll2::new(&g, &ell2));
+            let exp = get_expander(self.exp, dst, self.k);
+            let hash_to_field = FpHasher {
+                f: g,
+                exp,
+                l: self.l,
+            };
+            let h_eff = curve.get_cofactor();
+            Some(Box::new(CtEncoding::new(
+                hash_to_field,
+                map_to_curve,
+                h_eff,
+            )))
+        } else {
+            None
         };
+        let map_to_curve: Box<dyn MapToCurve<E = Curve>> = Box::new(ell2);
         let exp = get_expander(self.exp, dst, self.k);
         let hash_to_field: Box<dyn HashToField<F = <Curve as EllipticCurve>::F>> =
             Box::new(FpHasher { f, exp, l: self.l });
-        Box::new(Encoding {
+        let clear_cofactor: Box<dyn ClearCofactor<Point = <Curve as EllipticCurve>::Point>> =
+            match self.curve {
+                MtCurveID::Curve25519 => Box::new(DoubleCofactor::<Curve>::new(3)),
+                MtCurveID::Curve448 => Box::new(DoubleCofactor::<Curve>::new(2)),
+            };
+        let e = curve.clone();
+        let inverse_map: InverseMapBuilder<Curve> =
+            Box::new(move || Ok(Box::new(Ell2Inverse::new(e.clone(), z)?)));
+        Ok(Box::new(Encoding {
             curve,
             hash_to_field,
             map_to_curve,
-            cofactor,
+            clear_cofactor,
+            inverse_map: LazyInverseMap::new(Some(inverse_map)),
+            l: self.l,
             ro: self.ro,
-        })
+            ct,
+        }))
+    }
+}
+
+impl ModulusBits for MtCurveID {
+    fn modulus_bits(&self) -> usize {
+        self.get().get_field().get_modulus().bits() as usize
+    }
+}
+
+impl CheckMap for MtCurveID {
+    fn check_map(&self, map: MapID) -> Result<(), Error> {
+        match map {
+            MapID::ELL2(z) => check_z_ell2(&self.get().get_field().from_i32(z)),
+            _ => Err(Error::InvalidSuite(format!(
+                "{:?} is not a map to {:?}",
+                map, self
+            ))),
+        }
     }
 }
 
@@ -47,46 +133,49 @@ lazy_static! {
 }
 
 pub static CURVE25519_XMDSHA256_ELL2_NU_: Suite<MtCurveID> = Suite {
-    name: "curve25519_XMD:SHA-256_ELL2_NU_",
-    curve: CURVE25519,
+    name: Cow::Borrowed("curve25519_XMD:SHA-256_ELL2_NU_"),
+    curve: MtCurveID::Curve25519,
     map: MapID::ELL2(2),
     k: 128,
     exp: ExpID::XMD(HashID::SHA256),
     l: 48,
     ro: false,
+    ct: false,
 };
 pub static CURVE25519_XMDSHA256_ELL2_RO_: Suite<MtCurveID> = Suite {
-    name: "curve25519_XMD:SHA-256_ELL2_RO_",
+    name: Cow::Borrowed("curve25519_XMD:SHA-256_ELL2_RO_"),
     ro: true,
     ..CURVE25519_XMDSHA256_ELL2_NU_
 };
 
 pub static CURVE25519_XMDSHA512_ELL2_NU_: Suite<MtCurveID> = Suite {
-    name: "curve25519_XMD:SHA-512_ELL2_NU_",
-    curve: CURVE25519,
+    name: Cow::Borrowed("curve25519_XMD:SHA-512_ELL2_NU_"),
+    curve: MtCurveID::Curve25519,
     map: MapID::ELL2(2),
     k: 128,
     exp: ExpID::XMD(HashID::SHA512),
     l: 48,
     ro: false,
+    ct: false,
 };
 pub static CURVE25519_XMDSHA512_ELL2_RO_: Suite<MtCurveID> = Suite {
-    name: "curve25519_XMD:SHA-512_ELL2_RO_",
+    name: Cow::Borrowed("curve25519_XMD:SHA-512_ELL2_RO_"),
     ro: true,
     ..CURVE25519_XMDSHA512_ELL2_NU_
 };
 
 pub static CURVE448_XOFSHAKE256_ELL2_NU_: Suite<MtCurveID> = Suite {
-    name: "curve448_XOF:SHAKE256_ELL2_NU_",
-    curve: CURVE448,
+    name: Cow::Borrowed("curve448_XOF:SHAKE256_ELL2_NU_"),
+    curve: MtCurveID::Curve448,
     map: MapID::ELL2(-1),
     k: 224,
     exp: ExpID::XOF(XofID::SHAKE256),
     l: 84,
     ro: false,
+    ct: false,
 };
 pub static CURVE448_XOFSHAKE256_ELL2_RO_: Suite<MtCurveID> = Suite {
-    name: "curve448_XOF:SHAKE256_ELL2_RO_",
+    name: Cow::Borrowed("curve448_XOF:SHAKE256_ELL2_RO_"),
     ro: true,
     ..CURVE448_XOFSHAKE256_ELL2_NU_
 };
@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+
+use crate::api::{ExpID, GetHashToCurve, HashToCurve, MapID, Suite, Trace};
+use crate::curve::edwards::{Curve as EdCurve, Point as EdPoint};
+use crate::curve::montgomery::{Curve as MtCurve, Point as MtPoint};
+use crate::curve::weierstrass::{Curve as WeCurve, Point as WePoint};
+use crate::curve::EcPoint;
+use crate::edw::{EdCurveID, SUITES_EDWARDS};
+use crate::error::Error;
+use crate::g2::{Curve as G2Curve, G2CurveID, Point as G2Point, SUITES_BLS12381G2};
+use crate::group::{
+    Decaf448, Element, GetHashToGroup, HashToGroup, Ristretto255, SUITES_DECAF448,
+    SUITES_RISTRETTO255,
+};
+use crate::mont::{MtCurveID, SUITES_MONTGOMERY};
+use crate::weier::{WeCurveID, SUITES_WEIERSTRASS};
+
+/// AnySuite holds a suite of any curve family, or of a prime-order group, so
+/// that suites can be selected by their suite ID at runtime.
+#[derive(Clone)]
+pub enum AnySuite {
+    Weierstrass(Suite<WeCurveID>),
+    Montgomery(Suite<MtCurveID>),
+    Edwards(Suite<EdCurveID>),
+    BLS12381G2(Suite<G2CurveID>),
+    Ristretto255(Suite<Ristretto255>),
+    Decaf448(Suite<Decaf448>),
+}
+
+macro_rules! any_suite {
+    ($s:expr, $x:ident => $e:expr) => {
+        match $s {
+            AnySuite::Weierstrass($x) => $e,
+            AnySuite::Montgomery($x) => $e,
+            AnySuite::Edwards($x) => $e,
+            AnySuite::BLS12381G2($x) => $e,
+            AnySuite::Ristretto255($x) => $e,
+            AnySuite::Decaf448($x) => $e,
+        }
+    };
+}
+
+impl AnySuite {
+    /// Returns the suite ID, e.g., "P256_XMD:SHA-256_SSWU_RO_".
+    pub fn name(&self) -> &str {
+        any_suite!(self, s => s.name())
+    }
+    /// Returns the CURVE_ID part of the suite ID, e.g., "P256".
+    pub fn curve_name(&self) -> &str {
+        let name = self.name();
+        &name[..name.find('_').unwrap_or(name.len())]
+    }
+    pub fn map(&self) -> MapID {
+        any_suite!(self, s => s.map())
+    }
+    pub fn exp(&self) -> ExpID {
+        any_suite!(self, s => s.exp())
+    }
+    pub fn k(&self) -> usize {
+        any_suite!(self, s => s.k())
+    }
+    pub fn l(&self) -> usize {
+        any_suite!(self, s => s.l())
+    }
+    pub fn is_random_oracle(&self) -> bool {
+        any_suite!(self, s => s.is_random_oracle())
+    }
+    /// Returns true if the suite hashes to a prime-order group rather than
+    /// to the points of a curve.
+    pub fn is_group(&self) -> bool {
+        matches!(self, AnySuite::Ristretto255(_) | AnySuite::Decaf448(_))
+    }
+    pub fn try_get(&self, dst: &[u8]) -> Result<AnyHashToCurve, Error> {
+        Ok(match self {
+            AnySuite::Weierstrass(s) => AnyHashToCurve::Weierstrass(s.try_get(dst)?),
+            AnySuite::Montgomery(s) => AnyHashToCurve::Montgomery(s.try_get(dst)?),
+            AnySuite::Edwards(s) => AnyHashToCurve::Edwards(s.try_get(dst)?),
+            AnySuite::BLS12381G2(s) => AnyHashToCurve::BLS12381G2(s.try_get(dst)?),
+            AnySuite::Ristretto255(s) => AnyHashToCurve::Ristretto255(s.try_get(dst)?),
+            AnySuite::Decaf448(s) => AnyHashToCurve::Decaf448(s.try_get(dst)?),
+        })
+    }
+    pub fn get(&self, dst: &[u8]) -> AnyHashToCurve {
+        self.try_get(dst).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Hashes the messages of `msgs` in parallel, outputting the points in
+    /// the same order as `msgs`.
+    #[cfg(feature = "rayon")]
+    pub fn try_par_hash(&self, dst: &[u8], msgs: &[&[u8]]) -> Result<Vec<AnyPoint>, Error> {
+        Ok(match self {
+            AnySuite::Weierstrass(s) => s
+                .try_par_hash(dst, msgs)?
+                .into_iter()
+                .map(AnyPoint::Weierstrass)
+                .collect(),
+            AnySuite::Montgomery(s) => s
+                .try_par_hash(dst, msgs)?
+                .into_iter()
+                .map(AnyPoint::Montgomery)
+                .collect(),
+            AnySuite::Edwards(s) => s
+                .try_par_hash(dst, msgs)?
+                .into_iter()
+                .map(AnyPoint::Edwards)
+                .collect(),
+            AnySuite::BLS12381G2(s) => s
+                .try_par_hash(dst, msgs)?
+                .into_iter()
+                .map(AnyPoint::BLS12381G2)
+                .collect(),
+            AnySuite::Ristretto255(s) => s
+                .try_par_hash(dst, msgs)?
+                .into_iter()
+                .map(AnyPoint::Ristretto255)
+                .collect(),
+            AnySuite::Decaf448(s) => s
+                .try_par_hash(dst, msgs)?
+                .into_iter()
+                .map(AnyPoint::Decaf448)
+                .collect(),
+        })
+    }
+    #[cfg(feature = "rayon")]
+    pub fn par_hash(&self, dst: &[u8], msgs: &[&[u8]]) -> Vec<AnyPoint> {
+        self.try_par_hash(dst, msgs)
+            .unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl std::fmt::Display for AnySuite {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+macro_rules! any_trace {
+    ($h:expr, $msg:expr, $point:path) => {{
+        let t = $h.try_trace($msg)?;
+        Trace {
+            u: t.u.iter().map(|u| u.to_string()).collect(),
+            q: t.q.into_iter().map($point).collect(),
+            r: $point(t.r),
+            p: $point(t.p),
+        }
+    }};
+}
+
+/// AnyTrace is the trace of an [`AnyHashToCurve`], with the field elements
+/// written in hexadecimal as in the test vectors of the draft. Elements of
+/// GF(p^2) are written as "c0,c1".
+pub type AnyTrace = Trace<String, AnyPoint>;
+
+/// AnyHashToCurve is a hasher for a suite of any curve family, or of a
+/// prime-order group.
+pub enum AnyHashToCurve {
+    Weierstrass(Box<dyn HashToCurve<E = WeCurve>>),
+    Montgomery(Box<dyn HashToCurve<E = MtCurve>>),
+    Edwards(Box<dyn HashToCurve<E = EdCurve>>),
+    BLS12381G2(Box<dyn HashToCurve<E = G2Curve>>),
+    Ristretto255(Box<dyn HashToGroup<G = Ristretto255>>),
+    Decaf448(Box<dyn HashToGroup<G = Decaf448>>),
+}
+
+impl AnyHashToCurve {
+    pub fn is_random_oracle(&self) -> bool {
+        match self {
+            AnyHashToCurve::Weierstrass(h) => h.is_random_oracle(),
+            AnyHashToCurve::Montgomery(h) => h.is_random_oracle(),
+            AnyHashToCurve::Edwards(h) => h.is_random_oracle(),
+            AnyHashToCurve::BLS12381G2(h) => h.is_random_oracle(),
+            AnyHashToCurve::Ristretto255(h) => h.is_random_oracle(),
+            AnyHashToCurve::Decaf448(h) => h.is_random_oracle(),
+        }
+    }
+    pub fn try_hash(&self, msg: &[u8]) -> Result<AnyPoint, Error> {
+        Ok(match self {
+            AnyHashToCurve::Weierstrass(h) => AnyPoint::Weierstrass(h.try_hash(msg)?),
+            AnyHashToCurve::Montgomery(h) => AnyPoint::Montgomery(h.try_hash(msg)?),
+            AnyHashToCurve::Edwards(h) => AnyPoint::Edwards(h.try_hash(msg)?),
+            AnyHashToCurve::BLS12381G2(h) => AnyPoint::BLS12381G2(h.try_hash(msg)?),
+            AnyHashToCurve::Ristretto255(h) => AnyPoint::Ristretto255(h.try_hash(msg)?),
+            AnyHashToCurve::Decaf448(h) => AnyPoint::Decaf448(h.try_hash(msg)?),
+        })
+    }
+    pub fn hash(&self, msg: &[u8]) -> AnyPoint {
+        self.try_hash(msg).unwrap_or_else(|e| panic!("{e}"))
+    }
+    pub fn try_hash_batch(&self, msgs: &[&[u8]]) -> Result<Vec<AnyPoint>, Error> {
+        Ok(match self {
+            AnyHashToCurve::Weierstrass(h) => h
+                .try_hash_batch(msgs)?
+                .into_iter()
+                .map(AnyPoint::Weierstrass)
+                .collect(),
+            AnyHashToCurve::Montgomery(h) => h
+                .try_hash_batch(msgs)?
+                .into_iter()
+                .map(AnyPoint::Montgomery)
+                .collect(),
+            AnyHashToCurve::Edwards(h) => h
+                .try_hash_batch(msgs)?
+                .into_iter()
+                .map(AnyPoint::Edwards)
+                .collect(),
+            AnyHashToCurve::BLS12381G2(h) => h
+                .try_hash_batch(msgs)?
+                .into_iter()
+                .map(AnyPoint::BLS12381G2)
+                .collect(),
+            AnyHashToCurve::Ristretto255(h) => h
+                .try_hash_batch(msgs)?
+                .into_iter()
+                .map(AnyPoint::Ristretto255)
+                .collect(),
+            AnyHashToCurve::Decaf448(h) => h
+                .try_hash_batch(msgs)?
+                .into_iter()
+                .map(AnyPoint::Decaf448)
+                .collect(),
+        })
+    }
+    pub fn hash_batch(&self, msgs: &[&[u8]]) -> Vec<AnyPoint> {
+        self.try_hash_batch(msgs).unwrap_or_else(|e| panic!("{e}"))
+    }
+    pub fn try_trace(&self, msg: &[u8]) -> Result<AnyTrace, Error> {
+        Ok(match self {
+            AnyHashToCurve::Weierstrass(h) => any_trace!(h, msg, AnyPoint::Weierstrass),
+            AnyHashToCurve::Montgomery(h) => any_trace!(h, msg, AnyPoint::Montgomery),
+            AnyHashToCurve::Edwards(h) => any_trace!(h, msg, AnyPoint::Edwards),
+            AnyHashToCurve::BLS12381G2(h) => any_trace!(h, msg, AnyPoint::BLS12381G2),
+            // The groups map uniform bytes to elements without going through
+            // field elements, so there is nothing to trace.
+            AnyHashToCurve::Ristretto255(_) | AnyHashToCurve::Decaf448(_) => {
+                return Err(Error::UnsupportedTrace)
+            }
+        })
+    }
+    pub fn trace(&self, msg: &[u8]) -> AnyTrace {
+        self.try_trace(msg).unwrap_or_else(|e| panic!("{e}"))
+    }
+    /// Returns the affine coordinates (x, y) of a point output by this
+    /// hasher written in hexadecimal, or None if the point is the identity.
+    /// Elements of GF(p^2) are written as "c0,c1". It fails with
+    /// [`Error::PointMismatch`] if the point belongs to another curve, and
+    /// with [`Error::NoCoordinates`] if it is an element of a prime-order
+    /// group.
+    pub fn coordinates(&self, p: &AnyPoint) -> Result<Option<(String, String)>, Error> {
+        Ok(match (self, p) {
+            (AnyHashToCurve::Weierstrass(_), AnyPoint::Weierstrass(p)) => hex_affine(p),
+            (AnyHashToCurve::Montgomery(_), AnyPoint::Montgomery(p)) => hex_affine(p),
+            (AnyHashToCurve::Edwards(_), AnyPoint::Edwards(p)) => hex_affine(p),
+            (AnyHashToCurve::BLS12381G2(_), AnyPoint::BLS12381G2(p)) => hex_affine(p),
+            (AnyHashToCurve::Ristretto255(_), AnyPoint::Ristretto255(_))
+            | (AnyHashToCurve::Decaf448(_), AnyPoint::Decaf448(_)) => {
+                return Err(Error::NoCoordinates)
+            }
+            _ => return Err(Error::PointMismatch),
+        })
+    }
+}
+
+/// Returns the affine coordinates of a point written in hexadecimal, or None
+/// for the identity.
+fn hex_affine<P: EcPoint>(p: &P) -> Option<(String, String)>
+where
+    P::Elt: std::fmt::Display,
+{
+    if p.is_identity() {
+        return None;
+    }
+    p.to_affine().map(|(x, y)| (x.to_string(), y.to_string()))
+}
+
+/// AnyPoint is a point output by an [`AnyHashToCurve`].
+#[derive(Clone, PartialEq)]
+pub enum AnyPoint {
+    Weierstrass(WePoint),
+    Montgomery(MtPoint),
+    Edwards(EdPoint),
+    BLS12381G2(G2Point),
+    Ristretto255(Element<Ristretto255>),
+    Decaf448(Element<Decaf448>),
+}
+
+impl AnyPoint {
+    pub fn normalize(&mut self) {
+        match self {
+            AnyPoint::Weierstrass(p) => p.normalize(),
+            AnyPoint::Montgomery(p) => p.normalize(),
+            AnyPoint::Edwards(p) => p.normalize(),
+            AnyPoint::BLS12381G2(p) => p.normalize(),
+            // An element keeps the point it was computed with; only its
+            // encoding is canonical.
+            AnyPoint::Ristretto255(_) | AnyPoint::Decaf448(_) => {}
+        }
+    }
+}
+
+impl std::fmt::Display for AnyPoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            AnyPoint::Weierstrass(p) => write!(f, "{}", p),
+            AnyPoint::Montgomery(p) => write!(f, "{}", p),
+            AnyPoint::Edwards(p) => write!(f, "{}", p),
+            AnyPoint::BLS12381G2(p) => write!(f, "{}", p),
+            AnyPoint::Ristretto255(p) => write!(f, "{}", p),
+            AnyPoint::Decaf448(p) => write!(f, "{}", p),
+        }
+    }
+}
+
+lazy_static! {
+    /// SUITES holds every suite of this crate, indexed by its suite ID.
+    pub static ref SUITES: HashMap<String, AnySuite> = {
+        let mut h = HashMap::new();
+        for (k, s) in SUITES_WEIERSTRASS.iter() {
+            h.insert(k.clone(), AnySuite::Weierstrass(s.clone()));
+        }
+        for (k, s) in SUITES_MONTGOMERY.iter() {
+            h.insert(k.clone(), AnySuite::Montgomery(s.clone()));
+        }
+        for (k, s) in SUITES_EDWARDS.iter() {
+            h.insert(k.clone(), AnySuite::Edwards(s.clone()));
+        }
+        for (k, s) in SUITES_BLS12381G2.iter() {
+            h.insert(k.clone(), AnySuite::BLS12381G2(s.clone()));
+        }
+        for (k, s) in SUITES_RISTRETTO255.iter() {
+            h.insert(k.clone(), AnySuite::Ristretto255(s.clone()));
+        }
+        for (k, s) in SUITES_DECAF448.iter() {
+            h.insert(k.clone(), AnySuite::Decaf448(s.clone()));
+        }
+        h
+    };
+}
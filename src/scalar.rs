@@ -0,0 +1,46 @@
+use num_bigint::BigUint;
+
+use crate::api::Suite;
+use crate::curve::{EllipticCurve, GetCurve};
+use crate::error::Error;
+use crate::expander::{get_expander, Expander};
+
+/// ScalarHasher hashes arbitrary strings to integers modulo the order of the
+/// prime-order subgroup of a curve, i.e., it implements hash_to_field of
+/// Section 5.2 of the draft with p set to the group order and m = 1.
+pub struct ScalarHasher {
+    order: BigUint,
+    l: usize,
+    exp: Box<dyn Expander>,
+}
+
+impl ScalarHasher {
+    /// Returns a hasher to integers modulo `order`, consuming `l` bytes
+    /// output by `exp` per scalar.
+    pub fn new(order: BigUint, exp: Box<dyn Expander>, l: usize) -> Self {
+        ScalarHasher { order, l, exp }
+    }
+    #[inline]
+    pub fn get_order(&self) -> &BigUint {
+        &self.order
+    }
+    pub fn try_hash(&self, msg: &[u8], count: usize) -> Result<Vec<BigUint>, Error> {
+        let pseudo = self.exp.try_expand(msg, count * self.l)?;
+        Ok(pseudo
+            .chunks(self.l)
+            .map(|tv| BigUint::from_bytes_be(tv) % &self.order)
+            .collect())
+    }
+    pub fn hash(&self, msg: &[u8], count: usize) -> Vec<BigUint> {
+        self.try_hash(msg, count).unwrap_or_else(|e| panic!("{e}"))
+    }
+}
+
+impl<T: GetCurve> Suite<T> {
+    /// Returns a hasher to the scalar field of the suite's curve, which uses
+    /// the same expander, security level and L as the suite.
+    pub fn get_scalar(&self, dst: &[u8]) -> ScalarHasher {
+        let exp = get_expander(self.exp, dst, self.k);
+        ScalarHasher::new(self.curve.get().get_order(), exp, self.l)
+    }
+}
@@ -0,0 +1,144 @@
+use num_bigint::BigUint;
+
+use crate::curve::edwards::{Curve as EdCurve, Point as EdPoint};
+use crate::curve::montgomery::{Curve as MtCurve, Point as MtPoint};
+use crate::curve::weierstrass::{Curve as WeCurve, Point as WePoint};
+use crate::curve::{EcPoint, EllipticCurve};
+use crate::field::Field;
+use crate::fp::FpElt;
+use crate::g2::{Fp2, Point as G2Point};
+
+/// Returns the big-endian encodings of the affine coordinates (x, y) of a
+/// point, each one n bytes long, or None for the identity.
+fn affine<P: EcPoint<Elt = FpElt>>(p: &P, n: usize) -> Option<(Vec<u8>, Vec<u8>)> {
+    let (x, y) = p.to_affine()?;
+    Some((
+        to_fixed(x.value().to_bytes_be(), n),
+        to_fixed(y.value().to_bytes_be(), n),
+    ))
+}
+
+/// Serializes a point on a Weierstrass curve following Section 2.3.3 of
+/// SEC 1, either in compressed (0x02 or 0x03 || x) or uncompressed
+/// (0x04 || x || y) form. The identity is serialized as 0x00.
+pub fn serialize_sec1(e: &WeCurve, p: &WePoint, compressed: bool) -> Vec<u8> {
+    match affine(p, e.get_field().size_bytes()) {
+        None => vec![0x00],
+        Some((x, y)) => sec1(&x, &y, compressed),
+    }
+}
+
+/// Serializes a point on edwards25519 or edwards448 following Section 5.1.2
+/// (resp. Section 5.2.2) of RFC 8032: the little-endian encoding of y, with
+/// the least significant bit of x copied into the most significant bit.
+pub fn serialize_rfc8032(e: &EdCurve, p: &EdPoint) -> Vec<u8> {
+    let (x, y) = affine(p, e.get_field().size_bytes()).expect("Edwards points are affine");
+    let bits = e.get_field().get_modulus().bits() as usize;
+    rfc8032(&x, &y, (bits + 1).div_ceil(8))
+}
+
+/// Serializes the u-coordinate of a point on curve25519 or curve448 as
+/// little-endian bytes, following Section 5 of RFC 7748. The identity is
+/// serialized as u = 0.
+pub fn serialize_rfc7748(e: &MtCurve, p: &MtPoint) -> Vec<u8> {
+    let n = e.get_field().size_bytes();
+    match affine(p, n) {
+        None => vec![0; n],
+        Some((u, _)) => u.into_iter().rev().collect(),
+    }
+}
+
+/// Serializes a point of BLS12-381 G1 in the format of the ZCash BLS12-381
+/// specification, i.e., 48 bytes if compressed and 96 bytes otherwise.
+pub fn serialize_zcash_g1(e: &WeCurve, p: &WePoint, compressed: bool) -> Vec<u8> {
+    let n = e.get_field().size_bytes();
+    match affine(p, n) {
+        None => zcash(None, n, compressed),
+        Some((x, y)) => {
+            let largest = is_largest(&BigUint::from_bytes_be(&y));
+            zcash(Some((x, y, largest)), n, compressed)
+        }
+    }
+}
+
+/// Serializes a point of BLS12-381 G2 in the format of the ZCash BLS12-381
+/// specification, i.e., 96 bytes if compressed and 192 bytes otherwise. Each
+/// coordinate c0 + c1*I is written as c1 || c0.
+pub fn serialize_zcash_g2(p: &G2Point, compressed: bool) -> Vec<u8> {
+    let n = Fp2.size_bytes();
+    match p.to_affine() {
+        None => zcash(None, 2 * n, compressed),
+        Some((x, y)) => {
+            let concat = |c1: &BigUint, c0: &BigUint| {
+                let mut v = to_fixed(c1.to_bytes_be(), n);
+                v.extend(to_fixed(c0.to_bytes_be(), n));
+                v
+            };
+            let largest = if y.c1().bits() == 0 {
+                is_largest(y.c0())
+            } else {
+                is_largest(y.c1())
+            };
+            let (x, y) = (concat(x.c1(), x.c0()), concat(y.c1(), y.c0()));
+            zcash(Some((x, y, largest)), 2 * n, compressed)
+        }
+    }
+}
+
+/// Left-pads (or strips leading zeros from) a big-endian integer so that it
+/// has n bytes.
+fn to_fixed(v: Vec<u8>, n: usize) -> Vec<u8> {
+    let start = v.iter().position(|&b| b != 0).unwrap_or(v.len());
+    let v = &v[start..];
+    let mut out = vec![0; n.saturating_sub(v.len())];
+    out.extend_from_slice(v);
+    out
+}
+
+fn sec1(x: &[u8], y: &[u8], compressed: bool) -> Vec<u8> {
+    let mut out = Vec::with_capacity(1 + x.len() + y.len());
+    if compressed {
+        out.push(0x02 | (y[y.len() - 1] & 1));
+        out.extend_from_slice(x);
+    } else {
+        out.push(0x04);
+        out.extend_from_slice(x);
+        out.extend_from_slice(y);
+    }
+    out
+}
+
+fn rfc8032(x: &[u8], y: &[u8], len: usize) -> Vec<u8> {
+    let mut out: Vec<u8> = y.iter().rev().copied().collect();
+    out.resize(len, 0);
+    out[len - 1] |= (x[x.len() - 1] & 1) << 7;
+    out
+}
+
+/// Returns true if x > (p-1)/2, where p is the BLS12-381 base field prime.
+fn is_largest(x: &BigUint) -> bool {
+    *x > (Fp2.get_modulus() - 1u32) >> 1
+}
+
+/// Returns the ZCash encoding of a point given the big-endian encodings of
+/// its coordinates (each of n bytes), or None for the identity.
+fn zcash(p: Option<(Vec<u8>, Vec<u8>, bool)>, n: usize, compressed: bool) -> Vec<u8> {
+    const COMPRESSED: u8 = 0x80;
+    const INFINITY: u8 = 0x40;
+    const LARGEST: u8 = 0x20;
+    match p {
+        None => {
+            let mut out = vec![0; if compressed { n } else { 2 * n }];
+            out[0] = INFINITY | if compressed { COMPRESSED } else { 0 };
+            out
+        }
+        Some((mut x, y, largest)) => {
+            if compressed {
+                x[0] |= COMPRESSED | if largest { LARGEST } else { 0 };
+            } else {
+                x.extend(y);
+            }
+            x
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use num_bigint::BigUint;
+use rand_core::RngCore;
+
+use crate::api::HashToCurve;
+use crate::curve::EllipticCurve;
+use crate::error::Error;
+use crate::field::Field;
+
+type Elt<E> = <<E as EllipticCurve>::F as Field>::Elt;
+
+/// Returns the field element whose m coordinates are read from consecutive
+/// L-byte big-endian integers of `b`, each one reduced modulo p.
+fn from_bytes<F: Field>(f: &F, l: usize, b: &[u8]) -> F::Elt {
+    let c: Vec<_> = b.chunks(l).map(BigUint::from_bytes_be).collect();
+    f.from_ints(&c)
+}
+
+/// Implements the encoder of Elligator Squared (Tibouchi, FC 2014). A point
+/// P is written as map_to_curve(u0) + map_to_curve(u1), where u0 is taken at
+/// random and u1 is a preimage of P - map_to_curve(u0) taken from a random
+/// branch of the inverse map; both are output as m L-byte integers, one per
+/// coordinate. The loop
+/// ends after a few attempts on average, since a random point has a preimage
+/// in a random branch with constant probability.
+pub(crate) fn encode<H>(
+    h: &H,
+    l: usize,
+    p: &<H::E as EllipticCurve>::Point,
+    rng: &mut dyn RngCore,
+) -> Result<Vec<u8>, Error>
+where
+    H: HashToCurve + ?Sized,
+{
+    let branches = h.inverse_branches() as u64;
+    if branches == 0 {
+        return Err(Error::UnsupportedInverseMap);
+    }
+    let f = h.get_curve().get_field();
+    let modulus = f.get_modulus();
+    loop {
+        let mut r0 = vec![0u8; f.degree() * l];
+        rng.fill_bytes(&mut r0);
+        let u0: Elt<H::E> = from_bytes(&f, l, &r0);
+        let q = p.clone() + -h.map_to_curve(&u0);
+        let branch = (rng.next_u64() % branches) as usize;
+        if let Some(u1) = h.inverse_map(&q, branch) {
+            for c in f.to_ints(&u1) {
+                r0.extend(lift(&c, &modulus, l, rng));
+            }
+            return Ok(r0);
+        }
+    }
+}
+
+/// Implements the decoder of Elligator Squared. Each half of `b` is reduced
+/// modulo p as in hash_to_field, so every string of 2*m*L bytes is decoded.
+pub(crate) fn decode<H>(h: &H, l: usize, b: &[u8]) -> Result<<H::E as EllipticCurve>::Point, Error>
+where
+    H: HashToCurve + ?Sized,
+{
+    let f = h.get_curve().get_field();
+    let n = f.degree() * l;
+    if b.len() != 2 * n {
+        return Err(Error::InvalidLength {
+            expected: 2 * n,
+            got: b.len(),
+        });
+    }
+    let q0 = h.map_to_curve(&from_bytes(&f, l, &b[..n]));
+    let q1 = h.map_to_curve(&from_bytes(&f, l, &b[n..]));
+    Ok(q0 + q1)
+}
+
+/// Outputs an L-byte integer congruent to u modulo p, taken at random among
+/// those below 2^(8*L). Since 8*L exceeds the bit length of p by about k,
+/// the output is statistically close to uniform if u is uniform modulo p.
+fn lift(u: &BigUint, p: &BigUint, l: usize, rng: &mut dyn RngCore) -> Vec<u8> {
+    let bound = BigUint::from(1u32) << (8 * l);
+    let mut r = vec![0u8; l];
+    loop {
+        rng.fill_bytes(&mut r);
+        let r = BigUint::from_bytes_be(&r);
+        let v = &r - (&r % p) + u;
+        if v < bound {
+            let v = v.to_bytes_be();
+            let mut out = vec![0u8; l - v.len()];
+            out.extend(v);
+            return out;
+        }
+    }
+}
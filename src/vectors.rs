@@ -0,0 +1,334 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use num_bigint::{BigInt, BigUint};
+
+use crate::api::{ExpID, HashID, MapID, XofID};
+use crate::curve::{EllipticCurve, GetCurve};
+use crate::error::Error;
+use crate::expander::get_expander;
+use crate::field::Field;
+use crate::registry::{AnyHashToCurve, AnyPoint, AnySuite};
+
+/// Json is a JSON value that is written in the same format as the test
+/// vectors of the draft, that is, with sorted keys and indented with two
+/// spaces.
+enum Json {
+    Null,
+    Bool(bool),
+    Int(usize),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(BTreeMap<&'static str, Json>),
+}
+
+impl Json {
+    fn obj<const N: usize>(fields: [(&'static str, Json); N]) -> Json {
+        Json::Obj(fields.into_iter().collect())
+    }
+    fn str(s: impl Into<String>) -> Json {
+        Json::Str(s.into())
+    }
+    fn hex(n: impl std::fmt::LowerHex) -> Json {
+        Json::Str(format!("{:#x}", n))
+    }
+    fn write(&self, out: &mut String, indent: usize) {
+        let pad = |out: &mut String, n: usize| out.push_str(&"  ".repeat(n));
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+            Json::Int(n) => out.push_str(&n.to_string()),
+            Json::Str(s) => write_str(out, s),
+            Json::Arr(v) if v.is_empty() => out.push_str("[]"),
+            Json::Obj(m) if m.is_empty() => out.push_str("{}"),
+            Json::Arr(v) => {
+                out.push('[');
+                for (i, x) in v.iter().enumerate() {
+                    out.push_str(if i == 0 { "\n" } else { ",\n" });
+                    pad(out, indent + 1);
+                    x.write(out, indent + 1);
+                }
+                out.push('\n');
+                pad(out, indent);
+                out.push(']');
+            }
+            Json::Obj(m) => {
+                out.push('{');
+                for (i, (k, x)) in m.iter().enumerate() {
+                    out.push_str(if i == 0 { "\n" } else { ",\n" });
+                    pad(out, indent + 1);
+                    write_str(out, k);
+                    out.push_str(": ");
+                    x.write(out, indent + 1);
+                }
+                out.push('\n');
+                pad(out, indent);
+                out.push('}');
+            }
+        }
+    }
+    fn to_file(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, 0);
+        out.push('\n');
+        out
+    }
+}
+
+/// Writes a JSON string escaping any non-ASCII character, as the generator
+/// of the draft does.
+fn write_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            ' '..='\u{7f}' => out.push(c),
+            _ => {
+                for u in c.encode_utf16(&mut [0; 2]) {
+                    write!(out, "\\u{:04x}", u).unwrap();
+                }
+            }
+        }
+    }
+    out.push('"');
+}
+
+/// Returns the messages hashed by the test vectors of the draft.
+pub fn default_messages() -> Vec<String> {
+    vec![
+        String::new(),
+        "abc".to_string(),
+        "abcdef0123456789".to_string(),
+        format!("q128_{}", "q".repeat(128)),
+        format!("a512_{}", "a".repeat(512)),
+    ]
+}
+
+/// Returns the name that the test vectors of suites give to a map, e.g.,
+/// "SSWU" for `MapID::SSWU`, `MapID::SSWUAB0` and `MapID::SSWUAB0FP2`.
+pub fn map_name(map: MapID) -> &'static str {
+    match map {
+        MapID::SSWU(_) | MapID::SSWUAB0(_) | MapID::SSWUAB0FP2(..) => "SSWU",
+        MapID::SVDW(_) => "SVDW",
+        MapID::ELL2(_) => "ELL2",
+        MapID::R255MAP => "R255MAP",
+        MapID::D448MAP => "D448MAP",
+    }
+}
+
+/// Returns the names that the test vectors of suites give to an expander and
+/// to its hash function, e.g., ("XMD", "sha256").
+pub fn suite_expander_names(exp: ExpID) -> (&'static str, &'static str) {
+    match exp {
+        ExpID::XMD(HashID::SHA256) => ("XMD", "sha256"),
+        ExpID::XMD(HashID::SHA384) => ("XMD", "sha384"),
+        ExpID::XMD(HashID::SHA512) => ("XMD", "sha512"),
+        ExpID::XOF(XofID::SHAKE128) => ("XOF", "shake_128"),
+        ExpID::XOF(XofID::SHAKE256) => ("XOF", "shake_256"),
+    }
+}
+
+/// Returns the name of the file holding the test vectors of a suite, e.g.,
+/// "P256_XMD-SHA-256_SSWU_RO_.json".
+pub fn suite_file_name(suite: &AnySuite) -> String {
+    format!("{}.json", suite.name().replace(':', "-"))
+}
+
+/// Returns the test vectors of a suite for the given DST and messages, in the
+/// JSON format of the files in tests/testdata.
+pub fn suite_vectors(suite: &AnySuite, dst: &str, msgs: &[&str]) -> Result<String, Error> {
+    if suite.is_group() {
+        // The groups output no field elements, so their suites have no
+        // vectors in the format of the draft.
+        return Err(Error::UnsupportedTrace);
+    }
+    let h = suite.try_get(dst.as_bytes())?;
+    let p = modulus(suite);
+    let z = match suite.map() {
+        MapID::SSWU(z) | MapID::SSWUAB0(z) | MapID::SVDW(z) | MapID::ELL2(z) => vec![z],
+        MapID::SSWUAB0FP2(c0, c1) => vec![c0, c1],
+        MapID::R255MAP | MapID::D448MAP => unreachable!("groups were rejected above"),
+    };
+    // The extension degree m is the number of components of Z.
+    let m = z.len() as u32;
+    let z = {
+        let p = BigInt::from(p.clone());
+        let z: Vec<String> = z
+            .iter()
+            .map(|c| format!("{:#x}", ((BigInt::from(*c) % &p) + &p) % &p))
+            .collect();
+        z.join(",")
+    };
+    let map = map_name(suite.map());
+    let (expand, hash) = suite_expander_names(suite.exp());
+    let curve = match suite.curve_name() {
+        "P256" => "NIST P-256",
+        "P384" => "NIST P-384",
+        "P521" => "NIST P-521",
+        "BLS12381G1" => "BLS12-381 G1",
+        "BLS12381G2" => "BLS12-381 G2",
+        name => name,
+    };
+    let json = Json::obj([
+        ("L", Json::hex(suite.l())),
+        ("Z", Json::Str(z)),
+        ("ciphersuite", Json::str(suite.name())),
+        ("curve", Json::str(curve)),
+        ("dst", Json::str(dst)),
+        ("expand", Json::str(expand)),
+        (
+            "field",
+            Json::obj([("m", Json::hex(m)), ("p", Json::hex(p))]),
+        ),
+        ("hash", Json::str(hash)),
+        ("k", Json::hex(suite.k())),
+        ("map", Json::obj([("name", Json::str(map))])),
+        ("randomOracle", Json::Bool(suite.is_random_oracle())),
+        (
+            "vectors",
+            Json::Arr(
+                msgs.iter()
+                    .map(|msg| suite_vector(&h, msg))
+                    .collect::<Result<_, _>>()?,
+            ),
+        ),
+    ]);
+    Ok(json.to_file())
+}
+
+fn suite_vector(h: &AnyHashToCurve, msg: &str) -> Result<Json, Error> {
+    let point = |p: &AnyPoint| -> Result<Json, Error> {
+        Ok(match h.coordinates(p)? {
+            Some((x, y)) => Json::obj([("x", Json::Str(x)), ("y", Json::Str(y))]),
+            None => Json::Null,
+        })
+    };
+    let t = h.try_trace(msg.as_bytes())?;
+    let mut v = BTreeMap::new();
+    v.insert("P", point(&t.p)?);
+    match t.q.as_slice() {
+        [q] => {
+            v.insert("Q", point(q)?);
+        }
+        q => {
+            v.insert("Q0", point(&q[0])?);
+            v.insert("Q1", point(&q[1])?);
+        }
+    }
+    v.insert("msg", Json::str(msg));
+    v.insert("u", Json::Arr(t.u.into_iter().map(Json::Str).collect()));
+    Ok(Json::Obj(v))
+}
+
+fn modulus(suite: &AnySuite) -> BigUint {
+    match suite {
+        AnySuite::Weierstrass(s) => s.curve().get().get_field().get_modulus(),
+        AnySuite::Montgomery(s) => s.curve().get().get_field().get_modulus(),
+        AnySuite::Edwards(s) => s.curve().get().get_field().get_modulus(),
+        AnySuite::BLS12381G2(s) => s.curve().get().get_field().get_modulus(),
+        AnySuite::Ristretto255(_) | AnySuite::Decaf448(_) => {
+            unreachable!("groups have no vectors")
+        }
+    }
+}
+
+/// Returns the expanders of the test vectors of the draft, together with
+/// their DSTs and security levels.
+pub fn default_expanders() -> Vec<(ExpID, String, usize)> {
+    let long = |prefix: &str| format!("{}{}", prefix, "1".repeat(256 - prefix.len()));
+    vec![
+        (
+            ExpID::XMD(HashID::SHA256),
+            "QUUX-V01-CS02-with-expander-SHA256-128".to_string(),
+            128,
+        ),
+        (
+            ExpID::XMD(HashID::SHA256),
+            long("QUUX-V01-CS02-with-expander-SHA256-128-long-DST-"),
+            128,
+        ),
+        (
+            ExpID::XMD(HashID::SHA512),
+            "QUUX-V01-CS02-with-expander-SHA512-256".to_string(),
+            256,
+        ),
+        (
+            ExpID::XOF(XofID::SHAKE128),
+            "QUUX-V01-CS02-with-expander-SHAKE128".to_string(),
+            128,
+        ),
+        (
+            ExpID::XOF(XofID::SHAKE128),
+            long("QUUX-V01-CS02-with-expander-SHAKE128-long-DST-"),
+            128,
+        ),
+        (
+            ExpID::XOF(XofID::SHAKE256),
+            "QUUX-V01-CS02-with-expander-SHAKE256".to_string(),
+            256,
+        ),
+    ]
+}
+
+/// Returns the name of the file holding the test vectors of an expander,
+/// e.g., "expand_message_xmd_SHA256_38.json" for a DST of 38 bytes.
+pub fn expander_file_name(exp: ExpID, dst: &str) -> String {
+    let (name, hash) = expander_names(exp);
+    format!("{}_{}_{}.json", name, hash, dst.len())
+}
+
+/// Returns the test vectors of an expander for the given DST and messages,
+/// expanding each message to every length in `lengths`, in the JSON format
+/// of the files in src/expander/testdata.
+pub fn expander_vectors(
+    exp: ExpID,
+    dst: &str,
+    k: usize,
+    msgs: &[&str],
+    lengths: &[usize],
+) -> Result<String, Error> {
+    let (name, hash) = expander_names(exp);
+    let e = get_expander(exp, dst.as_bytes(), k);
+    let mut tests = Vec::new();
+    for &n in lengths {
+        for msg in msgs {
+            tests.push(Json::obj([
+                ("DST_prime", Json::Str(hex::encode(e.construct_dst_prime()))),
+                ("len_in_bytes", Json::hex(n)),
+                ("msg", Json::str(*msg)),
+                (
+                    "msg_prime",
+                    Json::Str(hex::encode(e.construct_msg_prime(msg.as_bytes(), n))),
+                ),
+                (
+                    "uniform_bytes",
+                    Json::Str(hex::encode(e.try_expand(msg.as_bytes(), n)?)),
+                ),
+            ]));
+        }
+    }
+    let json = Json::obj([
+        ("DST", Json::str(dst)),
+        ("hash", Json::str(hash)),
+        ("k", Json::Int(k)),
+        ("name", Json::str(name)),
+        ("tests", Json::Arr(tests)),
+    ]);
+    Ok(json.to_file())
+}
+
+fn expander_names(exp: ExpID) -> (&'static str, &'static str) {
+    match exp {
+        ExpID::XMD(HashID::SHA256) => ("expand_message_xmd", "SHA256"),
+        ExpID::XMD(HashID::SHA384) => ("expand_message_xmd", "SHA384"),
+        ExpID::XMD(HashID::SHA512) => ("expand_message_xmd", "SHA512"),
+        ExpID::XOF(XofID::SHAKE128) => ("expand_message_xof", "SHAKE128"),
+        ExpID::XOF(XofID::SHAKE256) => ("expand_message_xof", "SHAKE256"),
+    }
+}
@@ -1,51 +1,230 @@
+use std::borrow::Cow;
 use std::collections::HashMap;
 
-use redox_ecc::ellipticcurve::{EllipticCurve, Isogeny, MapToCurve};
-use redox_ecc::instances::{
-    get_isogeny_bls12381g1, get_isogeny_secp256k1, GetCurve, WeCurveID, BLS12381G1, P256, P384,
-    P521, SECP256K1,
-};
-use redox_ecc::ops::FromFactory;
-use redox_ecc::weierstrass::{Curve, SSWU, SSWUAB0, SVDW};
+use num_bigint::{BigInt, BigUint};
 
 use crate::api::{
-    Encoding, ExpID, GetHashToCurve, HashID, HashToCurve, HashToField, MapID, Suite, XofID,
+    CheckMap, ClearCofactor, CtEncoder, Encoding, ExpID, GetHashToCurve, HashID, HashToCurve,
+    HashToField, InverseMapBuilder, LazyInverseMap, MapID, ModulusBits, Suite, XofID,
 };
+use crate::cofactor::{Bls12381G1Cofactor, NoCofactor, BLS12381_Z_ABS};
+use crate::ct::{CtEncoding, CtGf, CtGfElt, CtMap, CtSSWU, CtSVDW, CtWeierstrass};
+use crate::curve::weierstrass::{Curve, Point};
+use crate::curve::{EllipticCurve, GetCurve, MapToCurve};
+use crate::error::Error;
 use crate::expander::get_expander;
-use crate::fp::FpHasher;
+use crate::field::{parse_int, Field};
+use crate::findz::{check_z_sswu, check_z_svdw};
+use crate::fp::{Fp, FpHasher};
+use crate::gf::Gf;
+use crate::inverse::{SSWUAB0Inverse, SSWUInverse, SVDWInverse};
+use crate::isogeny::{IsogenyMap, BLS12381G1_ISOGENY, SECP256K1_ISOGENY};
+use crate::map::{SSWU, SSWUAB0, SVDW};
 use crate::register_in_map;
 
+/// WeCtMap is the constant-time map to curve of a Weierstrass suite.
+type WeCtMap = Box<dyn CtMap<Elt = CtGfElt, Group = CtWeierstrass>>;
+
+/// WeCurveID identifies the Weierstrass curves of the suites, which are
+/// defined over prime fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WeCurveID {
+    P256,
+    P384,
+    P521,
+    Secp256k1,
+    /// The G1 curve of BLS12-381.
+    Bls12381G1,
+}
+
+lazy_static! {
+    static ref P256_FIELD: Gf = gf("0xffffffff00000001000000000000000000000000ffffffffffffffffffffffff");
+    static ref P384_FIELD: Gf = gf("0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff");
+    static ref P521_FIELD: Gf = gf("0x1ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+    static ref SECP256K1_FIELD: Gf = gf("0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f");
+    static ref BLS12381G1_FIELD: Gf = gf("0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+}
+
+/// Returns the prime field of the given characteristic.
+pub(crate) fn gf(p: &str) -> Gf {
+    Gf::new(&BigInt::from(parse_int(p)))
+}
+
+impl WeCurveID {
+    /// Returns the isogeny of the SSWU map for AB == 0, if the curve has one.
+    fn isogeny(&self) -> Option<&'static IsogenyMap> {
+        match self {
+            WeCurveID::Secp256k1 => Some(&SECP256K1_ISOGENY),
+            WeCurveID::Bls12381G1 => Some(&BLS12381G1_ISOGENY),
+            WeCurveID::P256 | WeCurveID::P384 | WeCurveID::P521 => None,
+        }
+    }
+}
+
+impl GetCurve for WeCurveID {
+    type E = Curve;
+    fn get(&self) -> Curve {
+        // The field, A, B, the order r and the cofactor h of the curve.
+        let (f, a, b, r, h): (&'static Gf, i32, &str, &str, &str) = match self {
+            WeCurveID::P256 => (
+                &P256_FIELD,
+                -3,
+                "0x5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b",
+                "0xffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551",
+                "1",
+            ),
+            WeCurveID::P384 => (
+                &P384_FIELD,
+                -3,
+                "0xb3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef",
+                "0xffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973",
+                "1",
+            ),
+            WeCurveID::P521 => (
+                &P521_FIELD,
+                -3,
+                "0x0051953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef109e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b503f00",
+                "0x01fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e91386409",
+                "1",
+            ),
+            WeCurveID::Secp256k1 => (
+                &SECP256K1_FIELD,
+                0,
+                "7",
+                "0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+                "1",
+            ),
+            WeCurveID::Bls12381G1 => (
+                &BLS12381G1_FIELD,
+                0,
+                "4",
+                "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001",
+                "0x396c8c005555e1568c00aaab0000aaab",
+            ),
+        };
+        let f = Fp::new(f);
+        Curve {
+            a: f.from_i32(a),
+            b: f.from(b),
+            r: parse_int(r),
+            h: parse_int(h),
+            f,
+        }
+    }
+}
+
 impl GetHashToCurve for Suite<WeCurveID> {
     type E = Curve;
-    fn get(&self, dst: &[u8]) -> Box<dyn HashToCurve<E = Self::E>> {
+    fn try_get(&self, dst: &[u8]) -> Result<Box<dyn HashToCurve<E = Self::E>>, Error> {
         let curve = self.curve.get();
         let f = curve.get_field();
-        let cofactor = curve.new_scalar(curve.get_cofactor());
-        let map_to_curve: Box<dyn MapToCurve<E = Curve>> = match self.map {
-            MapID::SSWUAB0(z) => {
-                let iso: Box<dyn Isogeny<E0 = Curve, E1 = Curve>> = if self.curve == SECP256K1 {
-                    Box::new(get_isogeny_secp256k1())
-                } else if self.curve == BLS12381G1 {
-                    Box::new(get_isogeny_bls12381g1())
-                } else {
-                    unimplemented!()
+        // The constant-time field, if the suite runs in constant time.
+        let ct_f = self.ct.then(|| CtGf::new(f));
+        let iso = self.curve.isogeny();
+        let (map_to_curve, ct_map): (Box<dyn MapToCurve<E = Curve>>, Option<WeCtMap>) =
+            match self.map {
+                MapID::SSWUAB0(z) => {
+                    let iso = iso.ok_or_else(|| Error::UnsupportedMap(self.name.to_string()))?;
+                    let z = f.from_i32(z);
+                    let ct_map = ct_f
+                        .as_ref()
+                        .map(|g| -> WeCtMap { Box::new(CtSSWU::with_isogeny(g, &curve, iso, &z)) });
+                    (Box::new(SSWUAB0::new(curve.clone(), iso, z)), ct_map)
+                }
+                MapID::SSWU(z) => {
+                    let z = f.from_i32(z);
+                    let ct_map = ct_f
+                        .as_ref()
+                        .map(|g| -> WeCtMap { Box::new(CtSSWU::new(g, &curve, &z)) });
+                    (Box::new(SSWU::new(curve.clone(), z)), ct_map)
+                }
+                MapID::SVDW(z) => {
+                    let svdw = SVDW::new(curve.clone(), f.from_i32(z))?;
+                    let ct_map = ct_f
+                        .as_ref()
+                        .map(|g| -> WeCtMap { Box::new(CtSVDW::new(g, &svdw)) });
+                    (Box::new(svdw), ct_map)
+                }
+                _ => return Err(Error::UnsupportedMap(self.name.to_string())),
+            };
+        let ct = ct_f
+            .zip(ct_map)
+            .map(|(g, m)| -> Box<dyn CtEncoder<Point = Point>> {
+                let h_eff = match self.curve {
+                    WeCurveID::Bls12381G1 => BigUint::from(BLS12381_Z_ABS + 1),
+                    WeCurveID::P256 | WeCurveID::P384 | WeCurveID::P521 | WeCurveID::Secp256k1 => {
+                        BigUint::from(1u32)
+                    }
                 };
-                Box::new(SSWUAB0::new(curve.clone(), f.from(z), iso))
-            }
-            MapID::SSWU(z) => Box::new(SSWU::new(curve.clone(), f.from(z))),
-            MapID::SVDW(z) => Box::new(SVDW::new(curve.clone(), f.from(z))),
-            _ => unimplemented!(),
-        };
+                let exp = get_expander(self.exp, dst, self.k);
+                let hash_to_field = FpHasher {
+                    f: g,
+                    exp,
+                    l: self.l,
+                };
+                Box::new(CtEncoding::new(hash_to_field, m, h_eff))
+            });
         let exp = get_expander(self.exp, dst, self.k);
         let hash_to_field: Box<dyn HashToField<F = <Curve as EllipticCurve>::F>> =
             Box::new(FpHasher { f, exp, l: self.l });
-        Box::new(Encoding {
+        let clear_cofactor: Box<dyn ClearCofactor<Point = <Curve as EllipticCurve>::Point>> =
+            match self.curve {
+                WeCurveID::Bls12381G1 => Box::new(Bls12381G1Cofactor),
+                // These curves have prime order.
+                WeCurveID::P256 | WeCurveID::P384 | WeCurveID::P521 | WeCurveID::Secp256k1 => {
+                    Box::new(NoCofactor::<Curve>::default())
+                }
+            };
+        let e = curve.clone();
+        let inverse_map: Option<InverseMapBuilder<Curve>> = match self.map {
+            MapID::SSWU(z) => Some(Box::new(move || {
+                Ok(Box::new(SSWUInverse::new(e.clone(), z)))
+            })),
+            MapID::SVDW(z) => Some(Box::new(move || {
+                Ok(Box::new(SVDWInverse::new(e.clone(), z)?))
+            })),
+            MapID::SSWUAB0(z) => iso.map(|iso| -> InverseMapBuilder<Curve> {
+                Box::new(move || Ok(Box::new(SSWUAB0Inverse::new(e.clone(), iso, z))))
+            }),
+            _ => None,
+        };
+        Ok(Box::new(Encoding {
             curve,
             hash_to_field,
             map_to_curve,
-            cofactor,
+            clear_cofactor,
+            inverse_map: LazyInverseMap::new(inverse_map),
+            l: self.l,
             ro: self.ro,
-        })
+            ct,
+        }))
+    }
+}
+
+impl ModulusBits for WeCurveID {
+    fn modulus_bits(&self) -> usize {
+        self.get().get_field().get_modulus().bits() as usize
+    }
+}
+
+impl CheckMap for WeCurveID {
+    fn check_map(&self, map: MapID) -> Result<(), Error> {
+        let curve = self.get();
+        let f = curve.get_field();
+        match map {
+            MapID::SSWU(z) => check_z_sswu(&f, &curve.a, &curve.b, &f.from_i32(z)),
+            MapID::SSWUAB0(z) => {
+                let iso = self.isogeny().ok_or_else(|| {
+                    Error::InvalidSuite(format!("{:?} has no isogeny for SSWUAB0", self))
+                })?;
+                check_z_sswu(&f, &f.from(iso.a), &f.from(iso.b), &f.from_i32(z))
+            }
+            MapID::SVDW(z) => check_z_svdw(&f, &curve.a, &curve.b, &f.from_i32(z)),
+            _ => Err(Error::InvalidSuite(format!(
+                "{:?} is not a map to {:?}",
+                map, self
+            ))),
+        }
     }
 }
 
@@ -53,20 +232,26 @@ lazy_static! {
     pub static ref SUITES_WEIERSTRASS: HashMap<String, Suite<WeCurveID>> = register_in_map!([
         P256_XMDSHA256_SSWU_NU_,
         P256_XMDSHA256_SSWU_RO_,
-        P256_XMDSHAKE128_SSWU_NU_,
-        P256_XMDSHAKE128_SSWU_RO_,
+        P256_XOFSHAKE128_SSWU_NU_,
+        P256_XOFSHAKE128_SSWU_RO_,
         P256_XMDSHA256_SVDW_NU_,
         P256_XMDSHA256_SVDW_RO_,
         P384_XMDSHA384_SSWU_NU_,
         P384_XMDSHA384_SSWU_RO_,
+        P384_XOFSHAKE256_SSWU_NU_,
+        P384_XOFSHAKE256_SSWU_RO_,
         P384_XMDSHA384_SVDW_NU_,
         P384_XMDSHA384_SVDW_RO_,
         P521_XMDSHA512_SSWU_NU_,
         P521_XMDSHA512_SSWU_RO_,
+        P521_XOFSHAKE256_SSWU_NU_,
+        P521_XOFSHAKE256_SSWU_RO_,
         P521_XMDSHA512_SVDW_NU_,
         P521_XMDSHA512_SVDW_RO_,
         SECP256K1_XMDSHA256_SSWU_RO_,
         SECP256K1_XMDSHA256_SSWU_NU_,
+        SECP256K1_XOFSHAKE128_SSWU_NU_,
+        SECP256K1_XOFSHAKE128_SSWU_RO_,
         SECP256K1_XMDSHA256_SVDW_RO_,
         SECP256K1_XMDSHA256_SVDW_NU_,
         BLS12381G1_XMDSHA256_SSWU_NU_,
@@ -77,166 +262,225 @@ lazy_static! {
 }
 
 pub static P256_XMDSHA256_SSWU_NU_: Suite<WeCurveID> = Suite {
-    name: "P256_XMD:SHA-256_SSWU_NU_",
-    curve: P256,
+    name: Cow::Borrowed("P256_XMD:SHA-256_SSWU_NU_"),
+    curve: WeCurveID::P256,
     k: 128,
     exp: ExpID::XMD(HashID::SHA256),
     map: MapID::SSWU(-10),
     l: 48,
     ro: false,
+    ct: false,
 };
 pub static P256_XMDSHA256_SSWU_RO_: Suite<WeCurveID> = Suite {
-    name: "P256_XMD:SHA-256_SSWU_RO_",
+    name: Cow::Borrowed("P256_XMD:SHA-256_SSWU_RO_"),
     ro: true,
     ..P256_XMDSHA256_SSWU_NU_
 };
 
-pub static P256_XMDSHAKE128_SSWU_NU_: Suite<WeCurveID> = Suite {
-    name: "P256_XMD:SHAKE128_SSWU_NU_",
-    curve: P256,
+pub static P256_XOFSHAKE128_SSWU_NU_: Suite<WeCurveID> = Suite {
+    name: Cow::Borrowed("P256_XOF:SHAKE128_SSWU_NU_"),
+    curve: WeCurveID::P256,
     k: 128,
     exp: ExpID::XOF(XofID::SHAKE128),
     map: MapID::SSWU(-10),
     l: 48,
     ro: false,
+    ct: false,
 };
-pub static P256_XMDSHAKE128_SSWU_RO_: Suite<WeCurveID> = Suite {
-    name: "P256_XMD:SHAKE128_SSWU_RO_",
+pub static P256_XOFSHAKE128_SSWU_RO_: Suite<WeCurveID> = Suite {
+    name: Cow::Borrowed("P256_XOF:SHAKE128_SSWU_RO_"),
     ro: true,
-    ..P256_XMDSHAKE128_SSWU_NU_
+    ..P256_XOFSHAKE128_SSWU_NU_
 };
 
 pub static P256_XMDSHA256_SVDW_NU_: Suite<WeCurveID> = Suite {
-    name: "P256_XMD:SHA-256_SVDW_NU_",
-    curve: P256,
+    name: Cow::Borrowed("P256_XMD:SHA-256_SVDW_NU_"),
+    curve: WeCurveID::P256,
     k: 128,
     exp: ExpID::XMD(HashID::SHA256),
     map: MapID::SVDW(-3),
     l: 48,
     ro: false,
+    ct: false,
 };
 pub static P256_XMDSHA256_SVDW_RO_: Suite<WeCurveID> = Suite {
-    name: "P256_XMD:SHA-256_SVDW_RO_",
+    name: Cow::Borrowed("P256_XMD:SHA-256_SVDW_RO_"),
     ro: true,
     ..P256_XMDSHA256_SVDW_NU_
 };
 
 pub static P384_XMDSHA384_SSWU_NU_: Suite<WeCurveID> = Suite {
-    name: "P384_XMD:SHA-384_SSWU_NU_",
-    curve: P384,
+    name: Cow::Borrowed("P384_XMD:SHA-384_SSWU_NU_"),
+    curve: WeCurveID::P384,
     k: 192,
     exp: ExpID::XMD(HashID::SHA384),
     map: MapID::SSWU(-12),
     l: 72,
     ro: false,
+    ct: false,
 };
 pub static P384_XMDSHA384_SSWU_RO_: Suite<WeCurveID> = Suite {
-    name: "P384_XMD:SHA-384_SSWU_RO_",
+    name: Cow::Borrowed("P384_XMD:SHA-384_SSWU_RO_"),
     ro: true,
     ..P384_XMDSHA384_SSWU_NU_
 };
 
+pub static P384_XOFSHAKE256_SSWU_NU_: Suite<WeCurveID> = Suite {
+    name: Cow::Borrowed("P384_XOF:SHAKE256_SSWU_NU_"),
+    curve: WeCurveID::P384,
+    k: 192,
+    exp: ExpID::XOF(XofID::SHAKE256),
+    map: MapID::SSWU(-12),
+    l: 72,
+    ro: false,
+    ct: false,
+};
+pub static P384_XOFSHAKE256_SSWU_RO_: Suite<WeCurveID> = Suite {
+    name: Cow::Borrowed("P384_XOF:SHAKE256_SSWU_RO_"),
+    ro: true,
+    ..P384_XOFSHAKE256_SSWU_NU_
+};
+
 pub static P384_XMDSHA384_SVDW_NU_: Suite<WeCurveID> = Suite {
-    name: "P384_XMD:SHA-384_SVDW_NU_",
-    curve: P384,
+    name: Cow::Borrowed("P384_XMD:SHA-384_SVDW_NU_"),
+    curve: WeCurveID::P384,
     k: 192,
     exp: ExpID::XMD(HashID::SHA384),
     map: MapID::SVDW(-1),
     l: 72,
     ro: false,
+    ct: false,
 };
 pub static P384_XMDSHA384_SVDW_RO_: Suite<WeCurveID> = Suite {
-    name: "P384_XMD:SHA-384_SVDW_RO_",
+    name: Cow::Borrowed("P384_XMD:SHA-384_SVDW_RO_"),
     ro: true,
     ..P384_XMDSHA384_SVDW_NU_
 };
 
 pub static P521_XMDSHA512_SSWU_NU_: Suite<WeCurveID> = Suite {
-    name: "P521_XMD:SHA-512_SSWU_NU_",
-    curve: P521,
+    name: Cow::Borrowed("P521_XMD:SHA-512_SSWU_NU_"),
+    curve: WeCurveID::P521,
     k: 256,
     exp: ExpID::XMD(HashID::SHA512),
     map: MapID::SSWU(-4),
     l: 98,
     ro: false,
+    ct: false,
 };
 pub static P521_XMDSHA512_SSWU_RO_: Suite<WeCurveID> = Suite {
-    name: "P521_XMD:SHA-512_SSWU_RO_",
+    name: Cow::Borrowed("P521_XMD:SHA-512_SSWU_RO_"),
     ro: true,
     ..P521_XMDSHA512_SSWU_NU_
 };
 
+pub static P521_XOFSHAKE256_SSWU_NU_: Suite<WeCurveID> = Suite {
+    name: Cow::Borrowed("P521_XOF:SHAKE256_SSWU_NU_"),
+    curve: WeCurveID::P521,
+    k: 256,
+    exp: ExpID::XOF(XofID::SHAKE256),
+    map: MapID::SSWU(-4),
+    l: 98,
+    ro: false,
+    ct: false,
+};
+pub static P521_XOFSHAKE256_SSWU_RO_: Suite<WeCurveID> = Suite {
+    name: Cow::Borrowed("P521_XOF:SHAKE256_SSWU_RO_"),
+    ro: true,
+    ..P521_XOFSHAKE256_SSWU_NU_
+};
+
 pub static P521_XMDSHA512_SVDW_NU_: Suite<WeCurveID> = Suite {
-    name: "P521_XMD:SHA-512_SVDW_NU_",
-    curve: P521,
+    name: Cow::Borrowed("P521_XMD:SHA-512_SVDW_NU_"),
+    curve: WeCurveID::P521,
     k: 256,
     exp: ExpID::XMD(HashID::SHA512),
     map: MapID::SVDW(1),
     l: 98,
     ro: false,
+    ct: false,
 };
 pub static P521_XMDSHA512_SVDW_RO_: Suite<WeCurveID> = Suite {
-    name: "P521_XMD:SHA-512_SVDW_RO_",
+    name: Cow::Borrowed("P521_XMD:SHA-512_SVDW_RO_"),
     ro: true,
     ..P521_XMDSHA512_SVDW_NU_
 };
 
 pub static SECP256K1_XMDSHA256_SSWU_NU_: Suite<WeCurveID> = Suite {
-    name: "secp256k1_XMD:SHA-256_SSWU_NU_",
-    curve: SECP256K1,
+    name: Cow::Borrowed("secp256k1_XMD:SHA-256_SSWU_NU_"),
+    curve: WeCurveID::Secp256k1,
     map: MapID::SSWUAB0(-11),
     k: 128,
     exp: ExpID::XMD(HashID::SHA256),
     l: 48,
     ro: false,
+    ct: false,
 };
 pub static SECP256K1_XMDSHA256_SSWU_RO_: Suite<WeCurveID> = Suite {
-    name: "secp256k1_XMD:SHA-256_SSWU_RO_",
+    name: Cow::Borrowed("secp256k1_XMD:SHA-256_SSWU_RO_"),
     ro: true,
     ..SECP256K1_XMDSHA256_SSWU_NU_
 };
 
+pub static SECP256K1_XOFSHAKE128_SSWU_NU_: Suite<WeCurveID> = Suite {
+    name: Cow::Borrowed("secp256k1_XOF:SHAKE128_SSWU_NU_"),
+    curve: WeCurveID::Secp256k1,
+    map: MapID::SSWUAB0(-11),
+    k: 128,
+    exp: ExpID::XOF(XofID::SHAKE128),
+    l: 48,
+    ro: false,
+    ct: false,
+};
+pub static SECP256K1_XOFSHAKE128_SSWU_RO_: Suite<WeCurveID> = Suite {
+    name: Cow::Borrowed("secp256k1_XOF:SHAKE128_SSWU_RO_"),
+    ro: true,
+    ..SECP256K1_XOFSHAKE128_SSWU_NU_
+};
+
 pub static SECP256K1_XMDSHA256_SVDW_NU_: Suite<WeCurveID> = Suite {
-    name: "secp256k1_XMD:SHA-256_SVDW_NU_",
-    curve: SECP256K1,
+    name: Cow::Borrowed("secp256k1_XMD:SHA-256_SVDW_NU_"),
+    curve: WeCurveID::Secp256k1,
     k: 128,
     exp: ExpID::XMD(HashID::SHA256),
     map: MapID::SVDW(1),
     l: 48,
     ro: false,
+    ct: false,
 };
 pub static SECP256K1_XMDSHA256_SVDW_RO_: Suite<WeCurveID> = Suite {
-    name: "secp256k1_XMD:SHA-256_SVDW_RO_",
+    name: Cow::Borrowed("secp256k1_XMD:SHA-256_SVDW_RO_"),
     ro: true,
     ..SECP256K1_XMDSHA256_SVDW_NU_
 };
 
 pub static BLS12381G1_XMDSHA256_SSWU_NU_: Suite<WeCurveID> = Suite {
-    name: "BLS12381G1_XMD:SHA-256_SSWU_NU_",
-    curve: BLS12381G1,
+    name: Cow::Borrowed("BLS12381G1_XMD:SHA-256_SSWU_NU_"),
+    curve: WeCurveID::Bls12381G1,
     map: MapID::SSWUAB0(11),
     k: 128,
     exp: ExpID::XMD(HashID::SHA256),
     l: 64,
     ro: false,
+    ct: false,
 };
 pub static BLS12381G1_XMDSHA256_SSWU_RO_: Suite<WeCurveID> = Suite {
-    name: "BLS12381G1_XMD:SHA-256_SSWU_RO_",
+    name: Cow::Borrowed("BLS12381G1_XMD:SHA-256_SSWU_RO_"),
     ro: true,
     ..BLS12381G1_XMDSHA256_SSWU_NU_
 };
 
 pub static BLS12381G1_XMDSHA256_SVDW_NU_: Suite<WeCurveID> = Suite {
-    name: "BLS12381G1_XMD:SHA-256_SVDW_NU_",
-    curve: BLS12381G1,
+    name: Cow::Borrowed("BLS12381G1_XMD:SHA-256_SVDW_NU_"),
+    curve: WeCurveID::Bls12381G1,
     k: 128,
     exp: ExpID::XMD(HashID::SHA256),
     map: MapID::SVDW(-3),
     l: 64,
     ro: false,
+    ct: false,
 };
 pub static BLS12381G1_XMDSHA256_SVDW_RO_: Suite<WeCurveID> = Suite {
-    name: "BLS12381G1_XMD:SHA-256_SVDW_RO_",
+    name: Cow::Borrowed("BLS12381G1_XMD:SHA-256_SVDW_RO_"),
     ro: true,
     ..BLS12381G1_XMDSHA256_SVDW_NU_
 };
@@ -1,11 +1,17 @@
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct SuiteVector {
+    #[serde(rename = "L")]
+    pub l: String,
+    #[serde(rename = "Z")]
+    pub z: String,
     pub ciphersuite: String,
     pub curve: String,
     pub dst: String,
+    pub expand: String,
     pub field: Field,
     pub hash: String,
+    pub k: String,
     pub map: Map,
     pub random_oracle: bool,
     pub vectors: Vec<Vector>,
@@ -29,7 +35,24 @@ pub struct Map {
 pub struct Vector {
     #[serde(rename = "P")]
     pub p: P,
+    #[serde(rename = "Q")]
+    pub q: Option<P>,
+    #[serde(rename = "Q0")]
+    pub q0: Option<P>,
+    #[serde(rename = "Q1")]
+    pub q1: Option<P>,
     pub msg: String,
+    pub u: Vec<String>,
+}
+
+impl Vector {
+    /// Returns the points output by map_to_curve, i.e., [Q0, Q1] or [Q].
+    pub fn mapped_points(&self) -> Vec<&P> {
+        [&self.q0, &self.q1, &self.q]
+            .into_iter()
+            .filter_map(|q| q.as_ref())
+            .collect()
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
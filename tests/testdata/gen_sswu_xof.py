@@ -0,0 +1,215 @@
+#!/usr/bin/env python3
+"""Generates the vectors of the XOF suites of the Weierstrass curves.
+
+RFC 9380 has no vectors for P256_XOF:SHAKE128, P384_XOF:SHAKE256,
+P521_XOF:SHAKE256 and secp256k1_XOF:SHAKE128, so this script computes them
+from the specification alone: expand_message_xof (Section 5.3.2),
+hash_to_field (Section 5.2), the Simplified SWU map (Section 6.6.2), the
+3-isogeny of secp256k1 (Appendix E.1) and the affine group law. It does not
+use this crate.
+
+Before writing anything, it checks itself against the vectors of the RFC:
+the XMD suites of the same curves in this directory and the
+expand_message_xof vectors in src/expander/testdata.
+
+Usage (from the root of the repository):
+
+    python3 tests/testdata/gen_sswu_xof.py
+"""
+
+import hashlib
+import json
+import os
+import sys
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+EXPANDER_DIR = os.path.join(HERE, '..', '..', 'src', 'expander', 'testdata')
+
+
+def i2osp(x, n):
+    return x.to_bytes(n, 'big')
+
+
+def expand_message_xmd(h, b_in_bytes, r_in_bytes):
+    def expand(msg, dst, n):
+        if len(dst) > 255:
+            dst = h(b'H2C-OVERSIZE-DST-' + dst).digest()
+        ell = -(-n // b_in_bytes)
+        dst_prime = dst + bytes([len(dst)])
+        b0 = h(b'\0' * r_in_bytes + msg + i2osp(n, 2) + b'\0' + dst_prime).digest()
+        bi = h(b0 + b'\1' + dst_prime).digest()
+        out = bi
+        for i in range(2, ell + 1):
+            bi = h(bytes(x ^ y for x, y in zip(b0, bi)) + bytes([i]) + dst_prime).digest()
+            out += bi
+        return out[:n]
+    return expand
+
+
+def expand_message_xof(h, k):
+    def expand(msg, dst, n):
+        if len(dst) > 255:
+            dst = h(b'H2C-OVERSIZE-DST-' + dst).digest(-(-2 * k // 8))
+        dst_prime = dst + bytes([len(dst)])
+        return h(msg + i2osp(n, 2) + dst_prime).digest(n)
+    return expand
+
+
+EXPANDERS = {
+    'sha256': expand_message_xmd(hashlib.sha256, 32, 64),
+    'sha384': expand_message_xmd(hashlib.sha384, 48, 128),
+    'sha512': expand_message_xmd(hashlib.sha512, 64, 128),
+    'shake_128': expand_message_xof(hashlib.shake_128, 128),
+    'shake_256': expand_message_xof(hashlib.shake_256, 256),
+}
+
+P256 = dict(
+    name='NIST P-256', p=2**256 - 2**224 + 2**192 + 2**96 - 1, A=-3, Z=-10,
+    B=0x5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b)
+P384 = dict(
+    name='NIST P-384', p=2**384 - 2**128 - 2**96 + 2**32 - 1, A=-3, Z=-12,
+    B=int('b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875a'
+          'c656398d8a2ed19d2a85c8edd3ec2aef', 16))
+P521 = dict(
+    name='NIST P-521', p=2**521 - 1, A=-3, Z=-4,
+    B=int('0051953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef1'
+          '09e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b503f00', 16))
+# SSWU maps to the curve E' of Appendix E.1, which is 3-isogenous to
+# secp256k1 (y^2 = x^3 + 7).
+SECP256K1 = dict(
+    name='secp256k1', p=2**256 - 2**32 - 977, Z=-11,
+    A=0x3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533,
+    B=1771, curve_A=0,
+    iso=dict(
+        x_num=[0x8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7,
+               0x07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581,
+               0x534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262,
+               0x8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c],
+        x_den=[0xd35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b,
+               0xedadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14,
+               0x1],
+        y_num=[0x4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c,
+               0xc75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3,
+               0x29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931,
+               0x2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84],
+        y_den=[0xfffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b,
+               0x7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573,
+               0x6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f,
+               0x1]))
+
+
+def sqrt(x, p):
+    # All the fields here have p = 3 mod 4.
+    r = pow(x, (p + 1) // 4, p)
+    return r if r * r % p == x % p else None
+
+
+def sswu(c, u):
+    """map_to_curve_simple_swu of Section 6.6.2, then the isogeny if any."""
+    p = c['p']
+    A, B, Z = c['A'] % p, c['B'] % p, c['Z'] % p
+    tv1 = pow((Z * Z * pow(u, 4, p) + Z * u * u) % p, p - 2, p)
+    if tv1 == 0:
+        x1 = B * pow(Z * A, p - 2, p) % p
+    else:
+        x1 = -B * pow(A, p - 2, p) * (1 + tv1) % p
+    y = sqrt((x1**3 + A * x1 + B) % p, p)
+    x = x1
+    if y is None:
+        x = Z * u * u * x1 % p
+        y = sqrt((x**3 + A * x + B) % p, p)
+    if u % 2 != y % 2:
+        y = -y % p
+    if 'iso' in c:
+        def poly(k):
+            return sum(ki * pow(x, i, p) for i, ki in enumerate(c['iso'][k])) % p
+        x, y = (poly('x_num') * pow(poly('x_den'), p - 2, p) % p,
+                y * poly('y_num') * pow(poly('y_den'), p - 2, p) % p)
+    return (x, y)
+
+
+def add(c, P, Q):
+    p = c['p']
+    if P is None:
+        return Q
+    if Q is None:
+        return P
+    (x1, y1), (x2, y2) = P, Q
+    if x1 == x2:
+        if (y1 + y2) % p == 0:
+            return None
+        l = (3 * x1 * x1 + c.get('curve_A', c['A'])) * pow(2 * y1, p - 2, p) % p
+    else:
+        l = (y2 - y1) * pow(x2 - x1, p - 2, p) % p
+    x3 = (l * l - x1 - x2) % p
+    return (x3, (l * (x1 - x3) - y1) % p)
+
+
+def suite(c, name, hash_, k, L, ro):
+    """Returns the vectors of a suite in the JSON format of the RFC."""
+    p = c['p']
+    n = (p.bit_length() + 7) // 8
+    fe = lambda v: '0x' + format(v, '0%dx' % (2 * n))
+    pt = lambda P: None if P is None else {'x': fe(P[0]), 'y': fe(P[1])}
+    dst = 'QUUX-V01-CS02-with-' + name
+    count = 2 if ro else 1
+    vectors = []
+    for msg in ['', 'abc', 'abcdef0123456789', 'q128_' + 'q' * 128, 'a512_' + 'a' * 512]:
+        b = EXPANDERS[hash_](msg.encode(), dst.encode(), count * L)
+        u = [int.from_bytes(b[i * L:(i + 1) * L], 'big') % p for i in range(count)]
+        Q = [sswu(c, ui) for ui in u]
+        v = {'msg': msg, 'u': [fe(ui) for ui in u]}
+        if ro:
+            v['Q0'], v['Q1'], v['P'] = pt(Q[0]), pt(Q[1]), pt(add(c, Q[0], Q[1]))
+        else:
+            v['Q'] = v['P'] = pt(Q[0])
+        vectors.append(v)
+    return json.dumps({
+        'L': hex(L), 'Z': hex(c['Z'] % p), 'ciphersuite': name, 'curve': c['name'],
+        'dst': dst, 'expand': 'XOF' if hash_.startswith('shake') else 'XMD',
+        'field': {'m': '0x1', 'p': hex(p)}, 'hash': hash_, 'k': hex(k),
+        'map': {'name': 'SSWU'}, 'randomOracle': ro, 'vectors': vectors,
+    }, indent=2, sort_keys=True) + '\n'
+
+
+def check_expanders():
+    for f in sorted(os.listdir(EXPANDER_DIR)):
+        if not f.startswith('expand_message_xof'):
+            continue
+        t = json.load(open(os.path.join(EXPANDER_DIR, f)))
+        expand = EXPANDERS[{'SHAKE128': 'shake_128', 'SHAKE256': 'shake_256'}[t['hash']]]
+        for v in t['tests']:
+            got = expand(v['msg'].encode(), t['DST'].encode(), int(v['len_in_bytes'], 16))
+            if got.hex() != v['uniform_bytes']:
+                sys.exit('%s: mismatch for msg %r' % (f, v['msg']))
+
+
+SUITES = [
+    # curve, name, XMD hash (RFC vectors), XOF tag, XOF hash, k, L
+    (P256, 'P256', 'XMD:SHA-256', 'sha256', 'XOF:SHAKE128', 'shake_128', 128, 48),
+    (P384, 'P384', 'XMD:SHA-384', 'sha384', 'XOF:SHAKE256', 'shake_256', 192, 72),
+    (P521, 'P521', 'XMD:SHA-512', 'sha512', 'XOF:SHAKE256', 'shake_256', 256, 98),
+    (SECP256K1, 'secp256k1', 'XMD:SHA-256', 'sha256', 'XOF:SHAKE128', 'shake_128', 128, 48),
+]
+
+
+def main():
+    check_expanders()
+    for c, curve, xmd_tag, xmd, xof_tag, xof, k, L in SUITES:
+        for enc in ['NU', 'RO']:
+            name = '%s_%s_SSWU_%s_' % (curve, xmd_tag, enc)
+            path = os.path.join(HERE, name.replace(':', '-') + '.json')
+            want = json.load(open(path))
+            got = json.loads(suite(c, name, xmd, k, L, enc == 'RO'))
+            if got['vectors'] != want['vectors']:
+                sys.exit('%s: mismatch with the vectors of the RFC' % name)
+    for c, curve, xmd_tag, xmd, xof_tag, xof, k, L in SUITES:
+        for enc in ['NU', 'RO']:
+            name = '%s_%s_SSWU_%s_' % (curve, xof_tag, enc)
+            with open(os.path.join(HERE, name.replace(':', '-') + '.json'), 'w') as f:
+                f.write(suite(c, name, xof, k, L, enc == 'RO'))
+            print('wrote', name)
+
+
+if __name__ == '__main__':
+    main()
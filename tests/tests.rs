@@ -1,14 +1,28 @@
-use std::collections::HashMap;
 use std::fs::{read_dir, File};
 use std::io::BufReader;
+use std::path::PathBuf;
 
 mod json;
 use json::SuiteVector;
 use libtest_mimic::{run, Arguments, Failed, Trial};
-use redox_ecc::ellipticcurve::EllipticCurve;
-use redox_ecc::ops::FromFactory;
+use num_bigint::{BigInt, BigUint};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 
-use h2c_rust_ref::{GetHashToCurve, SUITES_EDWARDS, SUITES_MONTGOMERY, SUITES_WEIERSTRASS};
+use h2c_rust_ref::{
+    default_expanders, default_messages, expander_file_name, expander_vectors, find_z_ell2,
+    find_z_sswu, find_z_svdw, get_expander, map_name, serialize_rfc7748, serialize_rfc8032,
+    serialize_sec1, serialize_zcash_g1, serialize_zcash_g2, suite_expander_names, suite_vectors,
+    AnyPoint, AnySuite, CheckMap, ClearCofactor, Decaf448, EdCurveID, EllipticCurve, Error, ExpID,
+    Field, GetHashToCurve, GetHashToGroup, HashID, HashToCurve, Hasher, MapID, ModulusBits,
+    MtCurveID, MulByCofactor, PrimeOrderGroup, Ristretto255, SigmaCofactor, Suite, WeCurve,
+    WeCurveID, XofID, BLS12381G1_XMDSHA256_SSWU_RO_, BLS12381G1_XMDSHA256_SVDW_RO_, BLS12381G2,
+    BLS12381G2_XMDSHA256_SSWU_RO_, CURVE25519_XMDSHA512_ELL2_RO_, CURVE448_XOFSHAKE256_ELL2_RO_,
+    DECAF448_XOFSHAKE256_D448MAP_RO_, EDWARDS25519_XMDSHA512_ELL2_RO_,
+    EDWARDS448_XOFSHAKE256_ELL2_RO_, P256_XMDSHA256_SSWU_RO_, P256_XMDSHA256_SVDW_RO_,
+    P384_XMDSHA384_SSWU_RO_, RISTRETTO255_XMDSHA512_R255MAP_RO_, SECP256K1_XMDSHA256_SSWU_RO_,
+    SECP256K1_XMDSHA256_SVDW_RO_, SUITES, SUITES_DECAF448, SUITES_RISTRETTO255,
+};
 
 #[test]
 fn suites() {
@@ -16,54 +30,767 @@ fn suites() {
     let mut tests_weierstrass = Vec::<Trial>::new();
     let mut tests_montgomery = Vec::<Trial>::new();
     let mut tests_edwards = Vec::<Trial>::new();
+    let mut tests_bls12381g2 = Vec::<Trial>::new();
     let mut tests_ignored = Vec::<Trial>::new();
 
-    for filename in read_dir("./tests/testdata").unwrap() {
-        let file = File::open(filename.unwrap().path()).unwrap();
+    for path in json_files("./tests/testdata") {
+        let file = File::open(path).unwrap();
         let u: SuiteVector = serde_json::from_reader(BufReader::new(file)).unwrap();
-        let key = u.ciphersuite.clone();
         let name = u.ciphersuite.clone();
-        if SUITES_WEIERSTRASS.contains_key(&key) {
-            tests_weierstrass.push(Trial::test(name, move || tt(&SUITES_WEIERSTRASS, &u)));
-        } else if SUITES_MONTGOMERY.contains_key(&key) {
-            tests_montgomery.push(Trial::test(name, move || tt(&SUITES_MONTGOMERY, &u)));
-        } else if SUITES_EDWARDS.contains_key(&key) {
-            tests_edwards.push(Trial::test(name, move || tt(&SUITES_EDWARDS, &u)));
-        } else {
-            tests_ignored
-                .push(Trial::test(name, move || Err("ignored".into())).with_ignored_flag(true));
+        // The constant-time hasher must give the same points.
+        match SUITES.get(&u.ciphersuite).cloned() {
+            Some(AnySuite::Weierstrass(s)) => {
+                tests_weierstrass.push(Trial::test(name, move || {
+                    tt(&*s.get(u.dst.as_bytes()), &u)?;
+                    tt(&*s.constant_time(true).get(u.dst.as_bytes()), &u)
+                }))
+            }
+            Some(AnySuite::Montgomery(s)) => tests_montgomery.push(Trial::test(name, move || {
+                tt(&*s.get(u.dst.as_bytes()), &u)?;
+                tt(&*s.constant_time(true).get(u.dst.as_bytes()), &u)
+            })),
+            Some(AnySuite::Edwards(s)) => tests_edwards.push(Trial::test(name, move || {
+                tt(&*s.get(u.dst.as_bytes()), &u)?;
+                tt(&*s.constant_time(true).get(u.dst.as_bytes()), &u)
+            })),
+            Some(AnySuite::BLS12381G2(s)) => tests_bls12381g2.push(Trial::test(name, move || {
+                tt(&*s.get(u.dst.as_bytes()), &u)?;
+                tt(&*s.constant_time(true).get(u.dst.as_bytes()), &u)
+            })),
+            // The groups have no field elements, hence no vectors.
+            Some(AnySuite::Ristretto255(_) | AnySuite::Decaf448(_)) | None => tests_ignored
+                .push(Trial::test(name, move || Err("ignored".into())).with_ignored_flag(true)),
         }
     }
 
     run(&args, tests_weierstrass).exit_if_failed();
     run(&args, tests_edwards).exit_if_failed();
     run(&args, tests_montgomery).exit_if_failed();
+    run(&args, tests_bls12381g2).exit_if_failed();
     run(&args, tests_ignored).exit_if_failed();
 }
 
-fn tt<T>(s: &HashMap<String, T>, u: &SuiteVector) -> Result<(), Failed>
-where
-    T: GetHashToCurve,
-    for<'a> <<T as GetHashToCurve>::E as EllipticCurve>::F: FromFactory<&'a str>,
-{
-    match s.get(&u.ciphersuite) {
-        None => Err("suite not found".into()),
-        Some(suite) => {
-            let h2c = suite.get(u.dst.as_bytes());
-            let curve = h2c.get_curve();
-            let f = curve.get_field();
-            for v in u.vectors.iter() {
-                let got = h2c.hash(v.msg.as_bytes());
-                let x = f.from(&v.p.x);
-                let y = f.from(&v.p.y);
-                let want = curve.new_point(x, y);
-                if got != want {
-                    return Err(
-                        format!("Suite: {}\ngot:  {}\nwant: {}", u.ciphersuite, got, want).into(),
-                    );
+/// Returns the vector files of a directory, skipping the scripts and notes
+/// that sit next to them.
+fn json_files(dir: &str) -> Vec<PathBuf> {
+    read_dir(dir)
+        .unwrap()
+        .map(|f| f.unwrap().path())
+        .filter(|p| p.extension().is_some_and(|e| e == "json"))
+        .collect()
+}
+
+/// Checks the constants of a suite against the parameters of its vectors.
+fn check_params(u: &SuiteVector) -> Result<(), Failed> {
+    let suite = &SUITES[&u.ciphersuite];
+    let p = BigInt::parse_bytes(u.field.p.trim_start_matches("0x").as_bytes(), 16).unwrap();
+    let (m, bits) = match suite {
+        AnySuite::Weierstrass(s) => (1, s.curve().modulus_bits()),
+        AnySuite::Montgomery(s) => (1, s.curve().modulus_bits()),
+        AnySuite::Edwards(s) => (1, s.curve().modulus_bits()),
+        AnySuite::BLS12381G2(s) => (2, s.curve().modulus_bits()),
+        AnySuite::Ristretto255(_) | AnySuite::Decaf448(_) => {
+            unreachable!("groups have no vectors")
+        }
+    };
+    let z = match suite.map() {
+        MapID::SSWU(z) | MapID::SSWUAB0(z) | MapID::SVDW(z) | MapID::ELL2(z) => vec![z],
+        MapID::SSWUAB0FP2(c0, c1) => vec![c0, c1],
+        MapID::R255MAP | MapID::D448MAP => unreachable!("not a suite of a curve"),
+    };
+    if z.len() != m {
+        return Err(format!("Suite: {}\nZ has {} components", u.ciphersuite, z.len()).into());
+    }
+    let z: Vec<String> = z
+        .iter()
+        .map(|c| format!("{:#x}", (BigInt::from(*c) + &p) % &p))
+        .collect();
+    let z = z.join(",");
+    let (expand, hash) = suite_expander_names(suite.exp());
+    let checks = [
+        ("L", format!("{:#x}", suite.l()), &u.l),
+        ("k", format!("{:#x}", suite.k()), &u.k),
+        ("Z", z, &u.z),
+        ("map", map_name(suite.map()).to_string(), &u.map.name),
+        ("expand", expand.to_string(), &u.expand),
+        ("hash", hash.to_string(), &u.hash),
+        ("m", format!("{:#x}", m), &u.field.m),
+        ("log2(p)", bits.to_string(), &p.bits().to_string()),
+        (
+            "randomOracle",
+            suite.is_random_oracle().to_string(),
+            &u.random_oracle.to_string(),
+        ),
+    ];
+    for (name, got, want) in checks.iter() {
+        if got != *want {
+            return Err(format!(
+                "Suite: {}\n{}\ngot:  {}\nwant: {}",
+                u.ciphersuite, name, got, want
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Hashes msg in every way the hasher offers: in one shot, in chunks, and
+/// with hash_to_curve or encode_to_curve.
+fn hashes<E: EllipticCurve>(h2c: &dyn HashToCurve<E = E>, msg: &[u8], ro: bool) -> Vec<E::Point> {
+    let mut hasher = Hasher::new(h2c);
+    for chunk in msg.chunks(5) {
+        hasher.update(chunk);
+    }
+    let encoded = if ro {
+        h2c.hash_to_curve(msg)
+    } else {
+        h2c.encode_to_curve(msg)
+    };
+    vec![h2c.hash(msg), hasher.finalize(), encoded]
+}
+
+fn tt<E: EllipticCurve>(h2c: &dyn HashToCurve<E = E>, u: &SuiteVector) -> Result<(), Failed> {
+    check_params(u)?;
+    let (e, f) = (h2c.get_curve(), h2c.get_curve().get_field());
+    let msgs: Vec<&[u8]> = u.vectors.iter().map(|v| v.msg.as_bytes()).collect();
+    let batch = h2c.hash_batch(&msgs);
+    for (v, batched) in u.vectors.iter().zip(batch) {
+        let trace = h2c.trace(v.msg.as_bytes());
+        if trace.u.len() != v.u.len() || trace.q.len() != v.mapped_points().len() {
+            return Err(format!("Suite: {}\nwrong number of u or Q", u.ciphersuite).into());
+        }
+        for (got, want) in trace.u.iter().zip(v.u.iter()) {
+            let want = f.from(want);
+            if *got != want {
+                return Err(
+                    format!("Suite: {}\nu\ngot:  {}\nwant: {}", u.ciphersuite, got, want).into(),
+                );
+            }
+        }
+        let points = trace.q.iter().zip(&trace.u).zip(v.mapped_points());
+        for (i, ((got, ui), want)) in points.enumerate() {
+            let want = e.new_point(f.from(&want.x), f.from(&want.y));
+            if *got != want || h2c.map_to_curve(ui) != want {
+                return Err(format!(
+                    "Suite: {}\nQ{}\ngot:  {}\nwant: {}",
+                    u.ciphersuite, i, got, want
+                )
+                .into());
+            }
+        }
+        let want = e.new_point(f.from(&v.p.x), f.from(&v.p.y));
+        let mut hashes = hashes(h2c, v.msg.as_bytes(), u.random_oracle);
+        hashes.extend([trace.p, batched]);
+        if let Some(got) = hashes.into_iter().find(|got| *got != want) {
+            return Err(format!("Suite: {}\ngot:  {}\nwant: {}", u.ciphersuite, got, want).into());
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn hash_to_scalar() {
+    // DeriveKeyPair for P256-SHA256 in OPRF mode, from Appendix A.3.1 of RFC 9497.
+    let dst = b"DeriveKeyPairOPRFV1-\x00-P256-SHA256";
+    let seed = [0xa3u8; 32];
+    let info = b"test key";
+    let mut derive_input = seed.to_vec();
+    derive_input.extend_from_slice(&(info.len() as u16).to_be_bytes());
+    derive_input.extend_from_slice(info);
+    derive_input.push(0);
+
+    let h = P256_XMDSHA256_SSWU_RO_.get_scalar(dst);
+    let got = h.hash(&derive_input, 1);
+    let want = BigUint::parse_bytes(
+        b"159749d750713afe245d2d39ccfaae8381c53ce92d098a9375ee70739c7ac0bf",
+        16,
+    )
+    .unwrap();
+    assert_eq!(got, vec![want]);
+}
+
+#[test]
+fn registry() {
+    let dst = b"QUUX-V01-CS02-with-edwards25519_XMD:SHA-512_ELL2_RO_";
+    let suite = SUITES.get("edwards25519_XMD:SHA-512_ELL2_RO_").unwrap();
+    assert_eq!(suite.curve_name(), "edwards25519");
+    assert_eq!((suite.k(), suite.l()), (128, 48));
+    assert!(matches!(suite.exp(), ExpID::XMD(HashID::SHA512)));
+    assert!(matches!(suite.map(), MapID::ELL2(_)));
+    assert!(suite.is_random_oracle());
+
+    let got = suite.get(dst).hash(b"abc");
+    let want = EDWARDS25519_XMDSHA512_ELL2_RO_.get(dst).hash(b"abc");
+    assert!(got == AnyPoint::Edwards(want));
+    assert!(SUITES.get("edwards25519_XMD:SHA-512_ELL2_XX_").is_none());
+
+    let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+    let h = SUITES["P256_XMD:SHA-256_SSWU_RO_"].get(dst);
+    let trace = h.trace(b"abc");
+    assert_eq!(
+        trace.u[1],
+        "0x379a27833b0bfe6f7bdca08e1e83c760bf9a338ab335542704edcd69ce9e46e0"
+    );
+    let (x, y) = h.coordinates(&trace.q[1]).unwrap().unwrap();
+    assert_eq!(
+        x,
+        "0x019b7cb4efcfeaf39f738fe638e31d375ad6837f58a852d032ff60c69ee3875f"
+    );
+    assert_eq!(
+        y,
+        "0x589a62d2b22357fed5449bc38065b760095ebe6aeac84b01156ee4252715446e"
+    );
+    assert!(trace.p == h.hash(b"abc"));
+    assert_eq!(h.coordinates(&got), Err(Error::PointMismatch));
+}
+
+#[test]
+fn suite_builder() {
+    fn rebuild<T: ModulusBits + CheckMap + Copy>(s: &Suite<T>) -> Result<Suite<T>, Error> {
+        Suite::builder(s.name().to_string(), *s.curve(), s.map(), s.exp())
+            .k(s.k())
+            .l(s.l())
+            .random_oracle(s.is_random_oracle())
+            .build()
+    }
+    for s in SUITES.values() {
+        let ok = match s {
+            AnySuite::Weierstrass(s) => rebuild(s).is_ok(),
+            AnySuite::Montgomery(s) => rebuild(s).is_ok(),
+            AnySuite::Edwards(s) => rebuild(s).is_ok(),
+            AnySuite::BLS12381G2(s) => rebuild(s).is_ok(),
+            // The groups take L from their encoding, not from p and k.
+            AnySuite::Ristretto255(_) | AnySuite::Decaf448(_) => continue,
+        };
+        assert!(ok, "{}", s);
+    }
+
+    let s = &P256_XMDSHA256_SSWU_RO_;
+    let b = Suite::builder("P256_XMD:SHA-256_SSWU_RO_", *s.curve(), s.map(), s.exp())
+        .random_oracle(true);
+    let custom = b.clone().build().unwrap();
+    assert_eq!(custom.l(), 48);
+    let dst = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+    assert!(custom.get(dst).hash(b"abc") == s.get(dst).hash(b"abc"));
+    assert!(matches!(
+        b.clone().l(32).build(),
+        Err(Error::InvalidSuite(_))
+    ));
+    assert!(matches!(
+        b.clone().k(256).build(),
+        Err(Error::InvalidSuite(_))
+    ));
+
+    // The suffix of the suite ID must match the encoding type.
+    assert!(matches!(
+        b.random_oracle(false).build(),
+        Err(Error::InvalidSuite(_))
+    ));
+
+    // The map must apply to the curve, and its Z must meet the criteria of
+    // Appendix H of the draft.
+    let invalid = [
+        (WeCurveID::P256, MapID::SSWU(-3)),
+        (WeCurveID::P256, MapID::SVDW(1)),
+        (WeCurveID::P256, MapID::SSWUAB0(-10)),
+        (WeCurveID::P256, MapID::ELL2(2)),
+        (WeCurveID::Secp256k1, MapID::SSWU(-11)),
+        (WeCurveID::Secp256k1, MapID::SSWUAB0(-10)),
+    ];
+    for (curve, map) in invalid {
+        let b = Suite::builder("P256_XMD:SHA-256_MAP_RO_", curve, map, s.exp());
+        let res = b.random_oracle(true).build();
+        assert!(matches!(res, Err(Error::InvalidSuite(_))), "{:?}", map);
+    }
+    let b = Suite::builder(
+        "P256_XMD:SHA-256_MAP_RO_",
+        MtCurveID::Curve25519,
+        MapID::ELL2(-1),
+        s.exp(),
+    );
+    assert!(matches!(b.build(), Err(Error::InvalidSuite(_))));
+    let b = Suite::builder(
+        "E_XMD:SHA-256_MAP_NU_",
+        EdCurveID::Edwards25519,
+        MapID::SSWU(2),
+        s.exp(),
+    );
+    assert!(matches!(b.build(), Err(Error::InvalidSuite(_))));
+    let b = Suite::builder(
+        "G2_XMD:SHA-256_MAP_NU_",
+        BLS12381G2,
+        MapID::SSWUAB0FP2(-2, 0),
+        s.exp(),
+    );
+    assert!(matches!(b.build(), Err(Error::InvalidSuite(_))));
+
+    // The suite ID may be computed at runtime.
+    let name = format!("{}_{}_SSWU_NU_", "P256", s.exp().suite_tag());
+    let b = Suite::builder(name.clone(), *s.curve(), s.map(), s.exp());
+    assert_eq!(b.build().unwrap().name(), name);
+
+    // The suite ID must name the expander of the suite.
+    let xof = ExpID::XOF(XofID::SHAKE128);
+    let b = Suite::builder("P256_XMD:SHAKE128_SSWU_NU_", *s.curve(), s.map(), xof);
+    assert!(matches!(b.build(), Err(Error::InvalidSuite(_))));
+    let b = Suite::builder("P256_XOF:SHAKE128_SSWU_NU_", *s.curve(), s.map(), xof);
+    assert!(b.build().is_ok());
+    for s in SUITES.values().filter(|s| s.is_group()) {
+        let tag = format!("_{}_", s.exp().suite_tag());
+        assert!(s.name().contains(&tag), "{}", s);
+    }
+}
+
+#[test]
+fn find_z() {
+    let z = |name: &str| match SUITES[name].map() {
+        MapID::SSWU(z) | MapID::SSWUAB0(z) | MapID::SVDW(z) | MapID::ELL2(z) => z,
+        MapID::SSWUAB0FP2(..) | MapID::R255MAP | MapID::D448MAP => {
+            unreachable!("no Z is searched for these maps")
+        }
+    };
+    let hex = |s: &str| BigInt::parse_bytes(s.as_bytes(), 16).unwrap();
+    let one = BigInt::from(1);
+
+    let p = (&one << 256) - (&one << 224) + (&one << 192) + (&one << 96) - 1;
+    let a = BigInt::from(-3);
+    let b = hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b");
+    assert_eq!(find_z_sswu(&p, &a, &b), Ok(z("P256_XMD:SHA-256_SSWU_RO_")));
+    assert_eq!(find_z_svdw(&p, &a, &b), z("P256_XMD:SHA-256_SVDW_RO_"));
+
+    let p = (&one << 384) - (&one << 128) - (&one << 96) + (&one << 32) - 1;
+    let b = hex("b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef");
+    assert_eq!(find_z_sswu(&p, &a, &b), Ok(z("P384_XMD:SHA-384_SSWU_RO_")));
+    assert_eq!(find_z_svdw(&p, &a, &b), z("P384_XMD:SHA-384_SVDW_RO_"));
+
+    let p = (&one << 521) - 1;
+    let b = hex("0051953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef109e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b503f00");
+    assert_eq!(find_z_sswu(&p, &a, &b), Ok(z("P521_XMD:SHA-512_SSWU_RO_")));
+    assert_eq!(find_z_svdw(&p, &a, &b), z("P521_XMD:SHA-512_SVDW_RO_"));
+
+    // SSWU is applied to a curve isogenous to secp256k1 and BLS12-381 G1.
+    let p = (&one << 256) - (&one << 32) - 977;
+    let (a, b) = (BigInt::from(0), BigInt::from(7));
+    let (a_iso, b_iso) = (
+        hex("3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533"),
+        BigInt::from(1771),
+    );
+    assert_eq!(
+        find_z_sswu(&p, &a_iso, &b_iso),
+        Ok(z("secp256k1_XMD:SHA-256_SSWU_RO_"))
+    );
+    assert_eq!(find_z_svdw(&p, &a, &b), z("secp256k1_XMD:SHA-256_SVDW_RO_"));
+    assert!(matches!(
+        find_z_sswu(&p, &a, &b),
+        Err(Error::InvalidSuite(_))
+    ));
+
+    let p = hex("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab");
+    let (a, b) = (BigInt::from(0), BigInt::from(4));
+    let (a_iso, b_iso) = (
+        hex("144698a3b8e9433d693a02c96d4982b0ea985383ee66a8d8e8981aefd881ac98936f8da0e0f97f5cf428082d584c1d"),
+        hex("12e2908d11688030018b12e8753eee3b2016c1f0f24f4070a0b9c14fcef35ef55a23215a316ceaa5d1cc48e98e172be0"),
+    );
+    assert_eq!(
+        find_z_sswu(&p, &a_iso, &b_iso),
+        Ok(z("BLS12381G1_XMD:SHA-256_SSWU_RO_"))
+    );
+    assert_eq!(
+        find_z_svdw(&p, &a, &b),
+        z("BLS12381G1_XMD:SHA-256_SVDW_RO_")
+    );
+
+    let p = (&one << 255) - 19;
+    assert_eq!(find_z_ell2(&p), z("curve25519_XMD:SHA-512_ELL2_RO_"));
+    assert_eq!(find_z_ell2(&p), z("edwards25519_XMD:SHA-512_ELL2_RO_"));
+    let p = (&one << 448) - (&one << 224) - 1;
+    assert_eq!(find_z_ell2(&p), z("curve448_XOF:SHAKE256_ELL2_RO_"));
+    assert_eq!(find_z_ell2(&p), z("edwards448_XOF:SHAKE256_ELL2_RO_"));
+}
+
+#[test]
+fn serialize() {
+    let dst = b"";
+    let h = P256_XMDSHA256_SSWU_RO_.get(dst);
+    let (e, f) = (h.get_curve(), h.get_curve().get_field());
+    let g = e.new_point(
+        f.from("0x6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"),
+        f.from("0x4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5"),
+    );
+    assert_eq!(
+        hex::encode(serialize_sec1(e, &g, true)),
+        "036b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296"
+    );
+    assert_eq!(serialize_sec1(e, &g, false).len(), 65);
+    assert_eq!(serialize_sec1(e, &e.identity(), true), [0x00]);
+
+    let h = BLS12381G1_XMDSHA256_SSWU_RO_.get(dst);
+    let (e, f) = (h.get_curve(), h.get_curve().get_field());
+    let g = e.new_point(
+        f.from("0x17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"),
+        f.from("0x08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"),
+    );
+    assert_eq!(
+        hex::encode(serialize_zcash_g1(e, &g, true)),
+        "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"
+    );
+    assert_eq!(serialize_zcash_g1(e, &e.identity(), true)[0], 0xc0);
+
+    let h = BLS12381G2_XMDSHA256_SSWU_RO_.get(dst);
+    let (e, f) = (h.get_curve(), h.get_curve().get_field());
+    let g = e.new_point(
+        f.from("0x024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8,0x13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e"),
+        f.from("0x0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801,0x0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be"),
+    );
+    assert_eq!(
+        hex::encode(serialize_zcash_g2(&g, true)),
+        "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8"
+    );
+    assert_eq!(serialize_zcash_g2(&g, false).len(), 192);
+
+    let h = EDWARDS25519_XMDSHA512_ELL2_RO_.get(dst);
+    let (e, f) = (h.get_curve(), h.get_curve().get_field());
+    let g = e.new_point(
+        f.from("0x216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a"),
+        f.from("0x6666666666666666666666666666666666666666666666666666666666666658"),
+    );
+    assert_eq!(
+        hex::encode(serialize_rfc8032(e, &g)),
+        "5866666666666666666666666666666666666666666666666666666666666666"
+    );
+
+    let h = EDWARDS448_XOFSHAKE256_ELL2_RO_.get(dst);
+    let (e, f) = (h.get_curve(), h.get_curve().get_field());
+    let g = e.new_point(
+        f.from("0x4f1970c66bed0ded221d15a622bf36da9e146570470f1767ea6de324a3d3a46412ae1af72ab66511433b80e18b00938e2626a82bc70cc05e"),
+        f.from("0x693f46716eb6bc248876203756c9c7624bea73736ca3984087789c1e05a0c2d73ad3ff1ce67c39c4fdbd132c4ed7c8ad9808795bf230fa14"),
+    );
+    assert_eq!(
+        hex::encode(serialize_rfc8032(e, &g)),
+        "14fa30f25b790898adc8d74e2c13bdfdc4397ce61cffd33ad7c2a0051e9c78874098a36c7373ea4b62c7c9563720768824bcb66e71463f6900"
+    );
+
+    let h = CURVE25519_XMDSHA512_ELL2_RO_.get(dst);
+    let (e, f) = (h.get_curve(), h.get_curve().get_field());
+    let g = e.new_point(
+        f.from("9"),
+        f.from("0x20ae19a1b8a086b4e01edd2c7748d14c923d4d7e6d7c61b229e9c5a27eced3d9"),
+    );
+    let mut want = [0u8; 32];
+    want[0] = 9;
+    assert_eq!(serialize_rfc7748(e, &g), want);
+
+    let h = CURVE448_XOFSHAKE256_ELL2_RO_.get(dst);
+    let (e, f) = (h.get_curve(), h.get_curve().get_field());
+    let g = e.new_point(
+        f.from("5"),
+        f.from("0x82dca2ed6a0a4e0993675491a7cd90313451a2cb0aaaba2f9f08a23c720c091247fd81dcb9bcf2deeced3b4eaf9885089028ddc2ba84a4e5"),
+    );
+    let mut want = [0u8; 56];
+    want[0] = 5;
+    assert_eq!(serialize_rfc7748(e, &g), want);
+}
+
+#[test]
+fn vectors() {
+    for path in json_files("./tests/testdata") {
+        let want = std::fs::read_to_string(path).unwrap();
+        let u: SuiteVector = serde_json::from_str(&want).unwrap();
+        if let Some(suite) = SUITES.get(&u.ciphersuite) {
+            let msgs: Vec<&str> = u.vectors.iter().map(|v| v.msg.as_str()).collect();
+            let got = suite_vectors(suite, &u.dst, &msgs).unwrap();
+            assert!(got == want, "{}", u.ciphersuite);
+        }
+    }
+    let msgs = default_messages();
+    let msgs: Vec<&str> = msgs.iter().map(|m| m.as_str()).collect();
+    for (exp, dst, k) in default_expanders() {
+        let name = expander_file_name(exp, &dst);
+        let want = std::fs::read_to_string(format!("./src/expander/testdata/{}", name)).unwrap();
+        let got = expander_vectors(exp, &dst, k, &msgs, &[0x20, 0x80]).unwrap();
+        assert!(got == want, "{}", name);
+    }
+}
+
+#[test]
+fn constant_time() {
+    // This also covers the SVDW suites, which have no vectors.
+    fn check<T: Copy>(suite: &Suite<T>)
+    where
+        Suite<T>: GetHashToCurve,
+    {
+        let msgs: [&[u8]; 2] = [b"", b"abc"];
+        let want = suite.get(b"dst").hash_batch(&msgs);
+        let ct = suite.constant_time(true);
+        assert!(ct.is_constant_time());
+        assert!(ct.get(b"dst").hash_batch(&msgs) == want, "{}", suite);
+    }
+
+    fn check_group<G: PrimeOrderGroup>(suite: &Suite<G>) {
+        let ct = suite.constant_time(true).try_get(b"dst");
+        assert!(
+            matches!(ct, Err(Error::UnsupportedConstantTime(_))),
+            "{}",
+            suite
+        );
+    }
+
+    // The groups are the only suites without a constant-time mode.
+    for s in SUITES.values() {
+        match s {
+            AnySuite::Weierstrass(s) => check(s),
+            AnySuite::Montgomery(s) => check(s),
+            AnySuite::Edwards(s) => check(s),
+            AnySuite::BLS12381G2(s) => check(s),
+            AnySuite::Ristretto255(s) => check_group(s),
+            AnySuite::Decaf448(s) => check_group(s),
+        }
+    }
+}
+
+#[test]
+fn hash_batch() {
+    // The SVDW suites have no vectors to check their batches against.
+    fn check<T: GetHashToCurve>(suite: &T) {
+        let h = suite.get(b"dst");
+        let msgs: [&[u8]; 3] = [b"", b"abc", b"abcdef0123456789"];
+        let want: Vec<_> = msgs.iter().map(|msg| h.hash(msg)).collect();
+        assert!(h.hash_batch(&msgs) == want);
+        assert!(h.hash_batch(&[]).is_empty());
+    }
+    check(&P256_XMDSHA256_SVDW_RO_);
+    check(&SECP256K1_XMDSHA256_SVDW_RO_);
+    check(&BLS12381G1_XMDSHA256_SVDW_RO_);
+    check(&EDWARDS448_XOFSHAKE256_ELL2_RO_);
+}
+
+#[test]
+fn clear_cofactor() {
+    fn check<T: GetHashToCurve>(suite: &T) {
+        let h = suite.get(b"dst");
+        let curve = h.get_curve();
+        let h_eff = curve.get_cofactor();
+        for msg in [&b""[..], b"abc", b"abcdef0123456789"] {
+            for q in h.trace(msg).q {
+                assert!(h.clear_cofactor(q.clone()) == q * &h_eff);
+            }
+        }
+    }
+    check(&P256_XMDSHA256_SSWU_RO_);
+    check(&SECP256K1_XMDSHA256_SSWU_RO_);
+    check(&CURVE25519_XMDSHA512_ELL2_RO_);
+    check(&CURVE448_XOFSHAKE256_ELL2_RO_);
+    check(&EDWARDS25519_XMDSHA512_ELL2_RO_);
+    check(&EDWARDS448_XOFSHAKE256_ELL2_RO_);
+
+    // BLS12-381 multiplies by the effective cofactors of Section 8.8.
+    let h = BLS12381G1_XMDSHA256_SSWU_RO_.get(b"dst");
+    let h_eff = BigUint::from(0xd201000000010001u64);
+    let by_h_eff = MulByCofactor::<WeCurve>::new(h_eff.clone());
+    // The endomorphism sigma multiplies G1 by lambda = z^2 - 1, so
+    // SigmaCofactor gives (1 - lambda)/3 times the points of h_eff.
+    let sigma = SigmaCofactor::bls12381g1();
+    let r = h.get_curve().get_order();
+    let lambda = BigUint::from(0xd201000000010000u64).pow(2) - 1u32;
+    let k = (&r + 1u32 - lambda) * BigUint::from(3u32).modpow(&(&r - 2u32), &r) % &r;
+    for msg in [&b""[..], b"abc", b"abcdef0123456789"] {
+        for q in h.trace(msg).q {
+            assert!(h.clear_cofactor(q.clone()) == q.clone() * &h_eff);
+            assert!(sigma.clear_cofactor(q.clone()) == by_h_eff.clear_cofactor(q) * &k);
+        }
+    }
+
+    let h = BLS12381G2_XMDSHA256_SSWU_RO_.get(b"dst");
+    let h_eff = BigUint::parse_bytes(
+        b"bc69f08f2ee75b3584c6a0ea91b352888e2a8e9145ad7689986ff031508ffe1329c2f178731db956d82bf015d1212b02ec0ec69d7477c1ae954cbc06689f6a359894c0adebbf6b4e8020005aaa95551",
+        16,
+    )
+    .unwrap();
+    for msg in [&b""[..], b"abc", b"abcdef0123456789"] {
+        for q in h.trace(msg).q {
+            assert!(h.clear_cofactor(q.clone()) == q * &h_eff);
+        }
+    }
+}
+
+#[test]
+fn inverse_map() {
+    fn check<T: GetHashToCurve>(suite: &T) {
+        let h = suite.get(b"dst");
+        assert!(h.inverse_branches() > 0);
+        for msg in [&b""[..], b"abc", b"abcdef0123456789"] {
+            let trace = h.trace(msg);
+            for (u, q) in trace.u.iter().zip(trace.q.iter()) {
+                let mut found = false;
+                for branch in 0..h.inverse_branches() {
+                    if let Some(v) = h.inverse_map(q, branch) {
+                        assert!(h.map_to_curve(&v) == *q);
+                        found |= v == *u;
+                    }
                 }
+                assert!(found);
             }
-            Ok(())
         }
     }
+    check(&P256_XMDSHA256_SSWU_RO_);
+    check(&P384_XMDSHA384_SSWU_RO_);
+    check(&CURVE25519_XMDSHA512_ELL2_RO_);
+    check(&CURVE448_XOFSHAKE256_ELL2_RO_);
+    check(&EDWARDS25519_XMDSHA512_ELL2_RO_);
+    check(&EDWARDS448_XOFSHAKE256_ELL2_RO_);
+    check(&SECP256K1_XMDSHA256_SSWU_RO_);
+    check(&BLS12381G1_XMDSHA256_SSWU_RO_);
+}
+
+#[test]
+fn elligator_squared() {
+    fn check<T>(suite: &Suite<T>)
+    where
+        Suite<T>: GetHashToCurve,
+    {
+        let h = suite.get(b"dst");
+        let mut rng = StdRng::seed_from_u64(0);
+        for msg in [&b""[..], b"abc", b"abcdef0123456789"] {
+            let t = h.trace(msg);
+            // R is not cleared of the cofactor.
+            for p in [t.p, t.r] {
+                let b = h.encode_uniform(&p, &mut rng);
+                assert_eq!(b.len(), 2 * suite.l());
+                assert!(h.decode_uniform(&b) == p);
+                assert!(h.encode_uniform(&p, &mut rng) != b);
+            }
+        }
+    }
+    check(&P256_XMDSHA256_SSWU_RO_);
+    check(&P384_XMDSHA384_SSWU_RO_);
+    check(&P256_XMDSHA256_SVDW_RO_);
+    check(&SECP256K1_XMDSHA256_SVDW_RO_);
+    check(&BLS12381G1_XMDSHA256_SVDW_RO_);
+    check(&CURVE25519_XMDSHA512_ELL2_RO_);
+    check(&CURVE448_XOFSHAKE256_ELL2_RO_);
+    check(&EDWARDS25519_XMDSHA512_ELL2_RO_);
+    check(&EDWARDS448_XOFSHAKE256_ELL2_RO_);
+    check(&SECP256K1_XMDSHA256_SSWU_RO_);
+    check(&BLS12381G1_XMDSHA256_SSWU_RO_);
+
+    let h = EDWARDS448_XOFSHAKE256_ELL2_RO_.get(b"dst");
+    let expected = 2 * EDWARDS448_XOFSHAKE256_ELL2_RO_.l();
+    assert!(matches!(
+        h.try_decode_uniform(&[0u8; 7]),
+        Err(Error::InvalidLength { expected: e, got: 7 }) if e == expected
+    ));
+}
+
+#[test]
+fn prime_order_groups() {
+    fn check_multiples<G: PrimeOrderGroup>(g: G, multiples: &[&str]) {
+        let mut p = g.identity();
+        assert!(p.encode() == vec![0; g.encoded_len()]);
+        for want in multiples {
+            p = p + g.generator();
+            assert_eq!(hex::encode(p.encode()), *want);
+            assert!(g.decode(&p.encode()) == Ok(p.clone()));
+        }
+        assert!(g.generator() * &g.get_order() == g.identity());
+    }
+    fn check_uniform<G: PrimeOrderGroup>(g: G, input: &str, want: &str) {
+        let got = g
+            .element_from_uniform_bytes(&hex::decode(input).unwrap())
+            .unwrap();
+        assert_eq!(hex::encode(got.encode()), want);
+    }
+    fn check_hash<G: PrimeOrderGroup>(suite: &Suite<G>)
+    where
+        Suite<G>: GetHashToGroup<G = G>,
+    {
+        let dst = format!("QUUX-V01-CS02-with-{}", suite.name());
+        let exp = get_expander(suite.exp(), dst.as_bytes(), suite.k());
+        let g = *suite.curve();
+        let uniform_bytes = exp.expand(b"abc", g.uniform_bytes_len());
+        let want = g.element_from_uniform_bytes(&uniform_bytes).unwrap();
+        assert!(suite.get(dst.as_bytes()).hash(b"abc") == want);
+    }
+
+    // Multiples of the generator, from Appendix A of RFC 9496.
+    check_multiples(
+        Ristretto255,
+        &[
+            "e2f2ae0a6abc4e71a884a961c500515f58e30b6aa582dd8db6a65945e08d2d76",
+            "6a493210f7499cd17fecb510ae0cea23a110e8d5b901f8acadd3095c73a3b919",
+            "94741f5d5d52755ece4f23f044ee27d5d1ea1e2bd196b462166b16152a9d0259",
+        ],
+    );
+    check_multiples(
+        Decaf448,
+        &[
+            "6666666666666666666666666666666666666666666666666666666633333333333333333333333333333333333333333333333333333333",
+            "c898eb4f87f97c564c6fd61fc7e49689314a1f818ec85eeb3bd5514ac816d38778f69ef347a89fca817e66defdedce178c7cc709b2116e75",
+            "a0c09bf2ba7208fda0f4bfe3d0f5b29a543012306d43831b5adc6fe7f8596fa308763db15468323b11cf6e4aeb8c18fe44678f44545a69bc",
+        ],
+    );
+
+    // Non-canonical and negative encodings are rejected.
+    let mut p = vec![0xff; 32];
+    p[0] = 0xed;
+    p[31] = 0x7f;
+    assert!(Ristretto255.decode(&p).is_err());
+    let mut one = vec![0; 32];
+    one[0] = 1;
+    assert!(Ristretto255.decode(&one).is_err());
+
+    // Elements derived from uniform bytes, from Appendix A of RFC 9496.
+    check_uniform(Ristretto255, "5d1be09e3d0c82fc538112490e35701979d99e06ca3e2b5b54bffe8b4dc772c14d98b696a1bbfb5ca32c436cc61c16563790306c79eaca7705668b47dffe5bb6", "3066f82a1a747d45120d1740f14358531a8f04bbffe6a819f86dfe50f44a0a46");
+    check_uniform(Ristretto255, "f116b34b8f17ceb56e8732a60d913dd10cce47a6d53bee9204be8b44f6678b270102a56902e2488c46120e9276cfe54638286b9e4b3cdb470b542d46c2068d38", "f26e5b6f7d362d2d2a94c5d0e7602cb4773c95a2e5c31a64f133189fa76ed61b");
+    check_uniform(Ristretto255, "8422e1bbdaab52938b81fd602effb6f89110e1e57208ad12d9ad767e2e25510c27140775f9337088b982d83d7fcf0b2fa1edffe51952cbe7365e95c86eaf325c", "006ccd2a9e6867e6a2c5cea83d3302cc9de128dd2a9a57dd8ee7b9d7ffe02826");
+    check_uniform(Decaf448, "cbb8c991fd2f0b7e1913462d6463e4fd2ce4ccdd28274dc2ca1f4165d5ee6cdccea57be3416e166fd06718a31af45a2f8e987e301be59ae6673e963001dbbda80df47014a21a26d6c7eb4ebe0312aa6fffb8d1b26bc62ca40ed51f8057a635a02c2b8c83f48fa6a2d70f58a1185902c0", "0c709c9607dbb01c94513358745b7c23953d03b33e39c7234e268d1d6e24f34014ccbc2216b965dd231d5327e591dc3c0e8844ccfd568848");
+    assert!(matches!(
+        Decaf448.element_from_uniform_bytes(&[0; 64]),
+        Err(Error::InvalidLength {
+            expected: 112,
+            got: 64
+        })
+    ));
+
+    // Hashing expands the message to uniform bytes for the one-way map.
+    check_hash(&RISTRETTO255_XMDSHA512_R255MAP_RO_);
+    check_hash(&DECAF448_XOFSHAKE256_D448MAP_RO_);
+    assert!(SUITES_RISTRETTO255.contains_key("ristretto255_XMD:SHA-512_R255MAP_RO_"));
+    assert!(SUITES_DECAF448.contains_key("decaf448_XOF:SHAKE256_D448MAP_RO_"));
+
+    // The groups are also reachable from the registry, but cannot be traced.
+    let dst = b"QUUX-V01-CS02-with-decaf448_XOF:SHAKE256_D448MAP_RO_";
+    let h = SUITES["decaf448_XOF:SHAKE256_D448MAP_RO_"].get(dst);
+    let p = h.hash(b"abc");
+    assert!(p == AnyPoint::Decaf448(DECAF448_XOFSHAKE256_D448MAP_RO_.get(dst).hash(b"abc")));
+    assert!(h.is_random_oracle());
+    assert!(matches!(h.try_trace(b"abc"), Err(Error::UnsupportedTrace)));
+    assert_eq!(h.coordinates(&p), Err(Error::NoCoordinates));
+}
+
+#[test]
+fn thread_safety() {
+    fn is_send_sync<T: Send + Sync>(_: &T) {}
+    let dst = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+    is_send_sync(&BLS12381G2_XMDSHA256_SSWU_RO_.get(dst));
+    is_send_sync(&P256_XMDSHA256_SSWU_RO_.get(dst));
+    is_send_sync(&CURVE25519_XMDSHA512_ELL2_RO_.get(dst));
+    is_send_sync(&EDWARDS448_XOFSHAKE256_ELL2_RO_.get(dst));
+    is_send_sync(&get_expander(ExpID::XOF(XofID::SHAKE128), dst, 128));
+    is_send_sync(&*SUITES);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_hash() {
+    let msgs: Vec<Vec<u8>> = (0..70u32).map(|i| i.to_be_bytes().to_vec()).collect();
+    let msgs: Vec<&[u8]> = msgs.iter().map(|m| m.as_slice()).collect();
+    for (name, suite) in SUITES.iter() {
+        let dst = format!("QUUX-V01-CS02-with-{}", name);
+        let want: Vec<_> = msgs
+            .iter()
+            .map(|msg| suite.get(dst.as_bytes()).hash(msg))
+            .collect();
+        let got = suite.par_hash(dst.as_bytes(), &msgs);
+        assert!(got == want, "{}", name);
+    }
 }